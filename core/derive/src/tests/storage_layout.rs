@@ -107,6 +107,113 @@ fn named_fields_struct_works() {
     }
 }
 
+#[test]
+fn named_field_rename_works() {
+    synstructure::test_derive! {
+        storage_layout_derive {
+            struct RenamedField {
+                a: bool,
+                #[layout(name = "renamed")]
+                b: u32,
+            }
+        }
+        expands to {
+            #[allow(non_upper_case_globals)]
+            const _DERIVE_ink_core_storage2_traits_StorageLayout_FOR_RenamedField: () = {
+                impl ::ink_core::storage2::traits::StorageLayout for RenamedField {
+                    fn layout(__key_ptr: &mut ::ink_core::storage2::traits::KeyPtr) -> ::ink_abi::layout2::Layout {
+                        ::ink_abi::layout2::Layout::Struct(
+                            ::ink_abi::layout2::StructLayout::new(vec![
+                                ::ink_abi::layout2::FieldLayout::new(
+                                    Some("a"),
+                                    <bool as ::ink_core::storage2::traits::StorageLayout>::layout(__key_ptr),
+                                ),
+                                ::ink_abi::layout2::FieldLayout::new(
+                                    Some("renamed"),
+                                    <u32 as ::ink_core::storage2::traits::StorageLayout>::layout(__key_ptr),
+                                ),
+                            ])
+                        )
+                    }
+                }
+            };
+        }
+    }
+}
+
+#[test]
+fn named_field_skip_works() {
+    synstructure::test_derive! {
+        storage_layout_derive {
+            struct SkippedField {
+                a: bool,
+                #[layout(skip)]
+                b: u32,
+                c: i64,
+            }
+        }
+        expands to {
+            #[allow(non_upper_case_globals)]
+            const _DERIVE_ink_core_storage2_traits_StorageLayout_FOR_SkippedField: () = {
+                impl ::ink_core::storage2::traits::StorageLayout for SkippedField {
+                    fn layout(__key_ptr: &mut ::ink_core::storage2::traits::KeyPtr) -> ::ink_abi::layout2::Layout {
+                        let __field_a = ::ink_abi::layout2::FieldLayout::new(
+                            Some("a"),
+                            <bool as ::ink_core::storage2::traits::StorageLayout>::layout(__key_ptr),
+                        );
+                        // `b` is skipped, but the `KeyPtr` must still advance as
+                        // if its layout had been reported so that every later
+                        // field keeps the same storage key it would otherwise
+                        // have been assigned.
+                        let _ = <u32 as ::ink_core::storage2::traits::StorageLayout>::layout(__key_ptr);
+                        let __field_c = ::ink_abi::layout2::FieldLayout::new(
+                            Some("c"),
+                            <i64 as ::ink_core::storage2::traits::StorageLayout>::layout(__key_ptr),
+                        );
+                        ::ink_abi::layout2::Layout::Struct(
+                            ::ink_abi::layout2::StructLayout::new(vec![__field_a, __field_c])
+                        )
+                    }
+                }
+            };
+        }
+    }
+}
+
+#[test]
+fn rename_all_camel_case_works() {
+    synstructure::test_derive! {
+        storage_layout_derive {
+            #[layout(rename_all = "camelCase")]
+            struct RenameAllStruct {
+                first_field: bool,
+                second_field: u32,
+            }
+        }
+        expands to {
+            #[allow(non_upper_case_globals)]
+            const _DERIVE_ink_core_storage2_traits_StorageLayout_FOR_RenameAllStruct: () = {
+                impl ::ink_core::storage2::traits::StorageLayout for RenameAllStruct {
+                    fn layout(__key_ptr: &mut ::ink_core::storage2::traits::KeyPtr) -> ::ink_abi::layout2::Layout {
+                        ::ink_abi::layout2::Layout::Struct(
+                            ::ink_abi::layout2::StructLayout::new(vec![
+                                ::ink_abi::layout2::FieldLayout::new(
+                                    Some("firstField"),
+                                    <bool as ::ink_core::storage2::traits::StorageLayout>::layout(__key_ptr),
+                                ),
+                                ::ink_abi::layout2::FieldLayout::new(
+                                    Some("secondField"),
+                                    <u32 as ::ink_core::storage2::traits::StorageLayout>::layout(__key_ptr),
+                                ),
+                            ])
+                        )
+                    }
+                }
+            };
+        }
+    }
+}
+
 #[test]
 fn clike_enum_works() {
     synstructure::test_derive! {
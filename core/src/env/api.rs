@@ -22,6 +22,7 @@ use crate::env::{
     call::{
         CallData,
         CallParams,
+        DelegateCallParams,
         InstantiateParams,
         ReturnType,
     },
@@ -29,6 +30,10 @@ use crate::env::{
         EnvInstance,
         OnInstance,
     },
+    hash::{
+        CryptoHash,
+        HashOutput,
+    },
     EnvTypes,
     Result,
     Topics,
@@ -187,6 +192,40 @@ where
     })
 }
 
+/// Transfers value from the executed contract to the destination account ID.
+///
+/// # Errors
+///
+/// - If the contract does not have sufficient free funds.
+/// - If the transfer would bring the contract's balance below the
+///   subsistence threshold.
+pub fn transfer<T>(destination: T::AccountId, value: T::Balance) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnv::transfer::<T>(instance, destination, value)
+    })
+}
+
+/// Terminates the existence of the executed contract, transferring its
+/// entire remaining balance to `beneficiary` and removing all of its
+/// storage.
+///
+/// # Note
+///
+/// This function never returns. Upon termination the contract's account,
+/// together with all of its storage cells, ceases to exist; any code
+/// executed after this call is unreachable.
+pub fn terminate_contract<T>(beneficiary: T::AccountId) -> !
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnv::terminate_contract::<T>(instance, beneficiary)
+    })
+}
+
 /// Emits an event with the given event data.
 pub fn emit_event<T, Event>(event: Event)
 where
@@ -239,22 +278,28 @@ pub fn clear_contract_storage(key: Key) {
     })
 }
 
-/// Invokes a call to the runtime.
+/// Dispatches a call into the runtime, executing it with the contract's
+/// own account as its origin.
 ///
 /// # Note
 ///
-/// The call is not guaranteed to execute immediately but might be deferred
-/// to the end of the contract execution.
+/// This is distinct from calling a chain extension: chain extensions are
+/// custom host functions, whereas this dispatches an ordinary runtime
+/// `Call`, such as a balances transfer or any other pallet extrinsic,
+/// generically. The outcome of the dispatch is returned by this call.
 ///
 /// # Errors
 ///
-/// - If the called runtime function does not exist.
-pub fn invoke_runtime<T>(params: &T::Call) -> Result<()>
+/// - If the runtime fails to dispatch the call, e.g. because of a bad
+///   origin, insufficient funds or arguments rejected by the pallet's own
+///   checks.
+pub fn call_runtime<T, Call>(call: &Call) -> Result<()>
 where
     T: EnvTypes,
+    Call: scale::Encode,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::invoke_runtime::<T>(instance, params)
+        TypedEnv::call_runtime::<T, Call>(instance, call)
     })
 }
 
@@ -317,6 +362,81 @@ where
     })
 }
 
+/// Instantiates another contract and returns the value returned by its
+/// constructor alongside the new contract's account ID.
+///
+/// # Errors
+///
+/// - If the code hash is invalid.
+/// - If the arguments passed to the instantiation process are invalid.
+/// - If the instantiation process traps.
+/// - If the instantiation process runs out of gas.
+/// - If given too few endowment.
+/// - If the returned account ID or constructor return value failed to
+///   decode properly.
+pub fn eval_instantiate_contract<T, R>(
+    params: &InstantiateParams<T, ReturnType<R>>,
+) -> Result<(T::AccountId, R)>
+where
+    T: EnvTypes,
+    R: scale::Decode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnv::eval_instantiate_contract::<T, R>(instance, params)
+    })
+}
+
+/// Delegate-calls a message of the contract at `params`'s code hash,
+/// discarding any returned value.
+///
+/// # Note
+///
+/// Unlike [`invoke_contract`], the code executes in the *caller's own*
+/// storage and balance context - same `caller`, same `account_id` - rather
+/// than a separate callee account, so a thin proxy contract can forward its
+/// messages to a swappable implementation hash without migrating storage.
+///
+/// # Errors
+///
+/// - If the code hash is invalid or not found.
+/// - If arguments passed to the delegated message are invalid.
+/// - If the delegated execution has trapped.
+/// - If the delegated execution ran out of gas.
+pub fn invoke_contract_delegate<T>(params: &DelegateCallParams<T, ()>) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnv::invoke_contract_delegate::<T>(instance, params)
+    })
+}
+
+/// Delegate-calls a message of the contract at `params`'s code hash and
+/// returns its result.
+///
+/// # Note
+///
+/// See [`invoke_contract_delegate`] for the context this executes in.
+///
+/// # Errors
+///
+/// - If the code hash is invalid or not found.
+/// - If arguments passed to the delegated message are invalid.
+/// - If the delegated execution has trapped.
+/// - If the delegated execution ran out of gas.
+/// - If the returned value failed to decode properly.
+pub fn eval_contract_delegate<T, R>(
+    params: &DelegateCallParams<T, ReturnType<R>>,
+) -> Result<R>
+where
+    T: EnvTypes,
+    R: scale::Decode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnv::eval_contract_delegate::<T, R>(instance, params)
+    })
+}
+
 /// Restores a smart contract in tombstone state.
 ///
 /// # Params
@@ -437,6 +557,151 @@ pub fn println(content: &str) {
     <EnvInstance as OnInstance>::on_instance(|instance| Env::println(instance, content))
 }
 
+/// Conducts the BLAKE2 256-bit hash of the input and puts the result into
+/// the output buffer.
+pub fn hash_blake2_256(input: &[u8], output: &mut [u8; 32]) {
+    <EnvInstance as Env>::hash_blake2_256(input, output)
+}
+
+/// Conducts the crypto hash of the given input and puts the result into the
+/// output buffer.
+///
+/// # Note
+///
+/// Picking `H` selects both the algorithm (e.g. [`Blake2x256`][`crate::env::hash::Blake2x256`])
+/// and, through [`HashOutput::Type`], the output buffer's size, so contracts
+/// that need to hash with more than one algorithm no longer need a
+/// differently named free function per hash and per output width.
+pub fn hash_bytes<H>(input: &[u8], output: &mut <H as HashOutput>::Type)
+where
+    H: CryptoHash,
+{
+    <H as CryptoHash>::hash(input, output)
+}
+
+/// Conducts the crypto hash of the SCALE encoding of the given value and
+/// puts the result into the output buffer.
+///
+/// # Note
+///
+/// Lets callers hash a structured value directly instead of having to
+/// SCALE-encode it into an intermediate buffer themselves first.
+pub fn hash_encoded<H, V>(value: &V, output: &mut <H as HashOutput>::Type)
+where
+    H: CryptoHash,
+    V: scale::Encode,
+{
+    let enc_input = scale::Encode::encode(value);
+    <H as CryptoHash>::hash(&enc_input[..], output)
+}
+
+/// Recovers the compressed ECDSA public key that produced `signature` over
+/// `message_hash`, and writes it into `output`.
+///
+/// # Note
+///
+/// This can be used by contracts that verify a cross-chain message or
+/// receipt without trusting an off-chain relayer, by checking the recovered
+/// signer against an expected key or, once decompressed into its 65-byte
+/// form and passed to [`ecdsa_to_eth_address`], an expected Ethereum-style
+/// address.
+///
+/// # Errors
+///
+/// If the signature, the recovery ID, or the message hash is malformed, or
+/// the signature is invalid for the given message hash.
+pub fn ecdsa_recover(
+    signature: &[u8; 65],
+    message_hash: &[u8; 32],
+    output: &mut [u8; 33],
+) -> Result<()> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::ecdsa_recover(instance, signature, message_hash, output)
+    })
+}
+
+/// Verifies an ed25519 `signature` of `message` against `pub_key`.
+///
+/// # Note
+///
+/// Like [`sr25519_verify`], this lets contracts that collect approvals
+/// off-chain verify them cheaply in a single on-chain call instead of
+/// requiring one transaction per signer.
+pub fn ed25519_verify(signature: &[u8; 64], message: &[u8], pub_key: &[u8; 32]) -> bool {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::ed25519_verify(instance, signature, message, pub_key)
+    })
+}
+
+/// Verifies an sr25519 `signature` of `message` against `pub_key`.
+///
+/// # Note
+///
+/// This lets contracts that collect approvals off-chain, such as a
+/// multisig wallet batching owner signatures, verify them cheaply in a
+/// single on-chain call instead of requiring one transaction per signer.
+pub fn sr25519_verify(signature: &[u8; 64], message: &[u8], pub_key: &[u8; 32]) -> bool {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::sr25519_verify(instance, signature, message, pub_key)
+    })
+}
+
+/// Returns the Ethereum-style address belonging to an uncompressed ECDSA
+/// public key.
+///
+/// # Note
+///
+/// [`ecdsa_recover`] writes the 33-byte compressed form of a key; this
+/// function takes the 65-byte uncompressed form and does not decompress.
+///
+/// This is the trailing 20 bytes of the `KECCAK` 256-bit hash of the public
+/// key's `[1..65]` byte range, i.e. the 64-byte encoding point without its
+/// leading `0x04` tag byte.
+pub fn ecdsa_to_eth_address(pubkey_uncompressed: &[u8; 65], output: &mut [u8; 20]) {
+    let mut hash = [0u8; 32];
+    <EnvInstance as Env>::hash_keccak_256(&pubkey_uncompressed[1..], &mut hash);
+    output.copy_from_slice(&hash[12..]);
+}
+
+/// Writes the value to the transient storage under the given key.
+///
+/// # Note
+///
+/// The transient storage is discarded at the end of the outermost contract
+/// call instead of being committed to the trie, making it suitable for
+/// reentrancy guards or other scratch state that should not incur a storage
+/// deposit.
+pub fn set_transient_storage<V>(key: &Key, value: &V)
+where
+    V: scale::Encode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::set_transient_storage::<V>(instance, key, value)
+    })
+}
+
+/// Returns the value stored under the given key in the transient storage
+/// if any.
+///
+/// # Errors
+///
+/// - If the decoding of the typed value failed
+pub fn get_transient_storage<R>(key: &Key) -> Result<Option<R>>
+where
+    R: scale::Decode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::get_transient_storage::<R>(instance, key)
+    })
+}
+
+/// Clears the transient storage key entry.
+pub fn clear_transient_storage(key: &Key) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::clear_transient_storage(instance, key)
+    })
+}
+
 /// Returns the value from the *runtime* storage at the position of the key if any.
 ///
 /// # Errors
@@ -15,9 +15,12 @@
 use crate::env::{
     call::{
         CallParams,
+        DelegateCallParams,
         InstantiateParams,
         ReturnType,
     },
+    chain_extension::ChainExtensionMethod,
+    EnvError,
     EnvTypes,
     Result,
     Topics,
@@ -48,6 +51,124 @@ impl ReturnFlags {
     }
 }
 
+/// The flags used to customize a cross-contract call.
+///
+/// # Note
+///
+/// By default none of the flags are set, reproducing today's plain
+/// `invoke_contract`/`eval_contract` behavior.
+///
+/// The flags reach the host only through [`TypedEnv::invoke_contract_tail`]
+/// and the delegate-call path; [`TypedEnv::invoke_contract`] and
+/// [`TypedEnv::eval_contract`] do not pass them on.
+#[derive(Clone, Copy)]
+pub struct CallFlags {
+    value: u32,
+}
+
+impl Default for CallFlags {
+    fn default() -> Self {
+        Self { value: 0 }
+    }
+}
+
+impl CallFlags {
+    const FORWARD_INPUT: u32 = 0b0001;
+    const CLONE_INPUT: u32 = 0b0010;
+    const TAIL_CALL: u32 = 0b0100;
+    const ALLOW_REENTRY: u32 = 0b1000;
+
+    /// Forwards the caller's own input buffer to the callee instead of
+    /// encoding fresh call data, saving a copy.
+    ///
+    /// # Note
+    ///
+    /// Mutually exclusive with explicitly pushed call arguments; setting
+    /// both results in an error when the call is fired, except through
+    /// `CallBuilder::fire_tail`, which does not check this.
+    pub fn set_forward_input(mut self, forward_input: bool) -> Self {
+        self.set_bit(Self::FORWARD_INPUT, forward_input);
+        self
+    }
+
+    /// Like [`CallFlags::set_forward_input`], but keeps the input buffer
+    /// around for the caller to reuse afterwards.
+    pub fn set_clone_input(mut self, clone_input: bool) -> Self {
+        self.set_bit(Self::CLONE_INPUT, clone_input);
+        self
+    }
+
+    /// Makes the callee's return value become the caller's own return
+    /// value, so that control never returns to the caller.
+    pub fn set_tail_call(mut self, tail_call: bool) -> Self {
+        self.set_bit(Self::TAIL_CALL, tail_call);
+        self
+    }
+
+    /// Permits the callee to call back into the currently executing
+    /// contract, which is forbidden by default.
+    ///
+    /// # Note
+    ///
+    /// An off-chain test engine implementation is expected to model this by
+    /// tracking the contract(s) currently being executed and rejecting a
+    /// call that re-enters one of them unless this flag was set, mirroring
+    /// the on-chain reentrancy guard.
+    pub fn set_allow_reentry(mut self, allow_reentry: bool) -> Self {
+        self.set_bit(Self::ALLOW_REENTRY, allow_reentry);
+        self
+    }
+
+    /// Returns `true` if the forward-input flag is set.
+    pub fn forward_input(&self) -> bool {
+        self.value & Self::FORWARD_INPUT != 0
+    }
+
+    /// Returns `true` if the tail-call flag is set.
+    pub fn tail_call(&self) -> bool {
+        self.value & Self::TAIL_CALL != 0
+    }
+
+    fn set_bit(&mut self, bit: u32, set: bool) {
+        if set {
+            self.value |= bit;
+        } else {
+            self.value &= !bit;
+        }
+    }
+
+    /// Returns the underlying `u32` representation.
+    pub fn into_u32(self) -> u32 {
+        self.value
+    }
+}
+
+/// A finer-grained failure mode for a non-trapping cross-contract call.
+///
+/// # Note
+///
+/// Returned by [`TypedEnv::try_invoke_contract`] and
+/// [`TypedEnv::try_eval_contract`] when the callee did not succeed but its
+/// failure is specific enough for the caller to react to, instead of
+/// having the whole transaction abort as a plain [`TypedEnv::invoke_contract`]
+/// or [`TypedEnv::eval_contract`] call would.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CallError {
+    /// The callee's execution trapped.
+    CalleeTrapped,
+    /// The callee's execution explicitly reverted. Its return data is not
+    /// passed on to the caller.
+    CalleeReverted,
+    /// A storage key addressed during the call could not be found.
+    KeyNotFound,
+    /// The value transfer to the callee failed.
+    TransferFailed,
+    /// The callee exists but is not callable (e.g. it is a tombstone).
+    NotCallable,
+    /// The callee's code hash could not be found.
+    CodeNotFound,
+}
+
 /// Environmental contract functionality that does not require `EnvTypes`.
 pub trait Env {
     /// Writes the value to the contract storage under the given key.
@@ -67,6 +188,33 @@ pub trait Env {
     /// Clears the contract's storage key entry.
     fn clear_contract_storage(&mut self, key: &Key);
 
+    /// Writes the value to the transient storage under the given key.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Env::set_contract_storage`] the transient storage is not
+    /// part of the trie and is discarded once the outermost contract call
+    /// returns, whether normally or via a trap. It is shared by all calls
+    /// nested within that outermost call, keyed by the contract's account
+    /// id, making it suitable for reentrancy guards or scratch state that
+    /// must not incur a storage deposit.
+    fn set_transient_storage<V>(&mut self, key: &Key, value: &V)
+    where
+        V: scale::Encode;
+
+    /// Returns the value stored under the given key in the transient
+    /// storage if any.
+    ///
+    /// # Errors
+    ///
+    /// - If the decoding of the typed value failed
+    fn get_transient_storage<R>(&mut self, key: &Key) -> Result<Option<R>>
+    where
+        R: scale::Decode;
+
+    /// Clears the transient storage key entry.
+    fn clear_transient_storage(&mut self, key: &Key);
+
     /// Returns the execution input to the executed contract and decodes it as `T`.
     ///
     /// # Note
@@ -124,10 +272,78 @@ pub trait Env {
     /// puts the result into the output buffer.
     fn hash_blake2_128(input: &[u8], output: &mut [u8; 16]);
 
+    /// Recovers the compressed ECDSA public key that produced `signature`
+    /// over `message_hash`, and puts the result into the output buffer.
+    ///
+    /// # Note
+    ///
+    /// An off-chain test engine implementation of [`Env`] is expected to
+    /// back this with a real secp256k1 implementation rather than a stub,
+    /// so that unit tests can exercise genuine signature verification
+    /// (meta-transactions, permit-style approvals, oracle attestations)
+    /// the same way the on-chain host function does.
+    ///
+    /// # Errors
+    ///
+    /// - If the signature or the recovery ID is malformed.
+    /// - If the signature is invalid for the given message hash.
+    fn ecdsa_recover(
+        &mut self,
+        signature: &[u8; 65],
+        message_hash: &[u8; 32],
+        output: &mut [u8; 33],
+    ) -> Result<()>;
+
+    /// Verifies an ed25519 `signature` of `message` against `pub_key`.
+    ///
+    /// # Note
+    ///
+    /// Like [`Env::sr25519_verify`], this is useful for contracts that
+    /// collect owner or participant approvals off-chain and want to verify
+    /// them cheaply in a single on-chain call.
+    ///
+    /// An off-chain test engine implementation of [`Env`] is expected to
+    /// back this with a real ed25519 implementation rather than a stub, so
+    /// that unit tests can exercise genuine signature verification.
+    fn ed25519_verify(
+        &mut self,
+        signature: &[u8; 64],
+        message: &[u8],
+        pub_key: &[u8; 32],
+    ) -> bool;
+
+    /// Verifies an sr25519 `signature` of `message` against `pub_key`.
+    ///
+    /// # Note
+    ///
+    /// This is useful for contracts that collect owner or participant
+    /// approvals off-chain and want to verify them cheaply in a single
+    /// on-chain call, e.g. a multisig wallet settling a transaction from
+    /// batched signatures instead of one on-chain confirmation per signer.
+    ///
+    /// An off-chain test engine implementation of [`Env`] is expected to
+    /// back this with a real sr25519 implementation rather than a stub, so
+    /// that unit tests can exercise genuine signature verification.
+    fn sr25519_verify(
+        &mut self,
+        signature: &[u8; 64],
+        message: &[u8],
+        pub_key: &[u8; 32],
+    ) -> bool;
+
     /// Calls the chain extension with the given ID and inputs.
     ///
     /// Returns the output of the chain extension of the specified type.
     ///
+    /// # Note
+    ///
+    /// An off-chain test engine implementation of [`Env`] is expected to let
+    /// tests register a closure keyed by `func_id` that receives the raw,
+    /// SCALE-encoded input bytes and produces the output bytes and a status
+    /// code, so contracts relying on custom runtime functionality (oracles,
+    /// randomness beacons, DID registries) can be exercised without a live
+    /// chain.
+    ///
     /// # Errors
     ///
     /// - If the chain extension with the given ID does not exist.
@@ -138,6 +354,29 @@ pub trait Env {
     where
         I: scale::Codec + 'static,
         O: scale::Codec + 'static;
+
+    /// Calls a typed, registry-driven chain extension method.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Env::call_chain_extension`], the method's `func_id` and
+    /// argument/return types come from `M` itself (see
+    /// [`ChainExtensionMethod`]), and the host's raw status code is decoded
+    /// through `M::ErrorCode` rather than the fixed [`EnvError`] set, so each
+    /// extension can surface its own, discoverable failure modes. If `M`
+    /// declares a [`ChainExtensionMethod::WEIGHT`], it is charged up front
+    /// instead of relying on the runtime to meter the host call.
+    ///
+    /// # Errors
+    ///
+    /// Returns `M::ErrorCode` if the runtime reports a non-zero status code,
+    /// or if the returned output failed to decode into `M::Output`.
+    fn call_chain_extension_method<M>(
+        &mut self,
+        input: &M::Input,
+    ) -> core::result::Result<M::Output, M::ErrorCode>
+    where
+        M: ChainExtensionMethod;
 }
 
 /// Environmental contract functionality.
@@ -251,6 +490,20 @@ pub trait TypedEnv: Env {
         T: EnvTypes,
         Args: scale::Encode;
 
+    /// Invokes a contract message as a tail call.
+    ///
+    /// # Note
+    ///
+    /// The callee's return value becomes the caller's own return value, so
+    /// execution never returns to the caller; see [`CallFlags::set_tail_call`].
+    fn invoke_contract_tail<T, Args>(
+        &mut self,
+        call_data: &CallParams<T, Args, ()>,
+    ) -> !
+    where
+        T: EnvTypes,
+        Args: scale::Encode;
+
     /// Evaluates a contract message and returns its result.
     ///
     /// # Note
@@ -265,6 +518,36 @@ pub trait TypedEnv: Env {
         Args: scale::Encode,
         R: scale::Decode;
 
+    /// Invokes a contract message without trapping on a callee failure.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`TypedEnv::invoke_contract`], a callee trap or revert is
+    /// reported as `Ok(Err(CallError::..))` instead of unwinding the
+    /// caller, letting it implement fallback logic. The outer `Result`
+    /// still surfaces host or encoding errors.
+    fn try_invoke_contract<T, Args>(
+        &mut self,
+        call_data: &CallParams<T, Args, ()>,
+    ) -> Result<core::result::Result<(), CallError>>
+    where
+        T: EnvTypes,
+        Args: scale::Encode;
+
+    /// Evaluates a contract message without trapping on a callee failure.
+    ///
+    /// # Note
+    ///
+    /// See [`TypedEnv::try_invoke_contract`] for the non-trapping semantics.
+    fn try_eval_contract<T, Args, R>(
+        &mut self,
+        call_data: &CallParams<T, Args, ReturnType<R>>,
+    ) -> Result<core::result::Result<R, CallError>>
+    where
+        T: EnvTypes,
+        Args: scale::Encode,
+        R: scale::Decode;
+
     /// Instantiates another contract.
     ///
     /// # Note
@@ -278,6 +561,58 @@ pub trait TypedEnv: Env {
         T: EnvTypes,
         Args: scale::Encode;
 
+    /// Instantiates another contract and returns the value returned by its
+    /// constructor alongside the new contract's account ID.
+    ///
+    /// # Note
+    ///
+    /// Mirrors the split between [`TypedEnv::invoke_contract`] and
+    /// [`TypedEnv::eval_contract`]: use this instead of
+    /// [`TypedEnv::instantiate_contract`] for constructors that return a
+    /// value, e.g. fallible constructors returning a `Result`.
+    fn eval_instantiate_contract<T, Args, R>(
+        &mut self,
+        params: &InstantiateParams<T, Args, ReturnType<R>>,
+    ) -> Result<(T::AccountId, R)>
+    where
+        T: EnvTypes,
+        Args: scale::Encode,
+        R: scale::Decode;
+
+    /// Delegate-calls a message of the contract at `code_hash`, discarding
+    /// any returned value.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`TypedEnv::invoke_contract`], the code at `code_hash`
+    /// executes in the *caller's own* storage and balance context - same
+    /// `caller()`, same `account_id()` - rather than a separate callee
+    /// account, letting a thin proxy contract forward messages to a
+    /// swappable implementation hash without migrating storage.
+    fn invoke_contract_delegate<T, Args>(
+        &mut self,
+        params: &DelegateCallParams<T, Args, ()>,
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+        Args: scale::Encode;
+
+    /// Delegate-calls a message of the contract at `code_hash` and returns
+    /// its result.
+    ///
+    /// # Note
+    ///
+    /// See [`TypedEnv::invoke_contract_delegate`] for the context this
+    /// executes in.
+    fn eval_contract_delegate<T, Args, R>(
+        &mut self,
+        params: &DelegateCallParams<T, Args, ReturnType<R>>,
+    ) -> Result<R>
+    where
+        T: EnvTypes,
+        Args: scale::Encode,
+        R: scale::Decode;
+
     /// Restores a smart contract tombstone.
     ///
     /// # Note
@@ -297,6 +632,17 @@ pub trait TypedEnv: Env {
     /// # Note
     ///
     /// For more details visit: [`ink_core::env::terminate_contract`]
+    ///
+    /// Removes the executing contract's account and transfers its entire
+    /// remaining balance to `beneficiary`, then halts execution without
+    /// returning to the caller. This is a cheaper, immediate end-of-life
+    /// path than the tombstone/[`TypedEnv::restore_contract`] dance, and
+    /// the primitive upgradeable-contract patterns use to wind down a
+    /// deprecated instance.
+    ///
+    /// An off-chain test engine implementation of [`TypedEnv`] is expected
+    /// to remove the executing contract's account from its accounts
+    /// database and credit `beneficiary` with its swept balance.
     fn terminate_contract<T>(&mut self, beneficiary: T::AccountId) -> !
     where
         T: EnvTypes;
@@ -306,7 +652,13 @@ pub trait TypedEnv: Env {
     /// # Note
     ///
     /// For more details visit: [`ink_core::env::transfer`]
-    fn transfer<T>(&mut self, destination: T::AccountId, value: T::Balance)
+    ///
+    /// An off-chain test engine implementation of [`TypedEnv`] is expected
+    /// to debit the executing contract's balance and credit `destination`,
+    /// creating its account first if it does not yet exist, and to fail
+    /// this call if the executing contract's balance is insufficient,
+    /// mirroring the on-chain host function's accounting.
+    fn transfer<T>(&mut self, destination: T::AccountId, value: T::Balance) -> Result<()>
     where
         T: EnvTypes;
 
@@ -318,4 +670,30 @@ pub trait TypedEnv: Env {
     fn random<T>(&mut self, subject: &[u8]) -> Result<T::Hash>
     where
         T: EnvTypes;
+
+    /// Dispatches a call into the runtime, executing it with the
+    /// contract's own account as its origin.
+    ///
+    /// # Note
+    ///
+    /// This is distinct from [`Env::call_chain_extension`]: chain
+    /// extensions are custom host functions, whereas this dispatches an
+    /// ordinary runtime `Call`, such as a balances transfer or any other
+    /// pallet extrinsic, generically.
+    ///
+    /// An off-chain test engine implementation of [`TypedEnv`] is expected
+    /// to provide a registration hook so that tests can supply a mock
+    /// dispatcher keyed by the call index, letting `call_runtime` be
+    /// exercised the same way the on-chain host function would route it.
+    ///
+    /// # Errors
+    ///
+    /// - If the runtime fails to dispatch the call, e.g. because of a bad
+    ///   origin, insufficient funds or arguments rejected by the pallet's
+    ///   own checks. The pallet's `DispatchError` is surfaced as
+    ///   [`EnvError::CallRuntimeFailed`].
+    fn call_runtime<T, Call>(&mut self, call: &Call) -> Result<()>
+    where
+        T: EnvTypes,
+        Call: scale::Encode;
 }
@@ -0,0 +1,721 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Data types and typed builders for cross-contract calls and instantiations.
+
+use crate::env::{
+    engine::{
+        EnvInstance,
+        OnInstance,
+    },
+    CallFlags,
+    Env,
+    EnvError,
+    EnvTypes,
+    Result,
+    TypedEnv,
+};
+use core::marker::PhantomData;
+
+/// The selector of a contract's message or constructor.
+///
+/// # Note
+///
+/// A selector is the first four bytes of the SCALE encoded call input and
+/// is used by the callee to decide which message or constructor to dispatch
+/// to. For ink! generated contracts this is usually the BLAKE2 256-bit hash
+/// of the message's or constructor's identifier, truncated to its first four
+/// bytes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Selector([u8; 4]);
+
+impl Selector {
+    /// Creates a new selector from the given raw bytes.
+    pub fn new(bytes: [u8; 4]) -> Self {
+        Self(bytes)
+    }
+
+    /// Computes the selector of the message or constructor with the given name.
+    ///
+    /// # Note
+    ///
+    /// This takes the first four bytes of the BLAKE2 256-bit hash of `name`,
+    /// mirroring how ink! itself derives selectors for undecorated messages
+    /// and constructors.
+    pub fn from_str(name: &str) -> Self {
+        let mut output = [0u8; 32];
+        <EnvInstance as Env>::hash_blake2_256(name.as_bytes(), &mut output);
+        Self::new([output[0], output[1], output[2], output[3]])
+    }
+
+    /// Returns the underlying four bytes of `self`.
+    pub fn to_bytes(self) -> [u8; 4] {
+        self.0
+    }
+}
+
+impl From<[u8; 4]> for Selector {
+    fn from(bytes: [u8; 4]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl scale::Encode for Selector {
+    fn encode_to<O: scale::Output>(&self, dest: &mut O) {
+        dest.write(&self.0)
+    }
+}
+
+impl scale::Decode for Selector {
+    fn decode<I: scale::Input>(input: &mut I) -> core::result::Result<Self, scale::Error> {
+        let mut bytes = [0u8; 4];
+        input.read(&mut bytes)?;
+        Ok(Self::new(bytes))
+    }
+}
+
+/// The raw, SCALE encoded call data of a contract invocation.
+///
+/// # Note
+///
+/// This is the four-byte selector of the called message or constructor
+/// immediately followed by its SCALE encoded arguments, without a length
+/// prefix. Contracts receive exactly this buffer as their execution input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallData {
+    selector: Selector,
+    params: ink_prelude::vec::Vec<u8>,
+}
+
+impl CallData {
+    /// Creates new call data for the message or constructor with the given selector.
+    pub fn new(selector: Selector) -> Self {
+        Self {
+            selector,
+            params: ink_prelude::vec::Vec::new(),
+        }
+    }
+
+    /// Pushes an argument to the call data by appending its SCALE encoding.
+    pub fn push_arg<A>(&mut self, arg: &A)
+    where
+        A: scale::Encode,
+    {
+        arg.encode_to(&mut self.params);
+    }
+
+    /// Returns the selector of the to-be-called message or constructor.
+    pub fn selector(&self) -> Selector {
+        self.selector
+    }
+
+    /// Returns the already SCALE encoded arguments of this call data.
+    pub fn params(&self) -> &[u8] {
+        &self.params
+    }
+}
+
+impl scale::Encode for CallData {
+    fn encode_to<O: scale::Output>(&self, dest: &mut O) {
+        self.selector.encode_to(dest);
+        for byte in &self.params {
+            dest.push_byte(*byte);
+        }
+    }
+}
+
+impl scale::Decode for CallData {
+    fn decode<I: scale::Input>(input: &mut I) -> core::result::Result<Self, scale::Error> {
+        let selector = Selector::decode(input)?;
+        let remaining_len = input.remaining_len()?.unwrap_or(0);
+        let mut params = ink_prelude::vec![0u8; remaining_len];
+        input.read(&mut params)?;
+        Ok(Self { selector, params })
+    }
+}
+
+/// Tags a [`CallParams`] or [`InstantiateParams`] as expecting a typed return value `R`.
+#[derive(Debug)]
+pub struct ReturnType<R>(PhantomData<fn() -> R>);
+
+impl<R> Default for ReturnType<R> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// The parameters of a cross-contract message call, ready to be dispatched
+/// to the environment.
+///
+/// # Note
+///
+/// Constructed solely through [`CallBuilder`]; this type just bundles the
+/// already encoded call data with the callee, gas limit and transferred
+/// value so that the environment backend has a single, owned value to work
+/// with.
+#[derive(Debug)]
+pub struct CallParams<T, Args, RetType>
+where
+    T: EnvTypes,
+{
+    /// The account ID of the to-be-called smart contract.
+    callee: T::AccountId,
+    /// The maximum gas costs allowed for the call.
+    ///
+    /// `None` means "use all remaining gas", preserving today's behavior.
+    gas_limit: Option<u64>,
+    /// The maximum amount of new storage deposit the call may incur.
+    ///
+    /// `None` means "use all remaining storage deposit allowance".
+    storage_deposit_limit: Option<T::Balance>,
+    /// The transferred value for the call.
+    transferred_value: T::Balance,
+    /// The already encoded selector and arguments of the call.
+    call_data: CallData,
+    /// The flags used to customize the call.
+    call_flags: CallFlags,
+    /// The expected argument and return types of the call.
+    types: PhantomData<fn() -> (Args, RetType)>,
+}
+
+impl<T, Args, RetType> CallParams<T, Args, RetType>
+where
+    T: EnvTypes,
+{
+    /// Returns the account ID of the to-be-called smart contract.
+    pub(crate) fn callee(&self) -> &T::AccountId {
+        &self.callee
+    }
+
+    /// Returns the gas limit for the contract call.
+    pub(crate) fn gas_limit(&self) -> Option<u64> {
+        self.gas_limit
+    }
+
+    /// Returns the storage deposit limit for the contract call.
+    pub(crate) fn storage_deposit_limit(&self) -> &Option<T::Balance> {
+        &self.storage_deposit_limit
+    }
+
+    /// Returns the transferred value for the contract call.
+    pub(crate) fn transferred_value(&self) -> &T::Balance {
+        &self.transferred_value
+    }
+
+    /// Returns the already encoded input data of the contract call.
+    pub(crate) fn input_data(&self) -> &CallData {
+        &self.call_data
+    }
+
+    /// Returns the call flags used to customize the call.
+    pub(crate) fn call_flags(&self) -> CallFlags {
+        self.call_flags
+    }
+}
+
+/// Builds up a [`CallParams`] for invoking or evaluating a message of another
+/// contract, with compile-time-checked argument and return types.
+///
+/// # Note
+///
+/// Arguments are SCALE-encoded into [`CallData`]'s heap-allocated argument
+/// buffer, which in turn is marshalled through [`EnvInstance`]'s single
+/// reusable scoped buffer on [`CallBuilder::fire`].
+///
+/// # Examples
+///
+/// ```no_compile
+/// let result = CallBuilder::<DefaultEnvTypes, Balance>::invoke(callee, selector)
+///     .gas_limit(5000)
+///     .transferred_value(10)
+///     .push_arg(&owner)
+///     .fire()
+///     .expect("call to `balance_of` must succeed");
+/// ```
+#[derive(Debug)]
+pub struct CallBuilder<T, RetType>
+where
+    T: EnvTypes,
+{
+    params: CallParams<T, (), RetType>,
+}
+
+impl<T, RetType> CallBuilder<T, RetType>
+where
+    T: EnvTypes,
+    T::Balance: Default,
+{
+    /// Creates a new call builder for invoking the message with the given
+    /// `selector` on `callee`.
+    pub fn invoke(callee: T::AccountId, selector: Selector) -> Self {
+        Self {
+            params: CallParams {
+                callee,
+                gas_limit: None,
+                storage_deposit_limit: None,
+                transferred_value: Default::default(),
+                call_data: CallData::new(selector),
+                call_flags: CallFlags::default(),
+                types: PhantomData,
+            },
+        }
+    }
+}
+
+impl<T, RetType> CallBuilder<T, RetType>
+where
+    T: EnvTypes,
+{
+    /// Sets the maximum allowed gas costs for the call.
+    ///
+    /// A failing or griefing callee can otherwise drain the caller's full
+    /// gas; capping it here lets the host report [`EnvError::OutOfGas`]
+    /// instead of consuming everything the caller has left.
+    pub fn gas_limit(mut self, gas_limit: u64) -> Self {
+        self.params.gas_limit = Some(gas_limit);
+        self
+    }
+
+    /// Sets the maximum amount of new storage deposit the call may incur.
+    ///
+    /// # Note
+    ///
+    /// Exceeding the limit is reported as
+    /// [`EnvError::StorageDepositLimitExhausted`] instead of letting the
+    /// callee force an unbounded storage charge onto the caller.
+    pub fn storage_deposit_limit(mut self, storage_deposit_limit: T::Balance) -> Self {
+        self.params.storage_deposit_limit = Some(storage_deposit_limit);
+        self
+    }
+
+    /// Sets the value to transfer with the call.
+    pub fn transferred_value(mut self, transferred_value: T::Balance) -> Self {
+        self.params.transferred_value = transferred_value;
+        self
+    }
+
+    /// Sets the flags used to customize the call.
+    pub fn call_flags(mut self, call_flags: CallFlags) -> Self {
+        self.params.call_flags = call_flags;
+        self
+    }
+
+    /// Pushes an argument to the call, in order.
+    pub fn push_arg<A>(mut self, arg: &A) -> Self
+    where
+        A: scale::Encode,
+    {
+        self.params.call_data.push_arg(arg);
+        self
+    }
+}
+
+impl<T> CallBuilder<T, ()>
+where
+    T: EnvTypes,
+{
+    /// Fires the call, discarding any returned value.
+    ///
+    /// # Errors
+    ///
+    /// - If the callee does not exist or is a tombstone.
+    /// - If the arguments passed to the callee message are invalid.
+    /// - If the callee execution has trapped or ran out of gas.
+    /// - If the call would exceed a configured `gas_limit` or
+    ///   `storage_deposit_limit`.
+    /// - If both `CallFlags::set_forward_input` and explicitly pushed
+    ///   arguments are set.
+    pub fn fire(self) -> Result<()> {
+        if self.params.call_flags.forward_input() && !self.params.call_data.params().is_empty() {
+            return Err(EnvError::ForwardInputConflict)
+        }
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            TypedEnv::invoke_contract::<T, ()>(instance, &self.params)
+        })
+    }
+}
+
+impl<T> CallBuilder<T, ()>
+where
+    T: EnvTypes,
+{
+    /// Fires the call as a tail call: the callee's return value becomes
+    /// the caller's own return value and control never returns here.
+    ///
+    /// # Note
+    ///
+    /// This is only meaningful once `CallFlags::set_tail_call(true)` has
+    /// been set via [`CallBuilder::call_flags`]; the host enforces this.
+    pub fn fire_tail(self) -> ! {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            TypedEnv::invoke_contract_tail::<T, ()>(instance, &self.params)
+        })
+    }
+}
+
+impl<T, R> CallBuilder<T, ReturnType<R>>
+where
+    T: EnvTypes,
+    R: scale::Decode,
+{
+    /// Fires the call and returns the callee's result.
+    ///
+    /// # Errors
+    ///
+    /// - If the callee does not exist or is a tombstone.
+    /// - If the arguments passed to the callee message are invalid.
+    /// - If the callee execution has trapped or ran out of gas.
+    /// - If the returned value failed to decode properly.
+    /// - If the call would exceed a configured `gas_limit` or
+    ///   `storage_deposit_limit`.
+    /// - If both `CallFlags::set_forward_input` and explicitly pushed
+    ///   arguments are set.
+    pub fn fire(self) -> Result<R> {
+        if self.params.call_flags.forward_input() && !self.params.call_data.params().is_empty() {
+            return Err(EnvError::ForwardInputConflict)
+        }
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            TypedEnv::eval_contract::<T, (), R>(instance, &self.params)
+        })
+    }
+}
+
+/// The parameters of a cross-contract instantiation, ready to be dispatched
+/// to the environment.
+///
+/// # Note
+///
+/// Constructed solely through [`CreateBuilder`], analogous to how
+/// [`CallParams`] is constructed through [`CallBuilder`].
+#[derive(Debug)]
+pub struct InstantiateParams<T, Args, C>
+where
+    T: EnvTypes,
+{
+    /// The code hash of the to-be-instantiated contract.
+    code_hash: T::Hash,
+    /// The maximum gas costs allowed for the instantiation.
+    ///
+    /// `None` means "use all remaining gas", preserving today's behavior.
+    gas_limit: Option<u64>,
+    /// The maximum amount of new storage deposit the instantiation may
+    /// incur.
+    ///
+    /// `None` means "use all remaining storage deposit allowance".
+    storage_deposit_limit: Option<T::Balance>,
+    /// The endowment for the instantiated contract.
+    endowment: T::Balance,
+    /// The already encoded selector and arguments of the constructor call.
+    call_data: CallData,
+    /// The expected argument types and resulting contract type.
+    types: PhantomData<fn() -> (Args, C)>,
+}
+
+impl<T, Args, C> InstantiateParams<T, Args, C>
+where
+    T: EnvTypes,
+{
+    /// Returns the code hash of the to-be-instantiated contract.
+    pub(crate) fn code_hash(&self) -> &T::Hash {
+        &self.code_hash
+    }
+
+    /// Returns the gas limit for the contract instantiation.
+    pub(crate) fn gas_limit(&self) -> Option<u64> {
+        self.gas_limit
+    }
+
+    /// Returns the storage deposit limit for the contract instantiation.
+    pub(crate) fn storage_deposit_limit(&self) -> &Option<T::Balance> {
+        &self.storage_deposit_limit
+    }
+
+    /// Returns the endowment for the instantiated contract.
+    pub(crate) fn endowment(&self) -> &T::Balance {
+        &self.endowment
+    }
+
+    /// Returns the already encoded input data of the constructor call.
+    pub(crate) fn input_data(&self) -> &CallData {
+        &self.call_data
+    }
+}
+
+/// Builds up an [`InstantiateParams`] for instantiating another contract from
+/// its code hash, with compile-time-checked constructor argument types.
+///
+/// # Note
+///
+/// Like [`CallBuilder`], constructor arguments are SCALE-encoded into the
+/// same [`CallData`]/scoped-buffer machinery.
+#[derive(Debug)]
+pub struct CreateBuilder<T, C>
+where
+    T: EnvTypes,
+{
+    params: InstantiateParams<T, (), C>,
+}
+
+impl<T, C> CreateBuilder<T, C>
+where
+    T: EnvTypes,
+    T::Balance: Default,
+{
+    /// Creates a new create builder for instantiating a contract from the
+    /// given `code_hash`, dispatching to its constructor with `selector`.
+    pub fn instantiate(code_hash: T::Hash, selector: Selector) -> Self {
+        Self {
+            params: InstantiateParams {
+                code_hash,
+                gas_limit: None,
+                storage_deposit_limit: None,
+                endowment: Default::default(),
+                call_data: CallData::new(selector),
+                types: PhantomData,
+            },
+        }
+    }
+}
+
+impl<T, C> CreateBuilder<T, C>
+where
+    T: EnvTypes,
+{
+    /// Sets the maximum allowed gas costs for the instantiation.
+    pub fn gas_limit(mut self, gas_limit: u64) -> Self {
+        self.params.gas_limit = Some(gas_limit);
+        self
+    }
+
+    /// Sets the maximum amount of new storage deposit the instantiation
+    /// may incur.
+    pub fn storage_deposit_limit(mut self, storage_deposit_limit: T::Balance) -> Self {
+        self.params.storage_deposit_limit = Some(storage_deposit_limit);
+        self
+    }
+
+    /// Sets the endowment for the instantiated contract.
+    pub fn endowment(mut self, endowment: T::Balance) -> Self {
+        self.params.endowment = endowment;
+        self
+    }
+
+    /// Pushes an argument to the constructor call, in order.
+    pub fn push_arg<A>(mut self, arg: &A) -> Self
+    where
+        A: scale::Encode,
+    {
+        self.params.call_data.push_arg(arg);
+        self
+    }
+}
+
+impl<T> CreateBuilder<T, ()>
+where
+    T: EnvTypes,
+{
+    /// Fires the instantiation and returns the new contract's account ID.
+    ///
+    /// # Errors
+    ///
+    /// - If the code hash is invalid.
+    /// - If the constructor arguments are invalid.
+    /// - If the instantiation traps or runs out of gas.
+    /// - If the instantiation would exceed a configured `gas_limit` or
+    ///   `storage_deposit_limit`.
+    /// - If too little endowment was transferred.
+    /// - If the returned account ID failed to decode properly.
+    pub fn fire(self) -> Result<T::AccountId> {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            TypedEnv::instantiate_contract::<T, (), ()>(instance, &self.params)
+        })
+    }
+}
+
+impl<T, R> CreateBuilder<T, ReturnType<R>>
+where
+    T: EnvTypes,
+    R: scale::Decode,
+{
+    /// Fires the instantiation and returns the new contract's account ID
+    /// together with the value returned by its constructor.
+    ///
+    /// # Note
+    ///
+    /// Use this instead of [`CreateBuilder::fire`] for constructors that
+    /// return a value, e.g. a fallible constructor returning a `Result`.
+    ///
+    /// # Errors
+    ///
+    /// - If the code hash is invalid.
+    /// - If the constructor arguments are invalid.
+    /// - If the instantiation traps or runs out of gas.
+    /// - If the instantiation would exceed a configured `gas_limit` or
+    ///   `storage_deposit_limit`.
+    /// - If too little endowment was transferred.
+    /// - If the returned account ID or constructor return value failed to
+    ///   decode properly.
+    pub fn fire(self) -> Result<(T::AccountId, R)> {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            TypedEnv::eval_instantiate_contract::<T, (), R>(instance, &self.params)
+        })
+    }
+}
+
+/// The parameters of a delegate call, ready to be dispatched to the
+/// environment.
+///
+/// # Note
+///
+/// Constructed solely through [`DelegateCallBuilder`], analogous to how
+/// [`CallParams`] is constructed through [`CallBuilder`]. Unlike
+/// [`CallParams`], there is no callee account or transferred value: the
+/// code at `code_hash` executes in the *caller's own* storage and balance
+/// context, so a thin proxy contract can forward its calls to a swappable
+/// implementation hash without migrating storage.
+#[derive(Debug)]
+pub struct DelegateCallParams<T, Args, RetType>
+where
+    T: EnvTypes,
+{
+    /// The code hash of the contract to delegate the call to.
+    code_hash: T::Hash,
+    /// The already encoded selector and arguments of the call.
+    call_data: CallData,
+    /// The flags used to customize the call.
+    call_flags: CallFlags,
+    /// The expected argument and return types of the call.
+    types: PhantomData<fn() -> (Args, RetType)>,
+}
+
+impl<T, Args, RetType> DelegateCallParams<T, Args, RetType>
+where
+    T: EnvTypes,
+{
+    /// Returns the code hash of the contract to delegate the call to.
+    pub(crate) fn code_hash(&self) -> &T::Hash {
+        &self.code_hash
+    }
+
+    /// Returns the already encoded input data of the call.
+    pub(crate) fn input_data(&self) -> &CallData {
+        &self.call_data
+    }
+
+    /// Returns the call flags used to customize the call.
+    pub(crate) fn call_flags(&self) -> CallFlags {
+        self.call_flags
+    }
+}
+
+/// Builds up a [`DelegateCallParams`] for delegate-calling the code at a
+/// given code hash, executed in the caller's own storage and balance
+/// context.
+///
+/// # Examples
+///
+/// ```no_compile
+/// let result = DelegateCallBuilder::<DefaultEnvTypes, Balance>::invoke(code_hash, selector)
+///     .push_arg(&new_value)
+///     .fire()
+///     .expect("delegate call to `set` must succeed");
+/// ```
+#[derive(Debug)]
+pub struct DelegateCallBuilder<T, RetType>
+where
+    T: EnvTypes,
+{
+    params: DelegateCallParams<T, (), RetType>,
+}
+
+impl<T, RetType> DelegateCallBuilder<T, RetType>
+where
+    T: EnvTypes,
+{
+    /// Creates a new delegate call builder for delegate-calling the code at
+    /// `code_hash`, dispatching to its message with the given `selector`.
+    pub fn invoke(code_hash: T::Hash, selector: Selector) -> Self {
+        Self {
+            params: DelegateCallParams {
+                code_hash,
+                call_data: CallData::new(selector),
+                call_flags: CallFlags::default(),
+                types: PhantomData,
+            },
+        }
+    }
+
+    /// Sets the flags used to customize the delegate call.
+    pub fn call_flags(mut self, call_flags: CallFlags) -> Self {
+        self.params.call_flags = call_flags;
+        self
+    }
+
+    /// Pushes an argument to the call, in order.
+    pub fn push_arg<A>(mut self, arg: &A) -> Self
+    where
+        A: scale::Encode,
+    {
+        self.params.call_data.push_arg(arg);
+        self
+    }
+}
+
+impl<T> DelegateCallBuilder<T, ()>
+where
+    T: EnvTypes,
+{
+    /// Fires the delegate call, discarding any returned value.
+    ///
+    /// # Errors
+    ///
+    /// - If the code hash is invalid or not found.
+    /// - If the arguments passed to the delegated message are invalid.
+    /// - If the delegated execution has trapped or ran out of gas.
+    /// - If both `CallFlags::set_forward_input` and explicitly pushed
+    ///   arguments are set.
+    pub fn fire(self) -> Result<()> {
+        if self.params.call_flags.forward_input() && !self.params.call_data.params().is_empty() {
+            return Err(EnvError::ForwardInputConflict)
+        }
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            TypedEnv::invoke_contract_delegate::<T, ()>(instance, &self.params)
+        })
+    }
+}
+
+impl<T, R> DelegateCallBuilder<T, ReturnType<R>>
+where
+    T: EnvTypes,
+    R: scale::Decode,
+{
+    /// Fires the delegate call and returns the delegated message's result.
+    ///
+    /// # Errors
+    ///
+    /// - If the code hash is invalid or not found.
+    /// - If the arguments passed to the delegated message are invalid.
+    /// - If the delegated execution has trapped or ran out of gas.
+    /// - If the returned value failed to decode properly.
+    /// - If both `CallFlags::set_forward_input` and explicitly pushed
+    ///   arguments are set.
+    pub fn fire(self) -> Result<R> {
+        if self.params.call_flags.forward_input() && !self.params.call_data.params().is_empty() {
+            return Err(EnvError::ForwardInputConflict)
+        }
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            TypedEnv::eval_contract_delegate::<T, (), R>(instance, &self.params)
+        })
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A typed, registry-driven alternative to [`Env::call_chain_extension`]'s
+//! raw `func_id: u32` dispatch, gated behind the same
+//! `ink-unstable-chain-extensions` feature.
+//!
+//! [`Env::call_chain_extension`]: crate::env::Env::call_chain_extension
+
+/// Converts the runtime's raw chain extension status code into a typed
+/// error.
+///
+/// # Note
+///
+/// Implemented by a [`ChainExtensionMethod::ErrorCode`] so that
+/// [`Env::call_chain_extension_method`] can surface extension-specific
+/// failures (e.g. distinguishing "unknown asset" from "insufficient
+/// balance") instead of only the fixed [`EnvError`] set the untyped
+/// `func_id`-based host call maps everything down to.
+///
+/// [`Env::call_chain_extension_method`]: crate::env::Env::call_chain_extension_method
+/// [`EnvError`]: crate::env::EnvError
+pub trait FromStatusCode: Sized {
+    /// Converts `status_code` into `Self`, or `Ok(())` if `status_code`
+    /// indicates success.
+    fn from_status_code(status_code: u32) -> Result<(), Self>;
+}
+
+/// Maps a single chain extension method to a compile-time `func_id` and its
+/// typed input, output and error code.
+///
+/// # Note
+///
+/// Usually implemented once per extension method by a derive-style macro
+/// (e.g. generated from an `#[ink::chain_extension]`-annotated trait),
+/// rather than written out by hand, turning the extension's surface into
+/// something contracts can depend on across runtime versions instead of
+/// hand-picking `func_id`s and hand-decoding their output.
+pub trait ChainExtensionMethod {
+    /// The typed input arguments of this method.
+    type Input: scale::Encode;
+
+    /// The typed output (success) value of this method.
+    type Output: scale::Decode;
+
+    /// The error code this method's extension maps non-zero host status
+    /// codes to.
+    type ErrorCode: FromStatusCode + From<scale::Error>;
+
+    /// The func ID that identifies this method to the runtime.
+    const ID: u32;
+
+    /// The weight (gas) this method charges the caller, or `None` to fall
+    /// back to the runtime's own metering of the host call.
+    ///
+    /// # Note
+    ///
+    /// Lets an extension that knows its own cost up front (e.g. a fixed-cost
+    /// cryptographic primitive) declare it here instead of relying on the
+    /// runtime to meter the host call after the fact.
+    const WEIGHT: Option<u64> = None;
+}
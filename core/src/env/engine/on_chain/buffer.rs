@@ -0,0 +1,78 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ink_prelude::vec::Vec;
+
+/// Carves non-overlapping, consecutive slices out of a single underlying
+/// buffer, usually [`EnvInstance`][`super::EnvInstance`]'s static 16 kB
+/// scratch buffer.
+///
+/// # Note
+///
+/// Each call to [`take`][`ScopedBuffer::take`] or one of its siblings
+/// shrinks what remains of the buffer and hands back a disjoint slice of
+/// it, so the results of several calls can be held and used together, e.g.
+/// to assemble the arguments of a host call.
+///
+/// Once a request no longer fits into what remains of the static buffer, it
+/// is instead served from a fresh heap allocation sized exactly to the
+/// request, rather than panicking: contracts that deal with oversized
+/// encodings (e.g. a custom `AccountId`, or a fallible constructor's return
+/// value) degrade to an allocation instead of trapping, while `take` and
+/// `take_rest` stay allocation-free for requests that fit.
+/// [`take_encoded`][`ScopedBuffer::take_encoded`] always SCALE-encodes
+/// the value into a temporary heap buffer before copying it over.
+pub struct ScopedBuffer<'a> {
+    buffer: &'a mut [u8],
+}
+
+impl<'a> From<&'a mut [u8]> for ScopedBuffer<'a> {
+    fn from(buffer: &'a mut [u8]) -> Self {
+        Self { buffer }
+    }
+}
+
+impl<'a> ScopedBuffer<'a> {
+    /// Takes the first `len` bytes off what remains of the buffer.
+    ///
+    /// # Note
+    ///
+    /// Falls back to a heap allocation of exactly `len` bytes if that many
+    /// no longer fit into what remains of the static buffer.
+    pub fn take(&mut self, len: usize) -> &'a mut [u8] {
+        if len > self.buffer.len() {
+            return Vec::leak(ink_prelude::vec![0u8; len])
+        }
+        let buffer = core::mem::take(&mut self.buffer);
+        let (lhs, rhs) = buffer.split_at_mut(len);
+        self.buffer = rhs;
+        lhs
+    }
+
+    /// Takes all bytes that remain of the buffer.
+    pub fn take_rest(&mut self) -> &'a mut [u8] {
+        self.take(self.buffer.len())
+    }
+
+    /// Takes a slice sized and filled by SCALE-encoding `val` into it.
+    pub fn take_encoded<E>(&mut self, val: &E) -> &'a mut [u8]
+    where
+        E: scale::Encode,
+    {
+        let encoded = scale::Encode::encode(val);
+        let slice = self.take(encoded.len());
+        slice.copy_from_slice(&encoded);
+        slice
+    }
+}
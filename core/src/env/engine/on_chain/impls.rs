@@ -21,9 +21,15 @@ use super::{
 use crate::env::{
     call::{
         CallParams,
+        DelegateCallParams,
         InstantiateParams,
         ReturnType,
     },
+    chain_extension::{
+        ChainExtensionMethod,
+        FromStatusCode,
+    },
+    CallError,
     Env,
     EnvError,
     EnvTypes,
@@ -46,6 +52,12 @@ impl From<ext::Error> for EnvError {
             ext::Error::NewContractNotFunded => Self::NewContractNotFunded,
             ext::Error::CodeNotFound => Self::CodeNotFound,
             ext::Error::NotCallable => Self::NotCallable,
+            ext::Error::EcdsaRecoverFailed => Self::EcdsaRecoverFailed,
+            ext::Error::CallRuntimeFailed => Self::CallRuntimeFailed,
+            ext::Error::OutOfGas => Self::OutOfGas,
+            ext::Error::StorageDepositLimitExhausted => {
+                Self::StorageDepositLimitExhausted
+            }
         }
     }
 }
@@ -77,21 +89,107 @@ impl EnvInstance {
         R: scale::Decode,
     {
         let mut scope = self.scoped_buffer();
-        let gas_limit = params.gas_limit();
+        let gas_limit = params.gas_limit().unwrap_or(0);
         let enc_callee = scope.take_encoded(params.callee());
         let enc_transferred_value = scope.take_encoded(params.transferred_value());
+        let enc_storage_deposit_limit =
+            scope.take_encoded(params.storage_deposit_limit());
         let enc_input = scope.take_encoded(params.input_data());
         let output = &mut scope.take_rest();
         ext::call(
             enc_callee,
             gas_limit,
             enc_transferred_value,
+            enc_storage_deposit_limit,
             enc_input,
             output,
         )?;
         let decoded = scale::Decode::decode(&mut &output[..])?;
         Ok(decoded)
     }
+
+    /// Reusable implementation for instantiating another contract and
+    /// decoding the value returned by its constructor.
+    fn instantiate_contract_impl<T, Args, C, R>(
+        &mut self,
+        params: &InstantiateParams<T, Args, C>,
+    ) -> Result<(T::AccountId, R)>
+    where
+        T: EnvTypes,
+        Args: scale::Encode,
+        R: scale::Decode,
+    {
+        let mut scoped = self.scoped_buffer();
+        let gas_limit = params.gas_limit().unwrap_or(0);
+        let enc_code_hash = scoped.take_encoded(params.code_hash());
+        let enc_endowment = scoped.take_encoded(params.endowment());
+        let enc_storage_deposit_limit =
+            scoped.take_encoded(params.storage_deposit_limit());
+        let enc_input = scoped.take_encoded(params.input_data());
+        // In the default configuration encoded `AccountId` require 32
+        // bytes; we reserve 1024 to comfortably cover custom `AccountId`
+        // types too. `ScopedBuffer::take` spills to the heap only when fewer
+        // than 1024 bytes remain in the static buffer; an encoded
+        // `AccountId` longer than 1024 bytes does not fit.
+        let out_address = &mut scoped.take(1024);
+        let out_return_value = &mut scoped.take_rest();
+        ext::instantiate(
+            enc_code_hash,
+            gas_limit,
+            enc_endowment,
+            enc_storage_deposit_limit,
+            enc_input,
+            out_address,
+            out_return_value,
+        )?;
+        let account_id = scale::Decode::decode(&mut &out_address[..])?;
+        let return_value = scale::Decode::decode(&mut &out_return_value[..])?;
+        Ok((account_id, return_value))
+    }
+
+    /// Reusable implementation for delegate-calling the code at a given
+    /// hash, executed in the caller's own storage and balance context.
+    fn invoke_contract_delegate_impl<T, Args, RetType, R>(
+        &mut self,
+        params: &DelegateCallParams<T, Args, RetType>,
+    ) -> Result<R>
+    where
+        T: EnvTypes,
+        Args: scale::Encode,
+        R: scale::Decode,
+    {
+        let mut scope = self.scoped_buffer();
+        let enc_code_hash = scope.take_encoded(params.code_hash());
+        let enc_input = scope.take_encoded(params.input_data());
+        let flags = params.call_flags().into_u32();
+        let output = &mut scope.take_rest();
+        ext::delegate_call(flags, enc_code_hash, enc_input, output)?;
+        let decoded = scale::Decode::decode(&mut &output[..])?;
+        Ok(decoded)
+    }
+
+    /// Reusable implementation for invoking another contract message
+    /// without trapping the caller on a callee failure.
+    fn try_invoke_contract_impl<T, Args, RetType, R>(
+        &mut self,
+        params: &CallParams<T, Args, RetType>,
+    ) -> Result<core::result::Result<R, CallError>>
+    where
+        T: EnvTypes,
+        Args: scale::Encode,
+        R: scale::Decode,
+    {
+        match self.invoke_contract_impl(params) {
+            Ok(value) => Ok(Ok(value)),
+            Err(EnvError::CalleeTrapped) => Ok(Err(CallError::CalleeTrapped)),
+            Err(EnvError::CalleeReverted) => Ok(Err(CallError::CalleeReverted)),
+            Err(EnvError::KeyNotFound) => Ok(Err(CallError::KeyNotFound)),
+            Err(EnvError::TransferFailed) => Ok(Err(CallError::TransferFailed)),
+            Err(EnvError::NotCallable) => Ok(Err(CallError::NotCallable)),
+            Err(EnvError::CodeNotFound) => Ok(Err(CallError::CodeNotFound)),
+            Err(other) => Err(other),
+        }
+    }
 }
 
 impl Env for EnvInstance {
@@ -121,6 +219,32 @@ impl Env for EnvInstance {
         ext::clear_storage(key.as_bytes())
     }
 
+    fn set_transient_storage<V>(&mut self, key: &Key, value: &V)
+    where
+        V: scale::Encode,
+    {
+        let buffer = self.scoped_buffer().take_encoded(value);
+        ext::set_transient_storage(key.as_bytes(), &buffer[..]);
+    }
+
+    fn get_transient_storage<R>(&mut self, key: &Key) -> Result<Option<R>>
+    where
+        R: scale::Decode,
+    {
+        let output = &mut self.scoped_buffer().take_rest();
+        match ext::get_transient_storage(key.as_bytes(), output) {
+            Ok(_) => (),
+            Err(ExtError::KeyNotFound) => return Ok(None),
+            Err(_) => panic!("encountered unexpected error"),
+        }
+        let decoded = scale::Decode::decode(&mut &output[..])?;
+        Ok(Some(decoded))
+    }
+
+    fn clear_transient_storage(&mut self, key: &Key) {
+        ext::clear_transient_storage(key.as_bytes())
+    }
+
     fn decode_input<T>(&mut self) -> Result<T>
     where
         T: scale::Decode,
@@ -157,6 +281,33 @@ impl Env for EnvInstance {
         ext::hash_sha2_256(input, output)
     }
 
+    fn ecdsa_recover(
+        &mut self,
+        signature: &[u8; 65],
+        message_hash: &[u8; 32],
+        output: &mut [u8; 33],
+    ) -> Result<()> {
+        ext::ecdsa_recover(signature, message_hash, output).map_err(Into::into)
+    }
+
+    fn ed25519_verify(
+        &mut self,
+        signature: &[u8; 64],
+        message: &[u8],
+        pub_key: &[u8; 32],
+    ) -> bool {
+        ext::ed25519_verify(signature, message, pub_key)
+    }
+
+    fn sr25519_verify(
+        &mut self,
+        signature: &[u8; 64],
+        message: &[u8],
+        pub_key: &[u8; 32],
+    ) -> bool {
+        ext::sr25519_verify(signature, message, pub_key)
+    }
+
     #[cfg(feature = "ink-unstable-chain-extensions")]
     fn call_chain_extension<I, O>(
         &mut self,
@@ -173,6 +324,25 @@ impl Env for EnvInstance {
         ext::call_chain_extension(func_id, enc_input, output)?;
         scale::Decode::decode(&mut &output[..]).map_err(Into::into)
     }
+
+    #[cfg(feature = "ink-unstable-chain-extensions")]
+    fn call_chain_extension_method<M>(
+        &mut self,
+        input: &M::Input,
+    ) -> core::result::Result<M::Output, M::ErrorCode>
+    where
+        M: ChainExtensionMethod,
+    {
+        if let Some(weight) = M::WEIGHT {
+            ext::charge_weight(weight);
+        }
+        let mut scope = self.scoped_buffer();
+        let enc_input = scope.take_encoded(input);
+        let output = &mut scope.take_rest();
+        let status_code = ext::call_chain_extension_status(M::ID, enc_input, output);
+        M::ErrorCode::from_status_code(status_code)?;
+        scale::Decode::decode(&mut &output[..]).map_err(Into::into)
+    }
 }
 
 impl TypedEnv for EnvInstance {
@@ -246,6 +416,52 @@ impl TypedEnv for EnvInstance {
         self.invoke_contract_impl(call_params)
     }
 
+    fn try_invoke_contract<T, Args>(
+        &mut self,
+        call_params: &CallParams<T, Args, ()>,
+    ) -> Result<core::result::Result<(), CallError>>
+    where
+        T: EnvTypes,
+        Args: scale::Encode,
+    {
+        self.try_invoke_contract_impl(call_params)
+    }
+
+    fn try_eval_contract<T, Args, R>(
+        &mut self,
+        call_params: &CallParams<T, Args, ReturnType<R>>,
+    ) -> Result<core::result::Result<R, CallError>>
+    where
+        T: EnvTypes,
+        Args: scale::Encode,
+        R: scale::Decode,
+    {
+        self.try_invoke_contract_impl(call_params)
+    }
+
+    fn invoke_contract_tail<T, Args>(&mut self, call_params: &CallParams<T, Args, ()>) -> !
+    where
+        T: EnvTypes,
+        Args: scale::Encode,
+    {
+        let mut scope = self.scoped_buffer();
+        let gas_limit = call_params.gas_limit().unwrap_or(0);
+        let enc_callee = scope.take_encoded(call_params.callee());
+        let enc_transferred_value = scope.take_encoded(call_params.transferred_value());
+        let enc_storage_deposit_limit =
+            scope.take_encoded(call_params.storage_deposit_limit());
+        let enc_input = scope.take_encoded(call_params.input_data());
+        let flags = call_params.call_flags().into_u32();
+        ext::call_tail(
+            flags,
+            enc_callee,
+            gas_limit,
+            enc_transferred_value,
+            enc_storage_deposit_limit,
+            enc_input,
+        );
+    }
+
     fn eval_contract<T, Args, R>(
         &mut self,
         call_params: &CallParams<T, Args, ReturnType<R>>,
@@ -266,30 +482,43 @@ impl TypedEnv for EnvInstance {
         T: EnvTypes,
         Args: scale::Encode,
     {
-        let mut scoped = self.scoped_buffer();
-        let gas_limit = params.gas_limit();
-        let enc_code_hash = scoped.take_encoded(params.code_hash());
-        let enc_endowment = scoped.take_encoded(params.endowment());
-        let enc_input = scoped.take_encoded(params.input_data());
-        // We support `AccountId` types with an encoding that requires up to
-        // 1024 bytes. Beyond that limit ink! contracts will trap for now.
-        // In the default configuration encoded `AccountId` require 32 bytes.
-        let out_address = &mut scoped.take(1024);
-        let out_return_value = &mut scoped.take_rest();
-        // We currently do nothing with the `out_return_value` buffer.
-        // This should change in the future but for that we need to add support
-        // for constructors that may return values.
-        // This is useful to support fallible constructors for example.
-        ext::instantiate(
-            enc_code_hash,
-            gas_limit,
-            enc_endowment,
-            enc_input,
-            out_address,
-            out_return_value,
-        )?;
-        let account_id = scale::Decode::decode(&mut &out_address[..])?;
-        Ok(account_id)
+        self.instantiate_contract_impl::<T, Args, C, ()>(params)
+            .map(|(account_id, ())| account_id)
+    }
+
+    fn eval_instantiate_contract<T, Args, R>(
+        &mut self,
+        params: &InstantiateParams<T, Args, ReturnType<R>>,
+    ) -> Result<(T::AccountId, R)>
+    where
+        T: EnvTypes,
+        Args: scale::Encode,
+        R: scale::Decode,
+    {
+        self.instantiate_contract_impl(params)
+    }
+
+    fn invoke_contract_delegate<T, Args>(
+        &mut self,
+        params: &DelegateCallParams<T, Args, ()>,
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+        Args: scale::Encode,
+    {
+        self.invoke_contract_delegate_impl(params)
+    }
+
+    fn eval_contract_delegate<T, Args, R>(
+        &mut self,
+        params: &DelegateCallParams<T, Args, ReturnType<R>>,
+    ) -> Result<R>
+    where
+        T: EnvTypes,
+        Args: scale::Encode,
+        R: scale::Decode,
+    {
+        self.invoke_contract_delegate_impl(params)
     }
 
     fn restore_contract<T>(
@@ -347,4 +576,13 @@ impl TypedEnv for EnvInstance {
         ext::random(enc_subject, output);
         scale::Decode::decode(&mut &output[..]).map_err(Into::into)
     }
+
+    fn call_runtime<T, Call>(&mut self, call: &Call) -> Result<()>
+    where
+        T: EnvTypes,
+        Call: scale::Encode,
+    {
+        let enc_call = self.scoped_buffer().take_encoded(call);
+        ext::call_runtime(enc_call).map_err(Into::into)
+    }
 }
@@ -0,0 +1,98 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Marker types selecting one of the runtime's native hash functions, so
+//! that [`crate::env::hash_bytes`] and [`crate::env::hash_encoded`] can be
+//! generic over the algorithm instead of contracts calling a differently
+//! named free function per hash and per output width.
+
+use super::{
+    backend::Env,
+    engine::{
+        EnvInstance,
+        OnInstance,
+    },
+};
+
+/// A hash algorithm that can be run via the runtime's native implementation.
+pub trait CryptoHash: HashOutput {
+    /// Computes the hash of the given `input` and writes it into `output`.
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type);
+}
+
+/// The output type of a [`CryptoHash`] algorithm.
+pub trait HashOutput {
+    /// The fixed-size byte array this hash algorithm writes its digest into.
+    type Type: Default + AsMut<[u8]>;
+}
+
+/// The SHA-2 256-bit hash algorithm.
+pub enum Sha2x256 {}
+
+/// The KECCAK 256-bit hash algorithm.
+pub enum Keccak256 {}
+
+/// The BLAKE2 256-bit hash algorithm.
+pub enum Blake2x256 {}
+
+/// The BLAKE2 128-bit hash algorithm.
+pub enum Blake2x128 {}
+
+impl HashOutput for Sha2x256 {
+    type Type = [u8; 32];
+}
+
+impl HashOutput for Keccak256 {
+    type Type = [u8; 32];
+}
+
+impl HashOutput for Blake2x256 {
+    type Type = [u8; 32];
+}
+
+impl HashOutput for Blake2x128 {
+    type Type = [u8; 16];
+}
+
+impl CryptoHash for Sha2x256 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            Env::hash_sha2_256(instance, input, output)
+        })
+    }
+}
+
+impl CryptoHash for Keccak256 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            Env::hash_keccak_256(instance, input, output)
+        })
+    }
+}
+
+impl CryptoHash for Blake2x256 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            Env::hash_blake2_256(instance, input, output)
+        })
+    }
+}
+
+impl CryptoHash for Blake2x128 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            Env::hash_blake2_128(instance, input, output)
+        })
+    }
+}
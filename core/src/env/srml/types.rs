@@ -24,13 +24,38 @@ impl EnvTypes for DefaultSrmlTypes {
     type Address = self::Address;
     type Balance = self::Balance;
     type Call = self::Call;
+    type Hash = self::Hash;
+    type Timestamp = self::Timestamp;
+    type BlockNumber = self::BlockNumber;
+    type EventRecord = self::EventRecord;
 }
 
 /// The default SRML address type
 pub type Address = node_runtime::Address;
 
 /// The default SRML balance type.
-pub type Balance = u64;
+///
+/// # Note
+///
+/// Widened from `u64` to `u128` so that native token amounts of runtimes
+/// whose balance type outgrows 64 bits (tracked generically via `num-traits`
+/// with its `i128` feature enabled) round-trip correctly through
+/// `parity_scale_codec` and `TypedCell<Balance>`.
+pub type Balance = u128;
 
 /// The default SRML call type
 pub type Call = node_runtime::Call;
+
+/// The default SRML hash type.
+///
+/// Used for `blake2`/`keccak` outputs and storage keys.
+pub type Hash = node_runtime::Hash;
+
+/// The default SRML timestamp type, as returned by the `now` accessor.
+pub type Timestamp = u64;
+
+/// The default SRML block number type.
+pub type BlockNumber = u32;
+
+/// The default SRML event record type.
+pub type EventRecord = node_runtime::EventRecord;
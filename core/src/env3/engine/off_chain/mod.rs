@@ -20,6 +20,7 @@ pub mod test_api;
 mod typed_encoded;
 mod types;
 
+pub(crate) use self::db::EmittedEvent;
 use self::{
     db::{
         Account,
@@ -27,7 +28,6 @@ use self::{
         Block,
         ChainSpec,
         Console,
-        EmittedEvent,
         EmittedEventsRecorder,
         ExecContext,
     },
@@ -54,6 +54,10 @@ use super::OnInstance;
 use crate::env3::EnvTypes;
 use core::cell::RefCell;
 use derive_more::From;
+use ink_prelude::{
+    collections::BTreeMap,
+    vec::Vec,
+};
 
 #[derive(Debug, From)]
 pub enum OffChainError {
@@ -65,6 +69,10 @@ pub enum OffChainError {
     UninitializedExecutionContext,
     #[from(ignore)]
     UnregisteredRuntimeCallHandler,
+    #[from(ignore)]
+    UnknownSnapshot,
+    #[from(ignore)]
+    OutOfGas,
 }
 
 pub type Result<T> = core::result::Result<T, OffChainError>;
@@ -89,6 +97,35 @@ pub struct EnvInstance {
     runtime_call_handler: RuntimeCallHandler,
     /// Emitted events recorder.
     emitted_events: EmittedEventsRecorder,
+    /// Depth of `exec_context` recorded by each still-open [`snapshot`][
+    /// `EnvInstance::snapshot`], or `None` once reverted or committed.
+    snapshots: Vec<Option<usize>>,
+    /// The maximum amount of gas a single call is allowed to consume, if any.
+    gas_limit: Option<u64>,
+    /// The amount of gas consumed by the currently executing call so far.
+    gas_used: u64,
+    /// Queued mock responses for runtime calls, keyed by their SCALE-encoded
+    /// call selector. Responses are consumed in FIFO order so that a
+    /// contract issuing the same call several times receives them in the
+    /// order they were queued.
+    call_mocks: BTreeMap<Vec<u8>, Vec<CallMockResult>>,
+}
+
+/// Identifies an environment checkpoint opened by [`EnvInstance::snapshot`].
+///
+/// Created through [`EnvInstance::snapshot`], and consumed by either
+/// [`EnvInstance::revert_to`] or [`EnvInstance::commit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotId(usize);
+
+/// A single queued response for a mocked runtime call.
+#[derive(Debug)]
+pub(crate) enum CallMockResult {
+    /// The runtime call succeeds, yielding this SCALE-encoded payload.
+    Ok(Vec<u8>),
+    /// The runtime call is rejected, yielding this SCALE-encoded dispatch
+    /// error.
+    Err(Vec<u8>),
 }
 
 impl EnvInstance {
@@ -103,7 +140,182 @@ impl EnvInstance {
             runtime_storage: RuntimeStorage::new(),
             runtime_call_handler: RuntimeCallHandler::new(),
             emitted_events: EmittedEventsRecorder::new(),
+            snapshots: Vec::new(),
+            gas_limit: None,
+            gas_used: 0,
+            call_mocks: BTreeMap::new(),
+        }
+    }
+
+    /// Opens a checkpoint of the environment's nested call state.
+    ///
+    /// # Note
+    ///
+    /// This is the foundation of an EVM-style revert mechanism: pair a
+    /// `snapshot` with [`EnvInstance::revert_to`] to pop the call frames a
+    /// simulated cross-contract call pushed if it traps, or with
+    /// [`EnvInstance::commit`] to discard the checkpoint once the call
+    /// went through.
+    ///
+    /// Only the nested execution context stack (`exec_context`) is
+    /// journaled so far: `revert_to` pops back to the exact call depth a
+    /// checkpoint was opened at. Extending this to also undo mutations
+    /// against the accounts database, the emulated runtime storage and
+    /// the emitted events recorder - ideally as a journal of the
+    /// individual deltas applied per frame, rather than a full snapshot
+    /// of each - is tracked as follow-up work.
+    pub fn snapshot(&mut self) -> SnapshotId {
+        let id = SnapshotId(self.snapshots.len());
+        self.snapshots.push(Some(self.exec_context.len()));
+        id
+    }
+
+    /// Reverts the environment back to the call depth recorded by `id`,
+    /// undoing every nested call frame pushed since the checkpoint was
+    /// opened, and consumes `id`.
+    ///
+    /// # Errors
+    ///
+    /// If `id` does not refer to a still-open checkpoint, e.g. because it
+    /// was already reverted or committed.
+    pub fn revert_to(&mut self, id: SnapshotId) -> Result<()> {
+        let depth = self
+            .snapshots
+            .get_mut(id.0)
+            .and_then(Option::take)
+            .ok_or(OffChainError::UnknownSnapshot)?;
+        self.exec_context.truncate(depth);
+        Ok(())
+    }
+
+    /// Discards the checkpoint recorded by `id` without undoing anything,
+    /// and consumes `id`.
+    ///
+    /// # Errors
+    ///
+    /// If `id` does not refer to a still-open checkpoint, e.g. because it
+    /// was already reverted or committed.
+    pub fn commit(&mut self, id: SnapshotId) -> Result<()> {
+        self.snapshots
+            .get_mut(id.0)
+            .and_then(Option::take)
+            .ok_or(OffChainError::UnknownSnapshot)?;
+        Ok(())
+    }
+
+    /// Returns the amount of gas consumed by the currently executing call.
+    pub fn consumed_gas(&self) -> u64 {
+        self.gas_used
+    }
+
+    /// Sets the maximum amount of gas a single call is allowed to consume.
+    ///
+    /// # Note
+    ///
+    /// Pass `None` to lift the limit again. Does not reset the amount of gas
+    /// already consumed.
+    pub fn set_gas_limit(&mut self, limit: Option<u64>) {
+        self.gas_limit = limit;
+    }
+
+    /// Charges `amount` of gas against the currently executing call.
+    ///
+    /// # Note
+    ///
+    /// This is the single charge point every gas-metered operation is meant
+    /// to run through. [`EnvInstance::dispatch_mocked_call`] already charges
+    /// through it for every dispatched runtime call. Wiring it into storage
+    /// reads/writes through `RuntimeStorage` and event emission as well is
+    /// tracked as follow-up work, since those mutation sites live in
+    /// `runtime_storage.rs`/`db.rs`, which are not part of this checkout.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OffChainError::OutOfGas`] if charging `amount` would exceed
+    /// the configured [`EnvInstance::set_gas_limit`]. This does not by
+    /// itself revert anything; [`EnvInstance::dispatch_mocked_call`] reverts
+    /// to the latest open [`EnvInstance::snapshot`] on this error, which
+    /// restores the execution context depth only.
+    pub(crate) fn charge_gas(&mut self, amount: u64) -> Result<()> {
+        let new_gas_used = self.gas_used.saturating_add(amount);
+        if let Some(limit) = self.gas_limit {
+            if new_gas_used > limit {
+                return Err(OffChainError::OutOfGas)
+            }
+        }
+        self.gas_used = new_gas_used;
+        Ok(())
+    }
+
+    /// Reverts to the most recently opened still-open [`EnvInstance::snapshot`],
+    /// if any, consuming it.
+    fn revert_to_latest_snapshot(&mut self) {
+        let latest = self
+            .snapshots
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, depth)| depth.map(|_| SnapshotId(index)));
+        if let Some(id) = latest {
+            let _ = self.revert_to(id);
+        }
+    }
+
+    /// Queues `result` as the next response for calls matching the
+    /// SCALE-encoded `selector`.
+    pub(crate) fn mock_call(&mut self, selector: Vec<u8>, result: CallMockResult) {
+        self.call_mocks.entry(selector).or_insert_with(Vec::new).push(result);
+    }
+
+    /// Flat gas cost charged for dispatching a single mocked runtime call.
+    const CALL_GAS_COST: u64 = 1;
+
+    /// Dispatches a mocked runtime call, consuming the next queued response
+    /// for `selector`.
+    ///
+    /// # Note
+    ///
+    /// The outer `Result` is the environment-level outcome: it is an
+    /// [`OffChainError::UnregisteredRuntimeCallHandler`] only if `selector`
+    /// has no queued responses left, or an [`OffChainError::OutOfGas`] if
+    /// dispatching the call would exceed the configured
+    /// [`EnvInstance::set_gas_limit`] - in which case the most recently
+    /// opened still-open [`EnvInstance::snapshot`], if any, is reverted
+    /// before the error is returned, popping the execution context stack
+    /// back to its recorded depth. The inner `Result` is the
+    /// call's own dispatch outcome - `Ok` with the SCALE-encoded return
+    /// payload, or `Err` with a SCALE-encoded dispatch error - mirroring
+    /// how a dispatchable call can itself fail even though invoking it
+    /// succeeded.
+    ///
+    /// Wiring this into the path a contract's own runtime call takes is
+    /// tracked as follow-up work, since `RuntimeCallHandler` and the actual
+    /// call dispatch machinery in `runtime_calls.rs` are not part of this
+    /// checkout.
+    ///
+    /// # Errors
+    ///
+    /// If `selector` has no queued responses left, or if dispatching the
+    /// call would exceed the gas limit.
+    pub(crate) fn dispatch_mocked_call(
+        &mut self,
+        selector: &[u8],
+    ) -> Result<core::result::Result<Vec<u8>, Vec<u8>>> {
+        if let Err(err) = self.charge_gas(Self::CALL_GAS_COST) {
+            self.revert_to_latest_snapshot();
+            return Err(err)
+        }
+        let queue = self
+            .call_mocks
+            .get_mut(selector)
+            .ok_or(OffChainError::UnregisteredRuntimeCallHandler)?;
+        if queue.is_empty() {
+            return Err(OffChainError::UnregisteredRuntimeCallHandler)
         }
+        Ok(match queue.remove(0) {
+            CallMockResult::Ok(bytes) => Ok(bytes),
+            CallMockResult::Err(bytes) => Err(bytes),
+        })
     }
 
     /// Advances the chain by a single block.
@@ -111,13 +323,34 @@ impl EnvInstance {
     where
         T: EnvTypes,
     {
-        let new_block_number = T::BlockNumber::from(self.blocks.len() as u32);
+        let new_block_number =
+            self.current_block()?.block_number::<T>()? + T::BlockNumber::from(1u32);
         let new_time_stamp = self.current_block()?.time_stamp::<T>()?
             + self.chain_spec.block_time::<T>()?;
         self.blocks.push(Block::new::<T>(new_block_number, new_time_stamp));
         Ok(())
     }
 
+    /// Sets the timestamp of the current block.
+    pub fn set_block_timestamp<T>(&mut self, new_value: T::Timestamp) -> crate::env3::Result<()>
+    where
+        T: EnvTypes,
+    {
+        let block_number = self.current_block()?.block_number::<T>()?;
+        *self.current_block_mut()? = Block::new::<T>(block_number, new_value);
+        Ok(())
+    }
+
+    /// Sets the block number of the current block.
+    pub fn set_block_number<T>(&mut self, new_value: T::BlockNumber) -> crate::env3::Result<()>
+    where
+        T: EnvTypes,
+    {
+        let time_stamp = self.current_block()?.time_stamp::<T>()?;
+        *self.current_block_mut()? = Block::new::<T>(new_value, time_stamp);
+        Ok(())
+    }
+
     /// Returns the current execution context.
     fn exec_context(&self) -> Result<&ExecContext> {
         self.exec_context
@@ -143,6 +376,11 @@ impl EnvInstance {
             .last_mut()
             .ok_or_else(|| OffChainError::UninitializedBlocks)
     }
+
+    /// Returns the events recorded so far by the off-chain test environment.
+    pub(crate) fn emitted_events(&self) -> &[EmittedEvent] {
+        self.emitted_events.all()
+    }
 }
 
 impl OnInstance for EnvInstance {
@@ -158,3 +396,58 @@ impl OnInstance for EnvInstance {
         INSTANCE.with(|instance| f(&mut instance.borrow_mut()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn charge_gas_respects_limit() {
+        let mut instance = EnvInstance::uninitialized();
+        instance.set_gas_limit(Some(10));
+        assert_eq!(instance.consumed_gas(), 0);
+        assert!(instance.charge_gas(4).is_ok());
+        assert_eq!(instance.consumed_gas(), 4);
+        assert!(matches!(instance.charge_gas(7), Err(OffChainError::OutOfGas)));
+        // A rejected charge must not move `consumed_gas` past the limit.
+        assert_eq!(instance.consumed_gas(), 4);
+        assert!(instance.charge_gas(6).is_ok());
+        assert_eq!(instance.consumed_gas(), 10);
+    }
+
+    #[test]
+    fn charge_gas_without_limit_never_fails() {
+        let mut instance = EnvInstance::uninitialized();
+        assert!(instance.charge_gas(u64::MAX).is_ok());
+        assert_eq!(instance.consumed_gas(), u64::MAX);
+    }
+
+    #[test]
+    fn dispatch_mocked_call_charges_gas() {
+        let mut instance = EnvInstance::uninitialized();
+        let selector = Vec::from([1, 2, 3, 4]);
+        instance.mock_call(selector.clone(), CallMockResult::Ok(Vec::from([42])));
+        assert_eq!(instance.consumed_gas(), 0);
+        assert_eq!(
+            instance.dispatch_mocked_call(&selector).unwrap(),
+            Ok(Vec::from([42]))
+        );
+        assert_eq!(instance.consumed_gas(), EnvInstance::CALL_GAS_COST);
+    }
+
+    #[test]
+    fn dispatch_mocked_call_reverts_on_out_of_gas() {
+        let mut instance = EnvInstance::uninitialized();
+        let selector = Vec::from([1, 2, 3, 4]);
+        instance.mock_call(selector.clone(), CallMockResult::Ok(Vec::new()));
+        instance.set_gas_limit(Some(0));
+        let snapshot = instance.snapshot();
+        assert!(matches!(
+            instance.dispatch_mocked_call(&selector),
+            Err(OffChainError::OutOfGas)
+        ));
+        // The snapshot opened before the call was consumed by the revert
+        // that `OutOfGas` triggers.
+        assert!(instance.revert_to(snapshot).is_err());
+    }
+}
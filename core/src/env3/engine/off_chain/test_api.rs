@@ -0,0 +1,336 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Operations on the off-chain testing environment.
+
+use super::{
+    CallMockResult,
+    EmittedEvent,
+    EnvInstance,
+    OnInstance,
+    SnapshotId,
+};
+use crate::env3::{
+    EnvTypes,
+    Result,
+};
+use ink_prelude::vec::Vec;
+use scale::{
+    Decode,
+    Encode,
+};
+
+/// Advances the chain by a single block.
+///
+/// # Note
+///
+/// This increments the current block number by one and bumps the block
+/// timestamp by the chain's configured per-block duration, allowing
+/// contracts with height- or time-dependent logic to be driven forward
+/// deterministically in tests.
+pub fn advance_block<T>() -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.advance_block::<T>())
+}
+
+/// Sets the timestamp of the current block.
+///
+/// # Note
+///
+/// Useful for testing vesting schedules, auctions or other time-dependent
+/// contract logic without waiting for [`advance_block`] to tick forward.
+///
+/// # Errors
+///
+/// If no block has been initialized yet.
+pub fn set_block_timestamp<T>(new_value: T::Timestamp) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.set_block_timestamp::<T>(new_value)
+    })
+}
+
+/// Sets the block number of the current block.
+///
+/// # Errors
+///
+/// If no block has been initialized yet.
+pub fn set_block_number<T>(new_value: T::BlockNumber) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.set_block_number::<T>(new_value)
+    })
+}
+
+/// Opens a checkpoint of the current off-chain execution context depth.
+///
+/// # Note
+///
+/// Pair this with [`revert_to`] to pop the nested call frames a failed
+/// inner call pushed, or with [`commit`] to discard the checkpoint once the
+/// call is known to have succeeded. Reverting does not undo changes to
+/// accounts, the emulated runtime storage or the recorded events.
+pub fn snapshot() -> SnapshotId {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.snapshot())
+}
+
+/// Reverts the off-chain execution context stack back to the call depth
+/// recorded by the checkpoint `id`.
+///
+/// # Errors
+///
+/// If `id` does not refer to a still-open checkpoint.
+pub fn revert_to(id: SnapshotId) -> Result<()> {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.revert_to(id))
+}
+
+/// Discards the checkpoint `id` without reverting anything.
+///
+/// # Errors
+///
+/// If `id` does not refer to a still-open checkpoint.
+pub fn commit(id: SnapshotId) -> Result<()> {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.commit(id))
+}
+
+/// Returns the amount of gas consumed by the currently executing call.
+pub fn consumed_gas() -> u64 {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.consumed_gas())
+}
+
+/// Sets the maximum amount of gas a single call is allowed to consume.
+///
+/// # Note
+///
+/// Pass `None` to lift the limit again. Does not reset the amount of gas
+/// already consumed so far.
+pub fn set_gas_limit(limit: Option<u64>) {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.set_gas_limit(limit))
+}
+
+/// Begins queuing mock responses for runtime calls matching the
+/// SCALE-encoded `selector`.
+///
+/// # Note
+///
+/// A contract issuing the same call several times receives the queued
+/// responses in the order they were chained here, e.g.:
+///
+/// ```no_run
+/// # use ink_core::env3::test::mock_call;
+/// mock_call(vec![0x01, 0x02, 0x03, 0x04])
+///     .returns(42u128)
+///     .returns(1337u128);
+/// ```
+pub fn mock_call(selector: Vec<u8>) -> MockRuntimeCallBuilder {
+    MockRuntimeCallBuilder { selector }
+}
+
+/// Builder for queuing mock responses to a specific runtime call.
+///
+/// Created through [`mock_call`].
+pub struct MockRuntimeCallBuilder {
+    selector: Vec<u8>,
+}
+
+impl MockRuntimeCallBuilder {
+    /// Queues a successful response, SCALE-encoding `value` as the call's
+    /// return payload.
+    pub fn returns<T>(self, value: T) -> Self
+    where
+        T: Encode,
+    {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            instance.mock_call(self.selector.clone(), CallMockResult::Ok(value.encode()))
+        });
+        self
+    }
+
+    /// Queues a failing response, SCALE-encoding `err` as the call's
+    /// dispatch error.
+    pub fn fails<E>(self, err: E) -> Self
+    where
+        E: Encode,
+    {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            instance.mock_call(self.selector.clone(), CallMockResult::Err(err.encode()))
+        });
+        self
+    }
+}
+
+/// A single event emitted by a contract and recorded by the off-chain
+/// test environment.
+///
+/// # Note
+///
+/// Retains the event's encoded topic hashes alongside its SCALE-encoded
+/// payload so that tests can assert on precisely which topics and data
+/// were emitted, not merely how many events were emitted.
+#[derive(Debug, Clone)]
+pub struct RecordedEvent {
+    topics: Vec<Vec<u8>>,
+    data: Vec<u8>,
+}
+
+impl From<&EmittedEvent> for RecordedEvent {
+    fn from(event: &EmittedEvent) -> Self {
+        Self {
+            topics: event.topics.clone(),
+            data: event.data.clone(),
+        }
+    }
+}
+
+impl RecordedEvent {
+    /// Returns the encoded topic hashes the event was emitted with.
+    pub fn topics(&self) -> &[Vec<u8>] {
+        &self.topics
+    }
+
+    /// Returns `true` if the event was emitted with the given encoded
+    /// topic hash among its topics.
+    pub fn has_topic(&self, topic: &[u8]) -> bool {
+        self.topics.iter().any(|recorded| recorded.as_slice() == topic)
+    }
+
+    /// Returns the SCALE-encoded payload of the event.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Decodes the event's payload into the given concrete event type.
+    ///
+    /// # Errors
+    ///
+    /// If the payload cannot be decoded as `E`.
+    pub fn decode<E>(&self) -> core::result::Result<E, scale::Error>
+    where
+        E: Decode,
+    {
+        E::decode(&mut &self.data[..])
+    }
+}
+
+/// Returns an iterator over all events recorded so far by the off-chain
+/// test environment, in the order they were emitted.
+///
+/// # Note
+///
+/// Use [`RecordedEventsExt::filter_by_topic`] to narrow the iterator down
+/// to events that were emitted with a specific encoded topic hash, e.g.
+/// to assert that a `Transfer` event with particular `from`/`to`/`value`
+/// topics was emitted rather than merely that some event occurred.
+pub fn recorded_events() -> impl Iterator<Item = RecordedEvent> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .emitted_events()
+            .iter()
+            .map(RecordedEvent::from)
+            .collect::<Vec<_>>()
+    })
+    .into_iter()
+}
+
+/// Returns an iterator over the recorded events that successfully decode as
+/// `Event`, in the order they were emitted.
+///
+/// # Note
+///
+/// Events whose payload cannot be decoded as `Event` - e.g. because they
+/// were emitted by a different contract or an earlier version of this one -
+/// are skipped rather than causing a panic, so a test asserting on one
+/// event type is not upset by unrelated events also present in the
+/// recording.
+///
+/// Topics are not yet compared structurally against the `TypedEncoded`
+/// type registry the environment maintains for indexed `AccountId`/`Hash`
+/// fields; until that wiring lands, callers needing topic-aware assertions
+/// should combine this with [`RecordedEventsExt::filter_by_topic`] on the
+/// raw [`recorded_events`].
+pub fn decoded_events<Event>() -> impl Iterator<Item = Event>
+where
+    Event: Decode,
+{
+    recorded_events().filter_map(|event| event.decode::<Event>().ok())
+}
+
+/// Asserts that an event equal to `expected` was emitted.
+///
+/// # Panics
+///
+/// If no recorded event decodes as `Event` and compares equal to `expected`.
+pub fn assert_event_emitted<Event>(expected: &Event)
+where
+    Event: Decode + PartialEq + core::fmt::Debug,
+{
+    let emitted = decoded_events::<Event>().any(|event| &event == expected);
+    assert!(
+        emitted,
+        "expected event {:?} to have been emitted, but it was not",
+        expected,
+    );
+}
+
+/// Asserts that exactly `count` recorded events decode as `Event`.
+///
+/// # Panics
+///
+/// If the number of recorded events that decode as `Event` is not `count`.
+pub fn assert_event_count<Event>(count: usize)
+where
+    Event: Decode,
+{
+    let actual = decoded_events::<Event>().count();
+    assert_eq!(
+        actual, count,
+        "expected {} events of this type to have been emitted, found {}",
+        count, actual,
+    );
+}
+
+/// Extension trait providing topic-based queries over recorded events.
+pub trait RecordedEventsExt: Iterator<Item = RecordedEvent> + Sized {
+    /// Filters the iterator down to events that were emitted with the
+    /// given encoded topic hash among their topics.
+    fn filter_by_topic(self, topic: Vec<u8>) -> RecordedEventsByTopic<Self> {
+        RecordedEventsByTopic { iter: self, topic }
+    }
+}
+
+impl<I> RecordedEventsExt for I where I: Iterator<Item = RecordedEvent> {}
+
+/// Iterator adapter yielding only the recorded events that were emitted
+/// with a specific topic. Created by [`RecordedEventsExt::filter_by_topic`].
+pub struct RecordedEventsByTopic<I> {
+    iter: I,
+    topic: Vec<u8>,
+}
+
+impl<I> Iterator for RecordedEventsByTopic<I>
+where
+    I: Iterator<Item = RecordedEvent>,
+{
+    type Item = RecordedEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.by_ref().find(|event| event.has_topic(&self.topic))
+    }
+}
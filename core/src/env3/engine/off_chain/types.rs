@@ -56,3 +56,96 @@ pub type OffMoment = TypedEncoded<type_marker::Moment>;
 pub type OffBlockNumber = TypedEncoded<type_marker::BlockNumber>;
 /// Off-chain environment call (runtime dispatch) type.
 pub type OffCall = TypedEncoded<type_marker::Call>;
+
+/// A 160-bit EVM-compatible address.
+///
+/// # Note
+///
+/// Suitable as the `AccountId` of a custom `EnvTypes` implementation for
+/// contracts that bridge to EVM-style chains. [`OffAccountId`] stores such
+/// values SCALE-encoded behind its `AccountId` type marker, so no change to
+/// [`OffAccountId`] itself is required to exercise 20-byte addresses -
+/// this type merely gives tests a concrete, checked Rust type to encode.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct H160([u8; 20]);
+
+/// A 256-bit EVM-compatible hash.
+///
+/// # Note
+///
+/// Suitable as the `Hash` of a custom `EnvTypes` implementation. See the
+/// [`H160`] documentation for how this relates to [`OffHash`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct H256([u8; 32]);
+
+/// A 256-bit EVM-compatible unsigned integer.
+///
+/// # Note
+///
+/// Suitable as the `Balance` of a custom `EnvTypes` implementation. See the
+/// [`H160`] documentation for how this relates to [`OffBalance`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct U256([u8; 32]);
+
+macro_rules! impl_evm_bytes_type {
+    ( $name:ident, $len:literal ) => {
+        impl $name {
+            /// Creates a new value from its big-endian byte representation.
+            pub fn from_bytes(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+
+            /// Returns the big-endian byte representation of `self`.
+            pub fn to_bytes(self) -> [u8; $len] {
+                self.0
+            }
+        }
+
+        impl From<[u8; $len]> for $name {
+            fn from(bytes: [u8; $len]) -> Self {
+                Self::from_bytes(bytes)
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0[..]
+            }
+        }
+
+        #[cfg(feature = "std")]
+        const _: () = {
+            use crate::storage2::traits::{
+                KeyPtr,
+                StorageLayout,
+            };
+            use ink_metadata::layout2::{
+                CellLayout,
+                Layout,
+                LayoutKey,
+            };
+
+            impl StorageLayout for $name {
+                fn layout(key_ptr: &mut KeyPtr) -> Layout {
+                    // A `$name` is a single packed storage cell even though
+                    // it spans `$len` bytes, mirroring how a `u128` is one
+                    // cell despite being wider than a byte: the `KeyPtr` only
+                    // ever advances by one cell per field, while the
+                    // generated `CellLayout` reports the type's true byte
+                    // width to off-chain tooling and the `TypedEncoded`
+                    // machinery.
+                    Layout::Cell(CellLayout::new::<$name>(LayoutKey::from(
+                        key_ptr.advance_by(1),
+                    )))
+                }
+            }
+        };
+    };
+}
+
+impl_evm_bytes_type!(H160, 20);
+impl_evm_bytes_type!(H256, 32);
+impl_evm_bytes_type!(U256, 32);
@@ -29,16 +29,24 @@ use crate::storage::{
 /// Allocator for dynamic contract storage.
 ///
 /// Uses storage effective bit vectors for free list representation.
-/// Searches for free cells and chunks via first-fit approach which
-/// can be slow (more than 2 reads) for more than 3000 dynamic allocations
-/// at the same time. This is subject to change in the future if
-/// experiments show that this is a bottle neck.
+/// Freed cell and chunk positions are additionally pushed onto a LIFO
+/// free-list stack, so `alloc_cell`/`alloc_chunk` only ever pop the stack
+/// head (one read) instead of rescanning the bitmap for the first set bit.
+/// The bitmaps remain the source of truth for the `debug_assert!` range
+/// and double-free checks and for [`Flush`], but are no longer on the hot
+/// allocation path.
 #[derive(Debug)]
 pub struct DynAlloc {
     /// Bitmap indicating free cell slots.
     free_cells: storage::BitVec,
     /// Bitmap indicating free chunk slots.
     free_chunks: storage::BitVec,
+    /// LIFO stack of freed cell positions, ready to be popped by the next
+    /// `alloc_cell` instead of rescanning `free_cells`.
+    free_cell_stack: storage::Stack<u32>,
+    /// LIFO stack of freed chunk positions, ready to be popped by the next
+    /// `alloc_chunk` instead of rescanning `free_chunks`.
+    free_chunk_stack: storage::Stack<u32>,
     /// Offset origin key for all cells.
     cells_origin: Key,
     /// Offset origin key for all chunks.
@@ -53,6 +61,8 @@ impl AllocateUsing for DynAlloc {
         Self {
             free_cells: AllocateUsing::allocate_using(alloc),
             free_chunks: AllocateUsing::allocate_using(alloc),
+            free_cell_stack: AllocateUsing::allocate_using(alloc),
+            free_chunk_stack: AllocateUsing::allocate_using(alloc),
             cells_origin: alloc.alloc(u32::max_value().into()),
             chunks_origin: alloc.alloc(u32::max_value().into()),
         }
@@ -65,6 +75,8 @@ impl Initialize for DynAlloc {
     fn initialize(&mut self, _args: Self::Args) {
         self.free_cells.initialize(());
         self.free_chunks.initialize(());
+        self.free_cell_stack.initialize(());
+        self.free_chunk_stack.initialize(());
     }
 }
 
@@ -72,6 +84,8 @@ impl Flush for DynAlloc {
     fn flush(&mut self) {
         self.free_cells.flush();
         self.free_chunks.flush();
+        self.free_cell_stack.flush();
+        self.free_chunk_stack.flush();
     }
 }
 
@@ -89,9 +103,13 @@ impl DynAlloc {
 impl DynAlloc {
     /// Allocates another cell and returns its key.
     fn alloc_cell(&mut self) -> Key {
-        let offset = if let Some(free) = self.free_cells.first_set_position() {
-            self.free_cells.set(free, false);
-            free
+        let offset = if let Some(position) = self.free_cell_stack.pop() {
+            debug_assert!(
+                self.free_cells.get(position),
+                "a position popped off the free-cell stack must still be marked free"
+            );
+            self.free_cells.set(position, false);
+            position
         } else {
             let len = self.free_cells.len();
             self.free_cells.push(false);
@@ -102,9 +120,13 @@ impl DynAlloc {
 
     /// Allocates another chunk and returns its key.
     fn alloc_chunk(&mut self) -> Key {
-        let offset = if let Some(free) = self.free_chunks.first_set_position() {
-            self.free_chunks.set(free, false);
-            free
+        let offset = if let Some(position) = self.free_chunk_stack.pop() {
+            debug_assert!(
+                self.free_chunks.get(position),
+                "a position popped off the free-chunk stack must still be marked free"
+            );
+            self.free_chunks.set(position, false);
+            position
         } else {
             let len = self.free_chunks.len();
             self.free_chunks.push(false);
@@ -122,7 +144,12 @@ impl DynAlloc {
         debug_assert!(key >= self.cells_origin);
         debug_assert!(key < self.cells_origin + self.free_cells.len());
         let position = self.key_to_cell_position(key);
+        debug_assert!(
+            !self.free_cells.get(position),
+            "encountered double free of a cell slot"
+        );
         self.free_cells.set(position, true);
+        self.free_cell_stack.push(position);
     }
 
     /// Deallocates the chunk key.
@@ -136,7 +163,12 @@ impl DynAlloc {
             key < self.chunks_origin + ((1 << 32) * u64::from(self.free_chunks.len()))
         );
         let position = self.key_to_chunk_position(key);
+        debug_assert!(
+            !self.free_chunks.get(position),
+            "encountered double free of a chunk slot"
+        );
         self.free_chunks.set(position, true);
+        self.free_chunk_stack.push(position);
     }
 
     /// Converts a key previously allocated as cell key
@@ -0,0 +1,207 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+// This file is part of ink!.
+//
+// ink! is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// ink! is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with ink!.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::storage::{
+    alloc::{
+        Allocate,
+        AllocateUsing,
+    },
+    cell::TypedCell,
+    Flush,
+};
+
+/// The in-memory state of a [`CachedCell`]'s write-back cache.
+#[derive(Debug, PartialEq, Eq)]
+enum Cache<T> {
+    /// Nothing has been loaded or stored into the cache yet.
+    ///
+    /// The next `get` has to hit the underlying [`TypedCell`].
+    Unknown,
+    /// The cache was mutated via `set`/`clear` and no longer agrees with the
+    /// underlying [`TypedCell`]; the held value must be written back on the
+    /// next [`Flush::flush`].
+    Desync(Option<T>),
+    /// The cache agrees with the underlying [`TypedCell`]; no write is due.
+    Sync(Option<T>),
+}
+
+impl<T> Default for Cache<T> {
+    fn default() -> Self {
+        Cache::Unknown
+    }
+}
+
+/// A write-back caching wrapper around a [`TypedCell`].
+///
+/// Provides interpreted, cached access to the associated contract storage
+/// slot.
+///
+/// # Note
+///
+/// Repeated `get` calls after the first are served straight out of the
+/// in-memory cache instead of hitting contract storage again, and `set`
+/// only marks the cache dirty; the dirty value is written back to the
+/// underlying [`TypedCell`] once, on [`Flush::flush`]. This realizes the
+/// caching role that the `Flush` trait's documentation already ascribes to
+/// pDSL's single-value caching cell.
+///
+/// # Guarantees
+///
+/// - `Owned`
+/// - `Typed`
+///
+/// Read more about kinds of guarantees and their effect [here](../index.html#guarantees).
+#[derive(Debug)]
+pub struct CachedCell<T> {
+    /// The underlying typed cell.
+    cell: TypedCell<T>,
+    /// The in-memory write-back cache.
+    cache: Cache<T>,
+}
+
+impl<T> AllocateUsing for CachedCell<T> {
+    unsafe fn allocate_using<A>(alloc: &mut A) -> Self
+    where
+        A: Allocate,
+    {
+        Self {
+            cell: AllocateUsing::allocate_using(alloc),
+            cache: Cache::Unknown,
+        }
+    }
+}
+
+impl<T> CachedCell<T> {
+    /// Mutates the cached value without immediately writing it through to
+    /// contract storage.
+    ///
+    /// The write is deferred until the next [`Flush::flush`].
+    pub fn set(&mut self, val: T) {
+        self.cache = Cache::Desync(Some(val));
+    }
+
+    /// Removes the cached value without immediately writing the removal
+    /// through to contract storage.
+    ///
+    /// The clear is deferred until the next [`Flush::flush`].
+    pub fn clear(&mut self) {
+        self.cache = Cache::Desync(None);
+    }
+}
+
+impl<T> CachedCell<T>
+where
+    T: parity_scale_codec::Decode + Clone,
+{
+    /// Returns the cached value, loading it from contract storage only if
+    /// the cache has not yet been synchronized.
+    pub fn get(&mut self) -> Option<T> {
+        match &self.cache {
+            Cache::Unknown => {
+                let loaded = self.cell.load();
+                self.cache = Cache::Sync(loaded.clone());
+                loaded
+            }
+            Cache::Desync(value) | Cache::Sync(value) => value.clone(),
+        }
+    }
+}
+
+impl<T> Flush for CachedCell<T>
+where
+    T: parity_scale_codec::Encode,
+{
+    fn flush(&mut self) {
+        match core::mem::replace(&mut self.cache, Cache::Unknown) {
+            Cache::Desync(value) => {
+                match &value {
+                    Some(val) => self.cell.store(val),
+                    None => self.cell.clear(),
+                }
+                self.cache = Cache::Sync(value);
+            }
+            other => self.cache = other,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test-env"))]
+mod tests {
+    use super::*;
+    use crate::{
+        env,
+        storage::Key,
+    };
+
+    use crate::{
+        storage::alloc::{
+            AllocateUsing,
+            BumpAlloc,
+        },
+        test_utils::run_test,
+    };
+
+    fn dummy_cell() -> CachedCell<i32> {
+        unsafe {
+            let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+            CachedCell::allocate_using(&mut alloc)
+        }
+    }
+
+    #[test]
+    fn simple() {
+        run_test(|| {
+            let mut cell = dummy_cell();
+            assert_eq!(cell.get(), None);
+            cell.set(5);
+            assert_eq!(cell.get(), Some(5));
+            cell.clear();
+            assert_eq!(cell.get(), None);
+        })
+    }
+
+    #[test]
+    fn count_reads() {
+        run_test(|| {
+            let mut cell = dummy_cell();
+            assert_eq!(env::test::total_reads(), 0);
+            cell.get();
+            assert_eq!(env::test::total_reads(), 1);
+            cell.get();
+            cell.get();
+            // Repeated `get`s after the first are served from the cache.
+            assert_eq!(env::test::total_reads(), 1);
+        })
+    }
+
+    #[test]
+    fn count_writes() {
+        run_test(|| {
+            let mut cell = dummy_cell();
+            assert_eq!(env::test::total_writes(), 0);
+            cell.set(1);
+            cell.set(2);
+            cell.set(3);
+            // Mutations are only cached, not yet written through.
+            assert_eq!(env::test::total_writes(), 0);
+            cell.flush();
+            assert_eq!(env::test::total_writes(), 1);
+            // A `flush` with nothing dirty writes nothing.
+            cell.flush();
+            assert_eq!(env::test::total_writes(), 1);
+        })
+    }
+}
@@ -149,6 +149,18 @@ mod tests {
         })
     }
 
+    #[test]
+    fn store_and_load_u128_max_class_balance() {
+        run_test(|| {
+            let mut alloc = unsafe { BumpAlloc::from_raw_parts(Key([0x0; 32])) };
+            let mut cell: TypedCell<u128> = unsafe { TypedCell::allocate_using(&mut alloc) };
+            let balance = u128::max_value() - 1;
+            assert_eq!(cell.load(), None);
+            cell.store(&balance);
+            assert_eq!(cell.load(), Some(balance));
+        })
+    }
+
     #[test]
     fn count_writes() {
         run_test(|| {
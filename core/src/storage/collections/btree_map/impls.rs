@@ -42,6 +42,10 @@ use crate::storage::{
 use core::{
     borrow::Borrow,
     cmp::Ord,
+    ops::{
+        Bound,
+        RangeBounds,
+    },
     ptr,
 };
 #[cfg(feature = "ink-generate-abi")]
@@ -51,6 +55,7 @@ use ink_abi::{
     LayoutStruct,
     StorageLayout,
 };
+use ink_prelude::vec::Vec;
 use scale::{
     Codec,
     Decode,
@@ -81,6 +86,22 @@ pub(super) enum HandleType {
     Internal,
 }
 
+/// An error that can occur while performing a fallible, non-panicking
+/// operation on a [`BTreeMap`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum CollectionError {
+    /// The operation would have allocated a new node, but the tree
+    /// already holds as many nodes as allowed by its configured
+    /// [`BTreeMap::max_nodes`] cap.
+    MaxNodesExceeded,
+    /// The underlying `SyncChunk` failed to store or retrieve an entry.
+    ///
+    /// None of the `SyncChunk` operations used by this map are currently
+    /// fallible, so this variant is reserved for forwarding such failures
+    /// once the underlying storage chunk exposes them.
+    ChunkAccessFailed,
+}
+
 /// Mapping stored in the contract storage.
 ///
 /// This implementation follows the algorithm used by the Rust
@@ -140,6 +161,23 @@ impl<K, V> BTreeMap<K, V> {
     pub(super) fn root(&self) -> Option<u32> {
         self.header.root
     }
+
+    /// Returns the cap on the number of nodes the tree may allocate, if any.
+    pub fn max_nodes(&self) -> Option<u32> {
+        self.header.max_nodes
+    }
+
+    /// Sets a cap on the number of nodes the tree may allocate.
+    ///
+    /// # Note
+    ///
+    /// Once the tree holds this many nodes, [`BTreeMap::try_insert`] refuses
+    /// to allocate a new one instead of growing the tree further. Pass
+    /// `None` to lift any previously configured cap. This has no effect on
+    /// reusing already allocated, now-vacant nodes.
+    pub fn set_max_nodes(&mut self, max_nodes: Option<u32>) {
+        self.header.max_nodes = max_nodes;
+    }
 }
 
 impl<K, V> BTreeMap<K, V>
@@ -196,6 +234,68 @@ where
         })
     }
 
+    /// Conservatively estimates whether inserting `key` would allocate more
+    /// new nodes than still fit under `max_nodes`.
+    ///
+    /// # Note
+    ///
+    /// If `key` is already present no allocation is needed at all. Otherwise
+    /// this counts one new node for every already-full node between the
+    /// insertion point and the root, which mirrors how many nodes
+    /// `insert_into_node`'s cascading split would allocate, plus one more if
+    /// the split reaches the root (which always pushes a new root level).
+    fn would_exceed_max_nodes<Q>(&self, key: &Q, max_nodes: u32) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        if self.root().is_none() {
+            // an empty tree allocates one new node for its root
+            return self.header.node_count >= max_nodes
+        }
+        let handle = match search::search_tree(self, key) {
+            Found(_) => return false,
+            NotFound(handle) => handle,
+        };
+        let available = max_nodes.saturating_sub(self.header.node_count);
+        let mut needed = 0u32;
+        let mut node = NodeHandle::from(handle);
+        loop {
+            let len = self.get_node(&node).expect("node must exist").len();
+            if len < CAPACITY {
+                break
+            }
+            needed += 1;
+            match self.ascend(node) {
+                Some(parent_kv) => node = NodeHandle::from(parent_kv),
+                None => {
+                    // splitting the root also pushes a new root level
+                    needed += 1;
+                    break
+                }
+            }
+        }
+        needed > available
+    }
+
+    /// Checks whether the tree still has headroom to allocate at least one
+    /// more node under its configured [`BTreeMap::max_nodes`] cap, without
+    /// allocating anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(CollectionError::MaxNodesExceeded)` if a `max_nodes` cap
+    /// is configured and the tree has already reached it. Returns `Ok(())`
+    /// if no cap is configured or the tree still has headroom.
+    pub fn try_reserve_node(&self) -> Result<(), CollectionError> {
+        match self.header.max_nodes {
+            Some(max_nodes) if self.header.node_count >= max_nodes => {
+                Err(CollectionError::MaxNodesExceeded)
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Creates a root node with `key` and `val`.
     ///
     /// Returns a reference to the inserted value.
@@ -326,6 +426,201 @@ where
         }
     }
 
+    /// Traverses downwards from `handle`, always taking the last edge down.
+    /// Once a leaf is reached a handle to the last key/value pair in the
+    /// leaf is returned.
+    fn last_leaf_edge(&self, mut handle: NodeHandle) -> KVHandle {
+        loop {
+            match self.get_handle_type(&handle) {
+                Leaf => return self.last_kv(&handle),
+                Internal => {
+                    let last_edge = self.last_edge(&handle);
+                    handle = self
+                        .descend(last_edge)
+                        .expect("every internal node has children; qed");
+                }
+            }
+        }
+    }
+
+    /// Returns a handle to the last edge in the node.
+    fn last_edge(&self, handle: &NodeHandle) -> KVHandle {
+        let len = self.get_node(handle).expect("node must exist").len();
+        KVHandle::new(handle.node(), len as u32)
+    }
+
+    /// Returns a handle to the last key/value pair in the node.
+    fn last_kv(&self, handle: &NodeHandle) -> KVHandle {
+        let len = self.get_node(handle).expect("node must exist").len();
+        KVHandle::new(handle.node(), len as u32 - 1)
+    }
+
+    /// Returns a handle to the key/value pair in in-order position right
+    /// after `handle`, or `None` if `handle` already points to the last
+    /// key/value pair in the tree.
+    ///
+    /// Mirrors the successor walk of the Rust stdlib `BTreeMap`: within a
+    /// leaf this simply moves one slot to the right; once a leaf is
+    /// exhausted the walk ascends until it finds a parent edge that still
+    /// has a key/value pair to its right.
+    fn next_kv(&self, handle: KVHandle) -> Option<KVHandle> {
+        if let Internal = self.get_handle_type(&handle.into()) {
+            let child = self
+                .descend(self.right_edge(handle))
+                .expect("internal node must have a right child; qed");
+            return Some(self.first_leaf_edge(child))
+        }
+        if let Some(kv) = self.right_kv(self.right_edge(handle)) {
+            return Some(kv)
+        }
+        let mut cur = NodeHandle::from(handle);
+        loop {
+            let parent_kv = self.ascend(cur)?;
+            if self.right_kv(parent_kv).is_some() {
+                return Some(parent_kv)
+            }
+            cur = NodeHandle::from(parent_kv);
+        }
+    }
+
+    /// Returns a handle to the key/value pair in in-order position right
+    /// before `handle`, or `None` if `handle` already points to the first
+    /// key/value pair in the tree.
+    ///
+    /// The mirror image of [`next_kv`](Self::next_kv): within a leaf this
+    /// moves one slot to the left via `left_kv`; an exhausted leaf ascends
+    /// until a parent edge with a key/value pair to its left is found.
+    fn prev_kv(&self, handle: KVHandle) -> Option<KVHandle> {
+        if let Internal = self.get_handle_type(&handle.into()) {
+            let child = self
+                .descend(self.left_edge(handle))
+                .expect("internal node must have a left child; qed");
+            return Some(self.last_leaf_edge(child))
+        }
+        if let Some(kv) = self.left_kv(handle) {
+            return Some(kv)
+        }
+        let mut cur = NodeHandle::from(handle);
+        loop {
+            let parent_edge = self.ascend(cur)?;
+            if let Some(kv) = self.left_kv(parent_edge) {
+                return Some(kv)
+            }
+            cur = NodeHandle::from(parent_edge);
+        }
+    }
+
+    /// Resolves an edge at the end of a downward search into a real
+    /// key/value handle by ascending until one is found, used when a
+    /// forward bound search bottoms out one-past-the-end of a leaf.
+    fn normalize_forward(&self, node: NodeHandle, idx: u32) -> Option<KVHandle> {
+        let len = self.get_node(&node).expect("node must exist").len() as u32;
+        if idx < len {
+            return Some(KVHandle::new(node.node(), idx))
+        }
+        let mut cur = node;
+        loop {
+            let parent_kv = self.ascend(cur)?;
+            if self.right_kv(parent_kv).is_some() {
+                return Some(parent_kv)
+            }
+            cur = NodeHandle::from(parent_kv);
+        }
+    }
+
+    /// The mirror image of [`normalize_forward`](Self::normalize_forward),
+    /// used when a backward bound search bottoms out at the very start of
+    /// a leaf.
+    fn normalize_backward(&self, node: NodeHandle, idx: u32) -> Option<KVHandle> {
+        if idx > 0 {
+            return Some(KVHandle::new(node.node(), idx - 1))
+        }
+        let mut cur = node;
+        loop {
+            let parent_edge = self.ascend(cur)?;
+            if let Some(kv) = self.left_kv(parent_edge) {
+                return Some(kv)
+            }
+            cur = NodeHandle::from(parent_edge);
+        }
+    }
+
+    /// Finds the handle to the first key/value pair that is not excluded
+    /// by the lower `bound` of a range, descending from the root.
+    fn lower_bound<Q>(&self, bound: Bound<&Q>) -> Option<KVHandle>
+    where
+        Q: Ord,
+        K: Borrow<Q>,
+    {
+        let mut handle = NodeHandle::new(self.root()?);
+        loop {
+            let node = self.get_node(&handle).expect("node must exist");
+            let mut idx = 0u32;
+            while (idx as usize) < node.len() {
+                let key = node.keys[idx as usize]
+                    .as_ref()
+                    .expect("key must exist")
+                    .borrow();
+                let before = match bound {
+                    Bound::Unbounded => false,
+                    Bound::Included(start) => key < start,
+                    Bound::Excluded(start) => key <= start,
+                };
+                if !before {
+                    break
+                }
+                idx += 1;
+            }
+            match self.get_handle_type(&handle) {
+                Leaf => return self.normalize_forward(handle, idx),
+                Internal => {
+                    let edge = KVHandle::new(handle.node(), idx);
+                    handle = self
+                        .descend(edge)
+                        .expect("internal node must have this child; qed");
+                }
+            }
+        }
+    }
+
+    /// Finds the handle to the last key/value pair that is not excluded by
+    /// the upper `bound` of a range, descending from the root.
+    fn upper_bound<Q>(&self, bound: Bound<&Q>) -> Option<KVHandle>
+    where
+        Q: Ord,
+        K: Borrow<Q>,
+    {
+        let mut handle = NodeHandle::new(self.root()?);
+        loop {
+            let node = self.get_node(&handle).expect("node must exist");
+            let mut idx = 0u32;
+            while (idx as usize) < node.len() {
+                let key = node.keys[idx as usize]
+                    .as_ref()
+                    .expect("key must exist")
+                    .borrow();
+                let before = match bound {
+                    Bound::Unbounded => true,
+                    Bound::Included(end) => key <= end,
+                    Bound::Excluded(end) => key < end,
+                };
+                if !before {
+                    break
+                }
+                idx += 1;
+            }
+            match self.get_handle_type(&handle) {
+                Leaf => return self.normalize_backward(handle, idx),
+                Internal => {
+                    let edge = KVHandle::new(handle.node(), idx);
+                    handle = self
+                        .descend(edge)
+                        .expect("internal node must have this child; qed");
+                }
+            }
+        }
+    }
+
     /// Removes the key/value pair pointed to by `handle`.
     ///
     /// If through this removal an underfull node was created, appropriate strategies
@@ -725,7 +1020,15 @@ where
     fn put(&mut self, node: Node<K, V>) -> u32 {
         let node_index = match self.header.next_vacant {
             None => {
-                // then there is no vacant entry which we can reuse
+                // then there is no vacant entry which we can reuse, so a
+                // brand new node is about to be allocated
+                if let Some(max_nodes) = self.header.max_nodes {
+                    assert!(
+                        self.header.node_count < max_nodes,
+                        "[ink_core::BTreeMap::put] Error: \
+                         node count would exceed the configured max_nodes limit"
+                    );
+                }
                 self.entries
                     .set(self.header.node_count, InternalEntry::Occupied(node));
                 self.header.node_count
@@ -1084,6 +1387,196 @@ where
         let node = self.get_node(handle).expect("node must exist");
         node.edges() > 0
     }
+
+    /// Builds the leaf level of a tree from a sequence of already-sorted
+    /// key/value pairs, filling each leaf to `CAPACITY` and promoting one
+    /// key/value pair to a pending separator between each pair of leaves.
+    ///
+    /// Returns the freshly allocated leaves alongside the separators that
+    /// still need to be promoted into the level above. The invariant
+    /// `children.len() == separators.len() + 1` always holds.
+    fn build_leaf_level(&mut self, entries: Vec<(K, V)>) -> (Vec<u32>, Vec<(K, V)>) {
+        let mut children = Vec::new();
+        let mut separators = Vec::new();
+        let mut entries = entries.into_iter();
+        let mut remaining = entries.len();
+        while remaining > 0 {
+            let take = if remaining < CAPACITY {
+                remaining
+            } else {
+                CAPACITY
+            };
+            let mut node = Node::<K, V>::new();
+            for i in 0..take {
+                let (k, v) = entries.next().expect("enough entries must remain; qed");
+                node.keys[i] = Some(k);
+                node.vals[i] = Some(v);
+            }
+            node.len = take as u32;
+            remaining -= take;
+            children.push(self.put(node));
+            if remaining > 0 {
+                separators.push(entries.next().expect("a separator entry must remain; qed"));
+                remaining -= 1;
+            }
+        }
+        (children, separators)
+    }
+
+    /// Builds one internal level on top of `children`/`separators`, grouping
+    /// up to `CAPACITY + 1` children (and the `CAPACITY` separators between
+    /// them) under each new parent node, and promoting one separator
+    /// between each pair of parents, mirroring `build_leaf_level`.
+    fn build_internal_level(
+        &mut self,
+        children: Vec<u32>,
+        separators: Vec<(K, V)>,
+    ) -> (Vec<u32>, Vec<(K, V)>) {
+        debug_assert_eq!(children.len(), separators.len() + 1);
+        let mut new_children = Vec::new();
+        let mut new_separators = Vec::new();
+        let mut separators = separators.into_iter();
+        let mut ci = 0usize;
+        while ci < children.len() {
+            let mut node = Node::<K, V>::new();
+            node.edges[0] = Some(children[ci]);
+            ci += 1;
+            let mut count = 0usize;
+            while count < CAPACITY && ci < children.len() {
+                let (k, v) = separators
+                    .next()
+                    .expect("a separator must exist for every remaining child; qed");
+                node.keys[count] = Some(k);
+                node.vals[count] = Some(v);
+                node.edges[count + 1] = Some(children[ci]);
+                ci += 1;
+                count += 1;
+            }
+            node.len = count as u32;
+            let idx = self.put(node);
+            self.correct_all_childrens_parent_links(&NodeHandle::new(idx));
+            new_children.push(idx);
+            if ci < children.len() {
+                new_separators.push(
+                    separators
+                        .next()
+                        .expect("a separator must exist before the next parent group; qed"),
+                );
+            }
+        }
+        (new_children, new_separators)
+    }
+
+    /// Restores the minimum fill invariant on the last node of the level
+    /// that was just given `parent_idx` as its parent, by repeatedly
+    /// stealing entries back from its previous sibling, and then does the
+    /// same for the last child of that node, down to the leaves.
+    ///
+    /// # Note
+    ///
+    /// The bottom-up construction in `BTreeMap::from_sorted` simply takes
+    /// however many entries are left over for the last node of a level,
+    /// which can leave it underfull; this restores the invariant the same
+    /// way removal does, by stealing through the parent.
+    fn fixup_last_child_underfill(&mut self, parent_idx: u32) {
+        let parent_len = self
+            .get_node(&NodeHandle::new(parent_idx))
+            .expect("parent node must exist")
+            .len();
+        if parent_len == 0 {
+            // The parent has only a single child; there is no sibling to
+            // steal from through it.
+            return
+        }
+        let handle = KVHandle::new(parent_idx, parent_len as u32 - 1);
+        loop {
+            let right_len = self
+                .right_child_node(handle)
+                .expect("right child must exist")
+                .len();
+            if right_len >= CAPACITY / 2 {
+                break
+            }
+            let left_len = self
+                .left_child_node(handle)
+                .expect("left child must exist")
+                .len();
+            if left_len <= CAPACITY / 2 {
+                // The left sibling has nothing to spare without becoming
+                // underfull itself.
+                break
+            }
+            self.steal_left(handle);
+        }
+        // A lone child left alone one level below can only be refilled now
+        // that its parent has gained keys of its own.
+        let right = self.right_child(handle).expect("right child must exist");
+        if self.has_children(&right) {
+            self.fixup_last_child_underfill(right.node);
+        }
+    }
+
+    /// Builds a new map from an iterator of already key-sorted, duplicate-free
+    /// entries in a single linear pass, avoiding the repeated splitting and
+    /// re-walking to the root that one [`BTreeMap::insert`] call per entry
+    /// would incur.
+    ///
+    /// # Note
+    ///
+    /// The caller is responsible for `iter` yielding entries in strictly
+    /// ascending key order; this is not verified.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let map = BTreeMap::from_sorted((0..1000).map(|i| (i, i * 2)), &mut alloc);
+    /// assert_eq!(map.len(), 1000);
+    /// ```
+    pub fn from_sorted<A, I>(iter: I, alloc: &mut A) -> Self
+    where
+        A: Allocate,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut map = unsafe { Self::allocate_using(alloc) };
+        map.initialize(());
+
+        let entries: Vec<(K, V)> = iter.into_iter().collect();
+        let total = entries.len();
+        if entries.is_empty() {
+            return map
+        }
+
+        let (mut children, mut separators) = map.build_leaf_level(entries);
+        while children.len() > 1 {
+            let (next_children, next_separators) =
+                map.build_internal_level(children, separators);
+            map.fixup_last_child_underfill(
+                *next_children
+                    .last()
+                    .expect("at least one parent was just built; qed"),
+            );
+            children = next_children;
+            separators = next_separators;
+        }
+
+        map.header.root = Some(children[0]);
+        map.header.len = total as u32;
+        map
+    }
+
+    /// Alias for [`BTreeMap::from_sorted`] under the name used by the
+    /// standard library's own bulk-loading constructors.
+    pub fn from_sorted_iter<A, I>(iter: I, alloc: &mut A) -> Self
+    where
+        A: Allocate,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        Self::from_sorted(iter, alloc)
+    }
 }
 
 /// Densely stored general information required by a map.
@@ -1111,6 +1604,12 @@ pub(super) struct BTreeMapHeader {
     /// Number of nodes the tree contains. This is not the number
     /// of elements!
     pub(super) node_count: u32,
+    /// An optional cap on `node_count`.
+    ///
+    /// If set, `put` refuses to allocate a brand new node once the tree
+    /// already holds this many nodes, allowing contracts to keep the
+    /// map's storage and gas footprint within a deterministic budget.
+    max_nodes: Option<u32>,
 }
 
 impl Flush for BTreeMapHeader {
@@ -1120,6 +1619,7 @@ impl Flush for BTreeMapHeader {
         self.root.flush();
         self.len.flush();
         self.node_count.flush();
+        self.max_nodes.flush();
     }
 }
 
@@ -1230,7 +1730,7 @@ impl From<u32> for NodeHandle {
 }
 
 /// Points to a specific key/value pair within a node in the tree.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub(super) struct KVHandle {
     /// Index of the node in entries.
     pub node: u32,
@@ -1264,6 +1764,7 @@ impl<K, V> Initialize for BTreeMap<K, V> {
             len: 0,
             node_count: 0,
             root: None,
+            max_nodes: None,
         });
     }
 }
@@ -1375,6 +1876,24 @@ where
         self.get(key).is_some()
     }
 
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// Semantically identical to `contains_key`, offered under the shorter
+    /// name used by `Mapping::contains` in later versions of ink!, which
+    /// split membership checks from value retrieval for their lazy storage
+    /// mapping. Prefer this over `get(key).is_some()` at call sites that
+    /// only care about presence.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.contains_key(key)
+    }
+
     /// Inserts a key/value pair into the map.
     ///
     /// If the map did not have this key present, `None` is returned.
@@ -1411,6 +1930,34 @@ where
         }
     }
 
+    /// Like [`BTreeMap::insert`], but refuses to allocate new nodes beyond
+    /// the configured [`BTreeMap::max_nodes`] cap instead of panicking.
+    ///
+    /// If no cap is configured this behaves exactly like `insert`. If the
+    /// insertion would require allocating more nodes than still fit under
+    /// the cap, the map is left completely unmodified and
+    /// `Err(CollectionError::MaxNodesExceeded)` is returned.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map = new_btree_map();
+    /// map.set_max_nodes(Some(0));
+    /// assert_eq!(map.try_insert(1, "a"), Err(CollectionError::MaxNodesExceeded));
+    /// ```
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, CollectionError> {
+        if let Some(max_nodes) = self.header.max_nodes {
+            if self.would_exceed_max_nodes(&key, max_nodes) {
+                return Err(CollectionError::MaxNodesExceeded)
+            }
+        }
+        Ok(self.insert(key, value))
+    }
+
     /// Removes a key from the map, returning the value at the key if the key
     /// was previously in the map.
     ///
@@ -1443,6 +1990,25 @@ where
         }
     }
 
+    /// Like [`BTreeMap::remove`], but returns a `Result` for symmetry with
+    /// [`BTreeMap::try_insert`].
+    ///
+    /// # Note
+    ///
+    /// Removing a key never allocates a new node (underfull nodes are
+    /// merged or stolen from, which can only ever free nodes up), so this
+    /// can currently never fail with `CollectionError::MaxNodesExceeded`.
+    /// The `Result` return type leaves room for surfacing a future
+    /// `CollectionError::ChunkAccessFailed` from the underlying storage
+    /// chunk.
+    pub fn try_remove<Q>(&mut self, key: &Q) -> Result<Option<V>, CollectionError>
+    where
+        Q: Ord,
+        K: Borrow<Q>,
+    {
+        Ok(self.remove(key))
+    }
+
     /// Gets the given key's corresponding entry in the map for in-place manipulation.
     ///
     /// # Examples
@@ -1473,54 +2039,770 @@ where
             }
         }
     }
-}
 
-impl<'a, K, V> Entry<'a, K, V>
-where
-    K: Ord + Codec,
-    V: Codec,
-{
-    /// Ensures a value is in the entry by inserting the default if empty, and returns
-    /// a mutable reference to the value in the entry.
+    /// Gets an iterator over the entries of the map, sorted by key.
     ///
     /// # Examples
     ///
+    /// Basic usage:
+    ///
     /// ```no_compile
     /// use ink_core::storage::BTreeMap;
     ///
-    /// let mut map: BTreeMap<&str, usize> = new_btree_map();
-    /// map.entry("poneyland").or_insert(12);
+    /// let mut map = new_btree_map();
+    /// map.insert(3, "c");
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
     ///
-    /// assert_eq!(map["poneyland"], 12);
+    /// for (key, value) in map.iter() {
+    ///     println!("{}: {}", key, value);
+    /// }
     /// ```
-    pub fn or_insert(self, default: V) -> &'a mut V {
-        match self {
-            Entry::Occupied(entry) => entry.into_mut(),
-            Entry::Vacant(entry) => entry.insert(default),
+    pub fn iter(&self) -> Iter<K, V> {
+        match self.root() {
+            Some(root) => {
+                let front = self.first_leaf_edge(NodeHandle::new(root));
+                let back = self.last_leaf_edge(NodeHandle::new(root));
+                Iter {
+                    tree: self,
+                    front: Some(front),
+                    back: Some(back),
+                }
+            }
+            None => {
+                Iter {
+                    tree: self,
+                    front: None,
+                    back: None,
+                }
+            }
         }
     }
 
-    /// Returns a reference to this entry's key.
+    /// Gets an iterator over a sub-range of entries of the map, sorted by key.
+    ///
+    /// The simplest way is to use the range syntax `min..max`, thus `range(min..max)`
+    /// will yield elements from `min` (inclusive) to `max` (exclusive). The range may
+    /// also be bounded on either or both ends using `..`, `..=` or be fully unbounded.
+    ///
+    /// # Note
+    ///
+    /// The returned iterator navigates the tree lazily, loading one node from
+    /// storage at a time as it advances, rather than eagerly collecting the
+    /// whole sub-range up front.
     ///
     /// # Examples
     ///
+    /// Basic usage:
+    ///
     /// ```no_compile
     /// use ink_core::storage::BTreeMap;
     ///
-    /// let mut map: BTreeMap<&str, usize> = new_btree_map();
-    /// assert_eq!(map.entry("poneyland").key(), &"poneyland");
+    /// let mut map = new_btree_map();
+    /// map.insert(3, "c");
+    /// map.insert(5, "e");
+    /// map.insert(8, "h");
+    ///
+    /// for (key, value) in map.range(4..) {
+    ///     println!("{}: {}", key, value);
+    /// }
     /// ```
-    pub fn key(&self) -> &K {
-        match *self {
-            Entry::Occupied(ref entry) => entry.key(),
-            Entry::Vacant(ref entry) => entry.key(),
+    pub fn range<Q, R>(&self, range: R) -> Iter<K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+        R: RangeBounds<Q>,
+    {
+        let front = self.lower_bound(range.start_bound());
+        let back = self.upper_bound(range.end_bound());
+        match (front, back) {
+            (Some(front), Some(back))
+                if self
+                    .get_kv(front)
+                    .expect("front handle must point to an existing kv; qed")
+                    .0
+                    <= self
+                        .get_kv(back)
+                        .expect("back handle must point to an existing kv; qed")
+                        .0 =>
+            {
+                Iter {
+                    tree: self,
+                    front: Some(front),
+                    back: Some(back),
+                }
+            }
+            _ => {
+                Iter {
+                    tree: self,
+                    front: None,
+                    back: None,
+                }
+            }
         }
     }
-}
 
-#[cfg(feature = "ink-generate-abi")]
-impl<K, V> HasLayout for BTreeMap<K, V>
-where
+    /// Gets a mutable iterator over the entries of the map, sorted by key.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map = new_btree_map();
+    /// map.insert(1, 10);
+    ///
+    /// for (_key, value) in map.iter_mut() {
+    ///     *value += 1;
+    /// }
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<K, V> {
+        match self.root() {
+            Some(root) => {
+                let front = self.first_leaf_edge(NodeHandle::new(root));
+                let back = self.last_leaf_edge(NodeHandle::new(root));
+                IterMut {
+                    tree: self as *mut Self,
+                    front: Some(front),
+                    back: Some(back),
+                    marker: core::marker::PhantomData,
+                }
+            }
+            None => {
+                IterMut {
+                    tree: self as *mut Self,
+                    front: None,
+                    back: None,
+                    marker: core::marker::PhantomData,
+                }
+            }
+        }
+    }
+
+    /// Gets a mutable iterator over a sub-range of entries of the map, sorted
+    /// by key. See [`BTreeMap::range`] for the accepted range syntax.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map = new_btree_map();
+    /// map.insert(3, 30);
+    /// map.insert(5, 50);
+    /// map.insert(8, 80);
+    ///
+    /// for (_key, value) in map.range_mut(4..) {
+    ///     *value += 1;
+    /// }
+    /// ```
+    pub fn range_mut<Q, R>(&mut self, range: R) -> IterMut<K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+        R: RangeBounds<Q>,
+    {
+        let front = self.lower_bound(range.start_bound());
+        let back = self.upper_bound(range.end_bound());
+        let (front, back) = match (front, back) {
+            (Some(front), Some(back))
+                if self
+                    .get_kv(front)
+                    .expect("front handle must point to an existing kv; qed")
+                    .0
+                    <= self
+                        .get_kv(back)
+                        .expect("back handle must point to an existing kv; qed")
+                        .0 =>
+            {
+                (Some(front), Some(back))
+            }
+            _ => (None, None),
+        };
+        IterMut {
+            tree: self as *mut Self,
+            front,
+            back,
+            marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Splits the map in two at `key`. Everything with a key greater than
+    /// or equal to `key` is moved into a freshly allocated map, which is
+    /// returned; `self` retains everything with a key less than `key`.
+    ///
+    /// # Note
+    ///
+    /// Unlike the standard library's `split_off`, this takes an explicit
+    /// `alloc` parameter: every storage collection in this crate is
+    /// constructed through an [`Allocate`], since there is no ambient
+    /// allocator to reach for a fresh storage region on-chain.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map = new_btree_map();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    ///
+    /// let right = map.split_off(&2, &mut alloc);
+    /// assert_eq!(map.get(&1), Some(&"a"));
+    /// assert_eq!(right.get(&2), Some(&"b"));
+    /// assert_eq!(right.get(&3), Some(&"c"));
+    /// ```
+    #[must_use]
+    pub fn split_off<A, Q>(&mut self, key: &Q, alloc: &mut A) -> Self
+    where
+        A: Allocate,
+        K: Borrow<Q> + Clone,
+        Q: Ord,
+    {
+        let mut right = unsafe { Self::allocate_using(alloc) };
+        right.initialize(());
+        while let Some(handle) = self.lower_bound(Bound::Included(key)) {
+            let (k, _) = self
+                .get_kv(handle)
+                .expect("lower_bound handle must point to an existing kv; qed");
+            let k = k.clone();
+            let v = self
+                .remove(&k)
+                .expect("key was just observed to exist in the map; qed");
+            right.insert(k, v);
+        }
+        right
+    }
+
+    /// Moves all elements out of `other` and into `self`, leaving `other`
+    /// empty.
+    ///
+    /// If a key from `other` already exists in `self`, its value is
+    /// overwritten with the one from `other`.
+    ///
+    /// # Note
+    ///
+    /// Every entry removed from `other` is recycled back onto its vacant
+    /// entry free list, the same as a plain [`BTreeMap::remove`] call, so
+    /// the now-empty `other` is ready to be reused or dropped cheaply.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut a = new_btree_map();
+    /// a.insert(1, "a");
+    /// let mut b = new_btree_map();
+    /// b.insert(2, "b");
+    ///
+    /// a.append(&mut b);
+    /// assert_eq!(a.len(), 2);
+    /// assert!(b.is_empty());
+    /// ```
+    pub fn append(&mut self, other: &mut Self)
+    where
+        K: Clone,
+    {
+        while let Some(handle) = other.lower_bound(Bound::Unbounded) {
+            let (k, _) = other
+                .get_kv(handle)
+                .expect("lower_bound handle must point to an existing kv; qed");
+            let k = k.clone();
+            let v = other
+                .remove(&k)
+                .expect("key was just observed to exist in the map; qed");
+            self.insert(k, v);
+        }
+    }
+
+    /// Gets an iterator over the keys of the map, in sorted order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map = new_btree_map();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    ///
+    /// let keys: Vec<_> = map.keys().collect();
+    /// assert_eq!(keys, vec![&1, &2]);
+    /// ```
+    pub fn keys(&self) -> Keys<K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    /// Gets an iterator over the values of the map, in order by key.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map = new_btree_map();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    ///
+    /// let values: Vec<_> = map.values().collect();
+    /// assert_eq!(values, vec![&"a", &"b"]);
+    /// ```
+    pub fn values(&self) -> Values<K, V> {
+        Values { inner: self.iter() }
+    }
+
+    /// Returns the first key/value pair in the map, sorted by key.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map = new_btree_map();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    /// assert_eq!(map.first_key_value(), Some((&1, &"a")));
+    /// ```
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        let root = self.root()?;
+        let front = self.first_leaf_edge(NodeHandle::new(root));
+        self.get_kv(front)
+    }
+
+    /// Returns the last key/value pair in the map, sorted by key.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map = new_btree_map();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    /// assert_eq!(map.last_key_value(), Some((&2, &"b")));
+    /// ```
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        let root = self.root()?;
+        let back = self.last_leaf_edge(NodeHandle::new(root));
+        self.get_kv(back)
+    }
+
+    /// Removes and returns the first key/value pair in the map, sorted by
+    /// key.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map = new_btree_map();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    /// assert_eq!(map.pop_first(), Some((1, "a")));
+    /// ```
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        let root = self.root()?;
+        let front = self.first_leaf_edge(NodeHandle::new(root));
+        Some(self.remove_kv(front))
+    }
+
+    /// Removes and returns the last key/value pair in the map, sorted by
+    /// key.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map = new_btree_map();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    /// assert_eq!(map.pop_last(), Some((2, "b")));
+    /// ```
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        let root = self.root()?;
+        let back = self.last_leaf_edge(NodeHandle::new(root));
+        Some(self.remove_kv(back))
+    }
+}
+
+/// An iterator over the entries of a [`BTreeMap`], sorted by key.
+///
+/// Created by [`BTreeMap::iter`] and [`BTreeMap::range`].
+pub struct Iter<'a, K, V> {
+    tree: &'a BTreeMap<K, V>,
+    /// The key/value pair that `next` will yield, or `None` once the
+    /// front and back of the iterator have crossed.
+    front: Option<KVHandle>,
+    /// The key/value pair that `next_back` will yield, or `None` once the
+    /// front and back of the iterator have crossed.
+    back: Option<KVHandle>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Ord + Codec,
+    V: Codec,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let front = self.front?;
+        let back = self.back?;
+        let item = self
+            .tree
+            .get_kv(front)
+            .expect("front handle must point to an existing kv; qed");
+        if front == back {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.front = self.tree.next_kv(front);
+        }
+        Some(item)
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V>
+where
+    K: Ord + Codec,
+    V: Codec,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let front = self.front?;
+        let back = self.back?;
+        let item = self
+            .tree
+            .get_kv(back)
+            .expect("back handle must point to an existing kv; qed");
+        if front == back {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.back = self.tree.prev_kv(back);
+        }
+        Some(item)
+    }
+}
+
+/// A mutable iterator over the entries of a [`BTreeMap`], sorted by key.
+///
+/// Created by [`BTreeMap::iter_mut`] and [`BTreeMap::range_mut`].
+pub struct IterMut<'a, K, V> {
+    tree: *mut BTreeMap<K, V>,
+    /// The key/value pair that `next` will yield, or `None` once the
+    /// front and back of the iterator have crossed.
+    front: Option<KVHandle>,
+    /// The key/value pair that `next_back` will yield, or `None` once the
+    /// front and back of the iterator have crossed.
+    back: Option<KVHandle>,
+    marker: core::marker::PhantomData<&'a mut BTreeMap<K, V>>,
+}
+
+impl<'a, K, V> IterMut<'a, K, V>
+where
+    K: Ord + Codec,
+    V: Codec,
+{
+    /// Resolves `handle` into its key and a mutable reference to its value.
+    ///
+    /// # Safety
+    ///
+    /// The returned references are only valid as long as no other handle
+    /// yielded by this iterator aliases the same node, which holds because
+    /// the front and back handles never point at the same key/value pair
+    /// until iteration is over.
+    fn resolve(&self, handle: KVHandle) -> (&'a K, &'a mut V) {
+        let node_ptr = unsafe { &mut *self.tree }
+            .get_node_mut(&handle.into())
+            .expect("handle must point to an existing node; qed") as *mut Node<K, V>;
+        let idx = handle.idx();
+        let k: &'a K = unsafe { (*node_ptr).keys[idx].as_ref() }.expect("key must exist; qed");
+        let v: &'a mut V =
+            unsafe { (*node_ptr).vals[idx].as_mut() }.expect("value must exist; qed");
+        (k, v)
+    }
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V>
+where
+    K: Ord + Codec,
+    V: Codec,
+{
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let front = self.front?;
+        let back = self.back?;
+        let item = self.resolve(front);
+        if front == back {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.front = unsafe { &*self.tree }.next_kv(front);
+        }
+        Some(item)
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for IterMut<'a, K, V>
+where
+    K: Ord + Codec,
+    V: Codec,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let front = self.front?;
+        let back = self.back?;
+        let item = self.resolve(back);
+        if front == back {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.back = unsafe { &*self.tree }.prev_kv(back);
+        }
+        Some(item)
+    }
+}
+
+/// An iterator over the keys of a [`BTreeMap`], sorted by key.
+///
+/// Created by [`BTreeMap::keys`].
+pub struct Keys<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V>
+where
+    K: Ord + Codec,
+    V: Codec,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, _)| k)
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Keys<'a, K, V>
+where
+    K: Ord + Codec,
+    V: Codec,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, _)| k)
+    }
+}
+
+/// An iterator over the values of a [`BTreeMap`], in order by key.
+///
+/// Created by [`BTreeMap::values`].
+pub struct Values<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V>
+where
+    K: Ord + Codec,
+    V: Codec,
+{
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, v)| v)
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Values<'a, K, V>
+where
+    K: Ord + Codec,
+    V: Codec,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, v)| v)
+    }
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: Ord + Codec,
+    V: Codec,
+{
+    /// Ensures a value is in the entry by inserting the default if empty, and returns
+    /// a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map: BTreeMap<&str, usize> = new_btree_map();
+    /// map.entry("poneyland").or_insert(12);
+    ///
+    /// assert_eq!(map["poneyland"], 12);
+    /// ```
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the
+    /// default function if empty, and returns a mutable reference to the
+    /// value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map: BTreeMap<&str, String> = new_btree_map();
+    /// let s = "hoho".to_string();
+    ///
+    /// map.entry("poneyland").or_insert_with(|| s);
+    ///
+    /// assert_eq!(map["poneyland"], "hoho".to_string());
+    /// ```
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result
+    /// of the default function, which takes the key as its argument, and
+    /// returns a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map: BTreeMap<&str, usize> = new_btree_map();
+    ///
+    /// map.entry("poneyland").or_insert_with_key(|key| key.chars().count());
+    ///
+    /// assert_eq!(map["poneyland"], 9);
+    /// ```
+    pub fn or_insert_with_key<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce(&K) -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = default(entry.key());
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map.
+    ///
+    /// # Examples
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map: BTreeMap<&str, usize> = new_btree_map();
+    ///
+    /// map.entry("poneyland")
+    ///     .and_modify(|e| *e += 1)
+    ///     .or_insert(42);
+    /// assert_eq!(map["poneyland"], 42);
+    ///
+    /// map.entry("poneyland")
+    ///     .and_modify(|e| *e += 1)
+    ///     .or_insert(42);
+    /// assert_eq!(map["poneyland"], 43);
+    /// ```
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+
+    /// Returns a reference to this entry's key.
+    ///
+    /// # Examples
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map: BTreeMap<&str, usize> = new_btree_map();
+    /// assert_eq!(map.entry("poneyland").key(), &"poneyland");
+    /// ```
+    pub fn key(&self) -> &K {
+        match *self {
+            Entry::Occupied(ref entry) => entry.key(),
+            Entry::Vacant(ref entry) => entry.key(),
+        }
+    }
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: Ord + Codec,
+    V: Codec + Default,
+{
+    /// Ensures a value is in the entry by inserting the default value if
+    /// empty, and returns a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    ///
+    /// let mut map: BTreeMap<&str, Option<usize>> = new_btree_map();
+    /// map.entry("poneyland").or_default();
+    ///
+    /// assert_eq!(map["poneyland"], None);
+    /// ```
+    pub fn or_default(self) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(Default::default()),
+        }
+    }
+}
+
+#[cfg(feature = "ink-generate-abi")]
+impl<K, V> HasLayout for BTreeMap<K, V>
+where
     K: Metadata + 'static,
     V: Metadata + 'static,
 {
@@ -1766,6 +3048,25 @@ where
         self.tree.remove_kv(self.handle).1
     }
 
+    /// Takes the key and value of the entry out of the map, and returns them.
+    ///
+    /// # Examples
+    ///
+    /// ```no_compile
+    /// use ink_core::storage::BTreeMap;
+    /// use ink_core::storage::btree_map::Entry;
+    ///
+    /// let mut map: BTreeMap<&str, usize> = new_btree_map();
+    /// map.entry("poneyland").or_insert(12);
+    ///
+    /// if let Entry::Occupied(o) = map.entry("poneyland") {
+    ///     assert_eq!(o.remove_entry(), ("poneyland", 12));
+    /// }
+    /// ```
+    pub fn remove_entry(self) -> (K, V) {
+        self.tree.remove_kv(self.handle)
+    }
+
     /// Inserts a value into this entry.
     fn insert(&mut self, value: V) -> Option<V> {
         let node = self
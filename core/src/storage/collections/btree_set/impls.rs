@@ -0,0 +1,403 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::super::btree_map::{
+    self,
+    BTreeMap,
+};
+use crate::storage::{
+    alloc::{
+        Allocate,
+        AllocateUsing,
+        Initialize,
+    },
+    Flush,
+};
+use core::{
+    borrow::Borrow,
+    cmp::{
+        Ord,
+        Ordering,
+    },
+    iter::Peekable,
+    ops::RangeBounds,
+};
+#[cfg(feature = "ink-generate-abi")]
+use ink_abi::{
+    HasLayout,
+    LayoutField,
+    LayoutStruct,
+    StorageLayout,
+};
+use scale::{
+    Codec,
+    Decode,
+    Encode,
+};
+#[cfg(feature = "ink-generate-abi")]
+use type_metadata::Metadata;
+
+/// An ordered set of unique `K` elements stored in the contract storage.
+///
+/// This reuses the whole node machinery of [`BTreeMap`] by storing each
+/// element as a key mapped to `()`, mirroring how the Rust standard
+/// library derives `BTreeSet` from `BTreeMap`.
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+pub struct BTreeSet<K> {
+    /// The elements of the set, stored as the keys of a map to unit values.
+    map: BTreeMap<K, ()>,
+}
+
+impl<K> Flush for BTreeSet<K>
+where
+    K: Encode + Flush,
+{
+    #[inline]
+    fn flush(&mut self) {
+        self.map.flush();
+    }
+}
+
+impl<K> Encode for BTreeSet<K>
+where
+    K: Codec,
+{
+    fn encode_to<W: scale::Output>(&self, dest: &mut W) {
+        self.map.encode_to(dest);
+    }
+}
+
+impl<K> Decode for BTreeSet<K>
+where
+    K: Codec,
+{
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let map = BTreeMap::decode(input)?;
+        Ok(Self { map })
+    }
+}
+
+impl<K> AllocateUsing for BTreeSet<K> {
+    #[inline]
+    unsafe fn allocate_using<A>(alloc: &mut A) -> Self
+    where
+        A: Allocate,
+    {
+        Self {
+            map: BTreeMap::allocate_using(alloc),
+        }
+    }
+}
+
+impl<K> Initialize for BTreeSet<K> {
+    type Args = ();
+
+    #[inline(always)]
+    fn default_value() -> Option<Self::Args> {
+        Some(())
+    }
+
+    #[inline]
+    fn initialize(&mut self, args: Self::Args) {
+        self.map.initialize(args)
+    }
+}
+
+#[cfg(feature = "ink-generate-abi")]
+impl<K> HasLayout for BTreeSet<K>
+where
+    K: Metadata + 'static,
+{
+    fn layout(&self) -> StorageLayout {
+        LayoutStruct::new(Self::meta_type(), vec![LayoutField::of("map", &self.map)]).into()
+    }
+}
+
+impl<K> BTreeSet<K>
+where
+    K: Ord + Codec,
+{
+    /// Returns the number of elements stored in the set.
+    pub fn len(&self) -> u32 {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns `true` if the set contains an element equal to `key`.
+    ///
+    /// The key may be any borrowed form of the set's element type, but the
+    /// ordering on the borrowed form *must* match the ordering on the
+    /// element type.
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Adds `key` to the set.
+    ///
+    /// Returns `true` if the set did not already contain an equal element.
+    pub fn insert(&mut self, key: K) -> bool {
+        self.map.insert(key, ()).is_none()
+    }
+
+    /// Removes an element equal to `key` from the set.
+    ///
+    /// Returns `true` if the set contained an equal element.
+    pub fn remove<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.map.remove(key).is_some()
+    }
+
+    /// Gets an iterator that visits the elements of the set in ascending order.
+    pub fn iter(&self) -> Iter<K> {
+        Iter {
+            inner: self.map.iter(),
+        }
+    }
+
+    /// Gets an iterator that visits the elements in `range`, in ascending order.
+    ///
+    /// The range may be any borrowed form of the set's element type, but the
+    /// ordering on the borrowed form *must* match the ordering on the
+    /// element type.
+    pub fn range<Q, R>(&self, range: R) -> Iter<K>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+        R: RangeBounds<Q>,
+    {
+        Iter {
+            inner: self.map.range(range),
+        }
+    }
+
+    /// Gets an iterator over the elements present in `self`, in `other`, or in
+    /// both, sorted in ascending order and without duplicates.
+    pub fn union<'a>(&'a self, other: &'a BTreeSet<K>) -> Union<'a, K> {
+        Union {
+            a: self.iter().peekable(),
+            b: other.iter().peekable(),
+        }
+    }
+
+    /// Gets an iterator over the elements present in both `self` and `other`,
+    /// sorted in ascending order.
+    pub fn intersection<'a>(&'a self, other: &'a BTreeSet<K>) -> Intersection<'a, K> {
+        Intersection {
+            a: self.iter().peekable(),
+            b: other.iter().peekable(),
+        }
+    }
+
+    /// Gets an iterator over the elements present in `self` but not in `other`,
+    /// sorted in ascending order.
+    pub fn difference<'a>(&'a self, other: &'a BTreeSet<K>) -> Difference<'a, K> {
+        Difference {
+            a: self.iter().peekable(),
+            b: other.iter().peekable(),
+        }
+    }
+
+    /// Gets an iterator over the elements present in `self` or `other`, but
+    /// not both, sorted in ascending order.
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a BTreeSet<K>,
+    ) -> SymmetricDifference<'a, K> {
+        SymmetricDifference {
+            a: self.iter().peekable(),
+            b: other.iter().peekable(),
+        }
+    }
+}
+
+/// An iterator over the elements of a [`BTreeSet`], in ascending order.
+///
+/// Created by [`BTreeSet::iter`].
+pub struct Iter<'a, K> {
+    inner: btree_map::Iter<'a, K, ()>,
+}
+
+impl<'a, K> Iterator for Iter<'a, K>
+where
+    K: Ord + Codec,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, _)| key)
+    }
+}
+
+impl<'a, K> DoubleEndedIterator for Iter<'a, K>
+where
+    K: Ord + Codec,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(key, _)| key)
+    }
+}
+
+/// A lazily evaluated iterator over the union of two sets.
+///
+/// Created by [`BTreeSet::union`].
+pub struct Union<'a, K> {
+    a: Peekable<Iter<'a, K>>,
+    b: Peekable<Iter<'a, K>>,
+}
+
+impl<'a, K> Iterator for Union<'a, K>
+where
+    K: Ord + Codec,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.a.peek(), self.b.peek()) {
+            (Some(a), Some(b)) => {
+                match a.cmp(b) {
+                    Ordering::Less => self.a.next(),
+                    Ordering::Greater => self.b.next(),
+                    Ordering::Equal => {
+                        self.b.next();
+                        self.a.next()
+                    }
+                }
+            }
+            (Some(_), None) => self.a.next(),
+            (None, Some(_)) => self.b.next(),
+            (None, None) => None,
+        }
+    }
+}
+
+/// A lazily evaluated iterator over the intersection of two sets.
+///
+/// Created by [`BTreeSet::intersection`].
+pub struct Intersection<'a, K> {
+    a: Peekable<Iter<'a, K>>,
+    b: Peekable<Iter<'a, K>>,
+}
+
+impl<'a, K> Iterator for Intersection<'a, K>
+where
+    K: Ord + Codec,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match (self.a.peek(), self.b.peek()) {
+                (Some(a), Some(b)) => {
+                    match a.cmp(b) {
+                        Ordering::Less => {
+                            self.a.next();
+                        }
+                        Ordering::Greater => {
+                            self.b.next();
+                        }
+                        Ordering::Equal => {
+                            self.b.next();
+                            return self.a.next()
+                        }
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+/// A lazily evaluated iterator over the elements of one set that are not in
+/// another.
+///
+/// Created by [`BTreeSet::difference`].
+pub struct Difference<'a, K> {
+    a: Peekable<Iter<'a, K>>,
+    b: Peekable<Iter<'a, K>>,
+}
+
+impl<'a, K> Iterator for Difference<'a, K>
+where
+    K: Ord + Codec,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match (self.a.peek(), self.b.peek()) {
+                (Some(a), Some(b)) => {
+                    match a.cmp(b) {
+                        Ordering::Less => return self.a.next(),
+                        Ordering::Greater => {
+                            self.b.next();
+                        }
+                        Ordering::Equal => {
+                            self.a.next();
+                            self.b.next();
+                        }
+                    }
+                }
+                (Some(_), None) => return self.a.next(),
+                (None, _) => return None,
+            }
+        }
+    }
+}
+
+/// A lazily evaluated iterator over the elements that are in exactly one of
+/// the two sets.
+///
+/// Created by [`BTreeSet::symmetric_difference`].
+pub struct SymmetricDifference<'a, K> {
+    a: Peekable<Iter<'a, K>>,
+    b: Peekable<Iter<'a, K>>,
+}
+
+impl<'a, K> Iterator for SymmetricDifference<'a, K>
+where
+    K: Ord + Codec,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match (self.a.peek(), self.b.peek()) {
+                (Some(a), Some(b)) => {
+                    match a.cmp(b) {
+                        Ordering::Less => return self.a.next(),
+                        Ordering::Greater => return self.b.next(),
+                        Ordering::Equal => {
+                            self.a.next();
+                            self.b.next();
+                        }
+                    }
+                }
+                (Some(_), None) => return self.a.next(),
+                (None, Some(_)) => return self.b.next(),
+                (None, None) => return None,
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An ordered set of unique elements, layered on top of `storage::BTreeMap`
+//! the same way the Rust standard library derives its `BTreeSet` from
+//! `BTreeMap`.
+
+mod impls;
+
+pub use self::impls::{
+    BTreeSet,
+    Difference,
+    Intersection,
+    Iter,
+    SymmetricDifference,
+    Union,
+};
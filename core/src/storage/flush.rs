@@ -111,3 +111,7 @@ impl Flush for bool where Self: Encode {
         }
     }
 }
+
+impl Flush for () {
+    fn flush(&mut self) {}
+}
@@ -0,0 +1,288 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage-backed max-heap priority queue.
+
+use crate::storage2::{
+    lazy::LazyIndexMap,
+    traits::{
+        KeyPtr,
+        PackedLayout,
+        SpreadLayout,
+    },
+};
+
+/// A priority queue implemented as a binary max-heap over lazily loaded
+/// storage cells.
+///
+/// # Note
+///
+/// The heap is stored as an implicit binary tree inside a [`LazyIndexMap`]:
+/// the children of the element at index `i` live at indices `2*i + 1` and
+/// `2*i + 2`. Since [`LazyIndexMap`] only ever loads the individual cells it
+/// is asked for, both [`BinaryHeap::push`] and [`BinaryHeap::pop`] touch at
+/// most `O(log n)` storage cells - the single root-to-leaf path that sifting
+/// walks - instead of the whole backing array. The greatest element is
+/// always the root and therefore always just a single cell access away via
+/// [`BinaryHeap::peek`].
+#[derive(Debug)]
+pub struct BinaryHeap<T>
+where
+    T: PackedLayout,
+{
+    /// The number of elements stored in the heap.
+    len: u32,
+    /// The elements of the heap, stored as an implicit binary tree.
+    elems: LazyIndexMap<T>,
+}
+
+impl<T> Default for BinaryHeap<T>
+where
+    T: PackedLayout,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> BinaryHeap<T>
+where
+    T: PackedLayout,
+{
+    /// Creates a new empty storage binary heap.
+    pub fn new() -> Self {
+        Self {
+            len: 0,
+            elems: LazyIndexMap::new(),
+        }
+    }
+
+    /// Returns the number of elements in the heap.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if the heap contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the index of the parent of the element at `index`.
+    fn parent(index: u32) -> Option<u32> {
+        if index == 0 {
+            return None
+        }
+        Some((index - 1) / 2)
+    }
+
+    /// Returns the indices of the children of the element at `index`.
+    fn children(index: u32) -> (u32, u32) {
+        (2 * index + 1, 2 * index + 2)
+    }
+}
+
+impl<T> BinaryHeap<T>
+where
+    T: PackedLayout + Ord,
+{
+    /// Returns a shared reference to the greatest element in the heap.
+    ///
+    /// # Note
+    ///
+    /// This performs a single storage cell access.
+    pub fn peek(&self) -> Option<&T> {
+        self.elems.get(0)
+    }
+
+    /// Pushes the given value onto the heap.
+    pub fn push(&mut self, value: T) {
+        let index = self.len;
+        self.elems.put(index, Some(value));
+        self.len += 1;
+        self.sift_up(index);
+    }
+
+    /// Removes and returns the greatest element in the heap, if any.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None
+        }
+        let last = self.len - 1;
+        self.elems.swap(0, last);
+        let popped = self.elems.put_get(last, None);
+        self.len = last;
+        if !self.is_empty() {
+            self.sift_down(0);
+        }
+        popped
+    }
+
+    /// Moves the element at `index` up until the max-heap property holds.
+    ///
+    /// Touches only the cells along the path from `index` up to the root.
+    fn sift_up(&mut self, mut index: u32) {
+        while let Some(parent) = Self::parent(index) {
+            let at_parent = self.elems.get(parent).expect("parent index is in bounds");
+            let at_index = self.elems.get(index).expect("index is in bounds");
+            if at_index <= at_parent {
+                break
+            }
+            self.elems.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    /// Moves the element at `index` down until the max-heap property holds.
+    ///
+    /// Touches only the cells along the path from `index` down to a leaf.
+    fn sift_down(&mut self, mut index: u32) {
+        loop {
+            let (left, right) = Self::children(index);
+            let mut largest = index;
+            if left < self.len
+                && self.elems.get(left).expect("left index is in bounds")
+                    > self.elems.get(largest).expect("largest index is in bounds")
+            {
+                largest = left;
+            }
+            if right < self.len
+                && self.elems.get(right).expect("right index is in bounds")
+                    > self.elems.get(largest).expect("largest index is in bounds")
+            {
+                largest = right;
+            }
+            if largest == index {
+                break
+            }
+            self.elems.swap(index, largest);
+            index = largest;
+        }
+    }
+
+    /// Returns an iterator over the elements of the heap in arbitrary order.
+    ///
+    /// # Note
+    ///
+    /// The iteration order is the heap's internal array order, not sorted
+    /// order. Repeatedly calling [`BinaryHeap::pop`] is the way to retrieve
+    /// elements greatest-first.
+    pub fn iter(&self) -> Iter<T> {
+        Iter {
+            heap: self,
+            index: 0,
+        }
+    }
+
+    /// Consumes the heap and returns an iterator yielding its elements in
+    /// descending, sorted order.
+    ///
+    /// # Note
+    ///
+    /// This is implemented as repeated calls to [`BinaryHeap::pop`], so it
+    /// carries the same `O(log n)`-per-element cost as draining the heap by
+    /// hand.
+    pub fn into_sorted_iter(self) -> IntoSortedIter<T> {
+        IntoSortedIter { heap: self }
+    }
+}
+
+/// An iterator over the elements of a [`BinaryHeap`] in internal array order.
+#[derive(Debug)]
+pub struct Iter<'a, T>
+where
+    T: PackedLayout,
+{
+    heap: &'a BinaryHeap<T>,
+    index: u32,
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: PackedLayout + Ord,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.heap.len() {
+            return None
+        }
+        let value = self.heap.elems.get(self.index);
+        self.index += 1;
+        value
+    }
+}
+
+/// An iterator that consumes a [`BinaryHeap`] and yields its elements in
+/// descending, sorted order.
+///
+/// Created through [`BinaryHeap::into_sorted_iter`].
+#[derive(Debug)]
+pub struct IntoSortedIter<T>
+where
+    T: PackedLayout,
+{
+    heap: BinaryHeap<T>,
+}
+
+impl<T> Iterator for IntoSortedIter<T>
+where
+    T: PackedLayout + Ord,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.heap.len() as usize;
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoSortedIter<T> where T: PackedLayout + Ord {}
+
+impl<T> SpreadLayout for BinaryHeap<T>
+where
+    T: PackedLayout,
+{
+    const FOOTPRINT: u64 = 1 + <LazyIndexMap<T> as SpreadLayout>::FOOTPRINT;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        Self {
+            len: SpreadLayout::pull_spread(ptr),
+            elems: SpreadLayout::pull_spread(ptr),
+        }
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        SpreadLayout::push_spread(&self.len, ptr);
+        SpreadLayout::push_spread(&self.elems, ptr);
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        for index in 0..self.len() {
+            // It might seem wasteful to clear all entries instead of just
+            // the occupied ones. However this spares us from having one extra
+            // read for every element in the heap to filter out vacant
+            // entries. So this is actually a trade-off and at the time of this
+            // implementation it is unclear which path is more efficient.
+            //
+            // The bet is that clearing a storage cell is cheaper than reading one.
+            self.elems.clear_packed_at(index);
+        }
+        SpreadLayout::clear_spread(&self.len, ptr);
+        SpreadLayout::clear_spread(&self.elems, ptr);
+    }
+}
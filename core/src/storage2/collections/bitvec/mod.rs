@@ -0,0 +1,218 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage-backed, compactly packed bit set.
+
+use crate::storage2::lazy::LazyIndexMap;
+
+/// The number of bits packed into a single storage cell.
+const BITS_PER_WORD: u32 = u64::BITS;
+
+/// A set of `u32` indices, packed 64 per storage cell.
+///
+/// # Note
+///
+/// Bits live in a [`LazyIndexMap<u64>`], one `u64` word per storage cell, at
+/// the word index `index / 64`. As with [`BinaryHeap`][`super::BinaryHeap`],
+/// this keeps [`Bitvec::get`], [`Bitvec::set`] and [`Bitvec::clear`] down to
+/// a single storage cell access instead of materializing the whole set, and
+/// lets [`Bitvec::union`], [`Bitvec::intersection`] and
+/// [`Bitvec::difference`] work word by word, touching every word below the
+/// highest word index the operands span rather than every single index.
+///
+/// Packing one bit per index instead of, say, a `Mapping<u32, bool>`, cuts
+/// the number of storage cells by up to 64x and the SCALE payload size by
+/// up to 8x
+/// - useful for allow-lists, claimed-airdrop markers, or any other large set
+/// of per-index flags.
+#[derive(Debug)]
+pub struct Bitvec {
+    /// The number of bits the set currently spans.
+    ///
+    /// Grows to cover the greatest index ever passed to [`Bitvec::set`] or
+    /// [`Bitvec::insert`]; bounds iteration and the set-algebra operations.
+    len: u32,
+    /// The number of bits currently set to `1`.
+    count: u32,
+    /// The bits, packed 64 per storage cell and lazily loaded.
+    words: LazyIndexMap<u64>,
+}
+
+impl Default for Bitvec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bitvec {
+    /// Creates a new empty bit set.
+    pub fn new() -> Self {
+        Self {
+            len: 0,
+            count: 0,
+            words: LazyIndexMap::new(),
+        }
+    }
+
+    /// Returns `true` if the set contains no indices.
+    pub fn is_empty(&self) -> bool {
+        self.count_ones() == 0
+    }
+
+    /// Returns the number of indices currently in the set.
+    pub fn count_ones(&self) -> u32 {
+        self.count
+    }
+
+    /// Returns the word index and bit mask for `index`.
+    fn locate(index: u32) -> (u32, u64) {
+        (index / BITS_PER_WORD, 1u64 << (index % BITS_PER_WORD))
+    }
+
+    /// Returns the number of words the set currently spans.
+    fn word_len(&self) -> u32 {
+        (self.len + (BITS_PER_WORD - 1)) / BITS_PER_WORD
+    }
+
+    /// Returns the word at `word_index`, or `0` if it was never written.
+    fn word(&self, word_index: u32) -> u64 {
+        self.words.get(word_index).copied().unwrap_or(0)
+    }
+
+    /// Returns `true` if `index` is in the set.
+    pub fn get(&self, index: u32) -> bool {
+        let (word_index, mask) = Self::locate(index);
+        self.word(word_index) & mask != 0
+    }
+
+    /// Puts `index` into the set.
+    pub fn set(&mut self, index: u32) {
+        let (word_index, mask) = Self::locate(index);
+        let word = self.word(word_index);
+        if word & mask == 0 {
+            self.count += 1;
+            self.words.put(word_index, Some(word | mask));
+        }
+        self.len = core::cmp::max(self.len, index + 1);
+    }
+
+    /// Removes `index` from the set.
+    pub fn clear(&mut self, index: u32) {
+        if index >= self.len {
+            return
+        }
+        let (word_index, mask) = Self::locate(index);
+        let word = self.word(word_index);
+        if word & mask != 0 {
+            self.count -= 1;
+            self.words.put(word_index, Some(word & !mask));
+        }
+    }
+
+    /// Puts `index` into the set, returning whether it was newly inserted.
+    pub fn insert(&mut self, index: u32) -> bool {
+        let was_present = self.get(index);
+        self.set(index);
+        !was_present
+    }
+
+    /// Removes `index` from the set, returning whether it was present.
+    pub fn remove(&mut self, index: u32) -> bool {
+        let was_present = self.get(index);
+        self.clear(index);
+        was_present
+    }
+
+    /// Turns `self` into the union of `self` and `other`.
+    ///
+    /// # Note
+    ///
+    /// Reads and writes every word below the greater word length of `self`
+    /// and `other`.
+    pub fn union(&mut self, other: &Self) {
+        let word_len = core::cmp::max(self.word_len(), other.word_len());
+        let mut count = 0;
+        for word_index in 0..word_len {
+            let merged = self.word(word_index) | other.word(word_index);
+            count += merged.count_ones();
+            self.words.put(word_index, Some(merged));
+        }
+        self.len = core::cmp::max(self.len, other.len);
+        self.count = count;
+    }
+
+    /// Turns `self` into the intersection of `self` and `other`.
+    ///
+    /// # Note
+    ///
+    /// Reads and writes every word below the greater word length of `self`
+    /// and `other`.
+    pub fn intersection(&mut self, other: &Self) {
+        let word_len = core::cmp::max(self.word_len(), other.word_len());
+        let mut count = 0;
+        for word_index in 0..word_len {
+            let merged = self.word(word_index) & other.word(word_index);
+            count += merged.count_ones();
+            self.words.put(word_index, Some(merged));
+        }
+        self.count = count;
+    }
+
+    /// Turns `self` into the set difference `self \ other`, i.e. the indices
+    /// present in `self` but not in `other`.
+    ///
+    /// # Note
+    ///
+    /// Reads and writes every word below the word length of `self`.
+    pub fn difference(&mut self, other: &Self) {
+        let word_len = self.word_len();
+        let mut count = 0;
+        for word_index in 0..word_len {
+            let remaining = self.word(word_index) & !other.word(word_index);
+            count += remaining.count_ones();
+            self.words.put(word_index, Some(remaining));
+        }
+        self.count = count;
+    }
+
+    /// Returns an iterator over the indices in the set, in ascending order.
+    pub fn iter(&self) -> Iter {
+        Iter {
+            bitvec: self,
+            index: 0,
+        }
+    }
+}
+
+/// An iterator over the indices of a [`Bitvec`] in ascending order.
+#[derive(Debug)]
+pub struct Iter<'a> {
+    bitvec: &'a Bitvec,
+    index: u32,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        while self.index < self.bitvec.len {
+            let index = self.index;
+            self.index += 1;
+            if self.bitvec.get(index) {
+                return Some(index)
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Box;
+use crate::storage2::{
+    alloc::free,
+    ClearForward,
+    KeyPtr,
+    PullForward,
+    StorageFootprint,
+};
+use core::mem::ManuallyDrop;
+
+impl<T> Drop for Box<T>
+where
+    T: ClearForward + StorageFootprint,
+{
+    fn drop(&mut self) {
+        // `into_inner` wraps `self` in `ManuallyDrop`, so this never runs
+        // for a box whose allocation `into_inner` has already freed.
+        if let Some(allocation) = self.allocation.take() {
+            if let Some(value) = self.value.get_cached() {
+                ClearForward::clear_forward(value, &mut KeyPtr::from(allocation.key()));
+            }
+            free(allocation);
+        }
+    }
+}
+
+impl<T> Box<T>
+where
+    T: ClearForward + StorageFootprint + PullForward,
+{
+    /// Loads the boxed value, frees its dynamic storage slot, and returns
+    /// ownership of the value to the caller.
+    ///
+    /// # Note
+    ///
+    /// Unlike dropping a `Box`, this does not clear the value's own storage
+    /// cells: the caller now owns the value and decides what becomes of it,
+    /// rather than having it cleared on its way out.
+    pub fn into_inner(self) -> T {
+        // SAFETY: `self` is wrapped in `ManuallyDrop` so its `Drop` impl
+        //         never runs; we read each of its fields out by value
+        //         exactly once below and never touch `self` again.
+        let mut this = ManuallyDrop::new(self);
+        let allocation = unsafe { core::ptr::read(&this.allocation) }
+            .take()
+            .expect("the allocation has already been freed");
+        free(allocation);
+        let value = unsafe { core::ptr::read(&this.value) };
+        value.into_inner()
+    }
+}
@@ -33,7 +33,13 @@ where
     T: ClearForward + StorageFootprint,
 {
     /// The storage area where the boxed storage entity is stored.
-    allocation: DynamicAllocation,
+    ///
+    /// # Note
+    ///
+    /// This is `None` once `Drop` has freed the allocation. `into_inner`
+    /// frees it too, but suppresses `Drop` for the consumed box, so the
+    /// allocation is never freed twice.
+    allocation: Option<DynamicAllocation>,
     /// The cache for the boxed storage entity.
     value: Lazy<T>,
 }
@@ -45,14 +51,21 @@ where
     /// Creates a new boxed entity.
     pub fn new(value: T) -> Self {
         Self {
-            allocation: alloc(),
+            allocation: Some(alloc()),
             value: Lazy::new(value),
         }
     }
 
     /// Returns the underlying storage key for the dynamic allocated entity.
+    ///
+    /// # Panics
+    ///
+    /// If the allocation has already been freed.
     fn key(&self) -> Key {
-        self.allocation.key()
+        self.allocation
+            .as_ref()
+            .expect("the allocation has already been freed")
+            .key()
     }
 }
 
@@ -0,0 +1,82 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of generic traits that are useful for the storage hash map.
+
+use super::HashMap as StorageHashMap;
+use crate::{
+    hash::hasher::Hasher,
+    storage2::traits::PackedLayout,
+};
+use core::iter::{
+    Extend,
+    FromIterator,
+};
+use ink_primitives::Key;
+
+impl<K, V, H> FromIterator<(K, V)> for StorageHashMap<K, V, H>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut hmap = StorageHashMap::new();
+        hmap.extend(iter);
+        hmap
+    }
+}
+
+impl<K, V, H> Extend<(K, V)> for StorageHashMap<K, V, H>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        // `insert` already resolves a pair through the same single
+        // `values.get_mut`-then-`put` cache access the `Entry` API itself
+        // goes through, overwriting an existing key exactly like a repeated
+        // `insert` call would.
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V, H> Extend<(&'a K, &'a V)> for StorageHashMap<K, V, H>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout + Clone,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (&'a K, &'a V)>,
+    {
+        self.extend(
+            iter.into_iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        )
+    }
+}
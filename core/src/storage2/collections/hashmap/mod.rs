@@ -35,7 +35,10 @@ use crate::{
     },
     storage2::{
         collections::Stash,
-        lazy::LazyHashMap,
+        lazy::{
+            lazy_hmap::Entry as LazyEntry,
+            LazyHashMap,
+        },
         traits::PackedLayout,
     },
 };
@@ -43,7 +46,10 @@ use core::{
     borrow::Borrow,
     cmp::Eq,
 };
-use ink_prelude::borrow::ToOwned;
+use ink_prelude::{
+    borrow::ToOwned,
+    vec::Vec,
+};
 use ink_primitives::Key;
 
 /// The index type within a hashmap.
@@ -300,6 +306,169 @@ where
             .unwrap_or(false)
     }
 
+    /// Inserts a key/value pair into the map, assuming `key` is not already
+    /// present, and returns a mutable reference to the inserted value.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`HashMap::insert`] this never probes the [`LazyHashMap`] for
+    /// an existing value first: it pushes `key` straight onto the keys
+    /// [`Stash`] and writes the [`ValueEntry`] at the resulting slot.
+    /// Prefer this over `insert` when bulk-loading a map that is already
+    /// known to be key-disjoint, e.g. when initializing from a sorted
+    /// migration, to avoid paying for a redundant existence check on every
+    /// item.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that `key` is not already present in the
+    /// map. Violating this corrupts the map: the `Stash` ends up holding
+    /// two slots for what should be a single key, and the older
+    /// [`ValueEntry`] becomes permanently unreachable.
+    pub unsafe fn insert_unique_unchecked(&mut self, key: K, value: V) -> &mut V {
+        let key_index = self.keys.put(key.to_owned());
+        &mut self
+            .values
+            .insert_unique_unchecked(key, ValueEntry { value, key_index })
+            .value
+    }
+
+    /// Extends the map with the key/value pairs yielded by `iter`, assuming
+    /// every key in `iter` is pairwise distinct and absent from the map.
+    ///
+    /// # Note
+    ///
+    /// This streams each pair through [`HashMap::insert_unique_unchecked`],
+    /// so it costs one storage write per element instead of the
+    /// read-then-write that [`Extend::extend`] pays for via `insert`.
+    ///
+    /// # Logic Error
+    ///
+    /// It is a logic error to call this with an `iter` that yields a key
+    /// already present in the map, or two equal keys: see
+    /// [`HashMap::insert_unique_unchecked`] for what happens if this is
+    /// violated.
+    pub fn extend_unique_unchecked<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        for (key, value) in iter {
+            // SAFETY: The caller of `extend_unique_unchecked` guarantees
+            //         that every key yielded by `iter` is distinct from the
+            //         others and from the map's existing keys, which is
+            //         exactly the precondition `insert_unique_unchecked`
+            //         requires.
+            unsafe {
+                self.insert_unique_unchecked(key, value);
+            }
+        }
+    }
+
+    /// Retains only the key/value pairs specified by the predicate.
+    ///
+    /// In other words, removes all pairs `(k, v)` for which `f(&k, &mut v)`
+    /// returns `false`.
+    ///
+    /// # Note
+    ///
+    /// This walks the underlying keys `Stash` once by slot index, resolving
+    /// each occupied slot's value through [`LazyHashMap::get_mut`] and
+    /// removing it right away via [`HashMap::take`] if the predicate
+    /// rejects it, rather than collecting the rejected keys into a buffer
+    /// and removing them in a second pass.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        for index in 0..self.keys.capacity() {
+            let key = match self.keys.get(index) {
+                Some(key) => key.clone(),
+                None => continue,
+            };
+            let retain = {
+                let value_entry = self
+                    .values
+                    .get_mut(&key)
+                    .expect("a key held by the stash must have an associated value");
+                f(&key, &mut value_entry.value)
+            };
+            if !retain {
+                self.take(&key);
+            }
+        }
+    }
+
+    /// Removes and returns all key/value pairs for which `f(&k, &mut v)`
+    /// returns `true`.
+    ///
+    /// # Note
+    ///
+    /// The returned [`DrainFilter`] removes an entry as soon as it is
+    /// yielded, through [`HashMap::take`] itself, so that both the keys
+    /// `Stash` slot and the storage cell of a removed entry are released.
+    pub fn drain_filter<F>(&mut self, f: F) -> DrainFilter<K, V, H, F>
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        let keys = self.keys().cloned().collect();
+        DrainFilter {
+            map: self,
+            keys,
+            pred: f,
+        }
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place manipulation.
+    ///
+    /// # Note
+    ///
+    /// Holding on to an [`OccupiedEntry`] keeps the already resolved storage
+    /// entry of the map around, so looking up the entry once and then
+    /// manipulating it via the returned [`Entry`] only ever touches the
+    /// underlying [`LazyHashMap`] once.
+    pub fn entry(&mut self, key: K) -> Entry<K, V, H> {
+        if let Some(value) = self.values.get_mut(&key) {
+            return Entry::Occupied(OccupiedEntry { key, value })
+        }
+        Entry::Vacant(VacantEntry { key, map: self })
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place
+    /// manipulation without requiring an owned `K` unless an insertion
+    /// actually happens.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`HashMap::entry`] this never clones or constructs an owned
+    /// `K` for a lookup that hits; the [`OccupiedEntryRef`] it returns reuses
+    /// the `K` already stored in the `keys` [`Stash`]. An owned `K` is only
+    /// materialized, via [`ToOwned::to_owned`], inside
+    /// [`VacantEntryRef::insert`].
+    pub fn entry_ref<'b, Q>(&mut self, key: &'b Q) -> EntryRef<'_, 'b, K, V, Q, H>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K> + ?Sized,
+    {
+        if let Some(value_entry) = self.values.get_mut(key) {
+            let key_ref = self
+                .keys
+                .get(value_entry.key_index)
+                .expect("`key_index` must point to a valid key entry");
+            return EntryRef::Occupied(OccupiedEntryRef {
+                key: key_ref,
+                value: value_entry,
+            })
+        }
+        EntryRef::Vacant(VacantEntryRef { key, map: self })
+    }
+
+    /// Returns a builder for raw entries of this map, for advanced callers
+    /// that already know (or can cheaply recompute) a key's on-chain storage
+    /// slot and want to avoid re-running the [`Hasher`] for it.
+    pub fn raw_entry_mut(&mut self) -> RawEntryBuilderMut<K, V, H> {
+        RawEntryBuilderMut { map: self }
+    }
+
     /// Defragments storage used by the storage hash map.
     ///
     /// Returns the number of storage cells freed this way.
@@ -330,3 +499,570 @@ where
         self.keys.defrag(Some(max_iterations), callback)
     }
 }
+
+/// An iterator that removes and yields all key/value pairs for which the
+/// predicate passed to [`HashMap::drain_filter`] returns `true`.
+///
+/// Created through [`HashMap::drain_filter`].
+pub struct DrainFilter<'a, K, V, H, F>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+    F: FnMut(&K, &mut V) -> bool,
+{
+    /// The map the entries are drained from.
+    map: &'a mut HashMap<K, V, H>,
+    /// The still to be visited keys, captured at construction time.
+    keys: Vec<K>,
+    /// The predicate deciding whether an entry is drained.
+    pred: F,
+}
+
+impl<'a, K, V, H, F> Iterator for DrainFilter<'a, K, V, H, F>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+    F: FnMut(&K, &mut V) -> bool,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(key) = self.keys.pop() {
+            let matches = match self.map.values.get_mut(&key) {
+                Some(value_entry) => (self.pred)(&key, &mut value_entry.value),
+                None => continue,
+            };
+            if matches {
+                let value = self
+                    .map
+                    .take(&key)
+                    .expect("key was just resolved to an occupied value; qed");
+                return Some((key, value))
+            }
+        }
+        None
+    }
+}
+
+impl<'a, K, V, H, F> Drop for DrainFilter<'a, K, V, H, F>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+    F: FnMut(&K, &mut V) -> bool,
+{
+    fn drop(&mut self) {
+        // Exhaust the iterator so that every remaining matching entry is
+        // still removed even if the caller drops this `DrainFilter` before
+        // fully consuming it, matching `std`'s own `drain_filter`/
+        // `extract_if` iterators.
+        while self.next().is_some() {}
+    }
+}
+
+/// An entry within the storage hash map, mirroring `std`'s `BTreeMap`/`HashMap` entry API.
+///
+/// This `enum` is constructed from the [`entry`][`HashMap::entry`] method on [`HashMap`].
+pub enum Entry<'a, K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// An occupied entry that already holds a value.
+    Occupied(OccupiedEntry<'a, K, V, H>),
+    /// A vacant entry that does not yet hold a value.
+    Vacant(VacantEntry<'a, K, V, H>),
+}
+
+impl<'a, K, V, H> Entry<'a, K, V, H>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Ensures a value is in the entry by inserting the default value if empty,
+    /// and returns a mutable reference to the value in the entry.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default
+    /// function if empty, and returns a mutable reference to the value in the
+    /// entry.
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result of
+    /// the default function, which takes the key as its argument, and returns
+    /// a mutable reference to the value in the entry.
+    pub fn or_insert_with_key<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce(&K) -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = default(&entry.key);
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map.
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+
+    /// Returns a reference to this entry's key.
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+}
+
+impl<'a, K, V, H> Entry<'a, K, V, H>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout + Default,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Ensures a value is in the entry by inserting the default value if empty,
+    /// and returns a mutable reference to the value in the entry.
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+/// An occupied entry of a storage hash map.
+///
+/// # Note
+///
+/// Holds on to the already resolved `&mut ValueEntry<V>` so that none of the
+/// `OccupiedEntry`'s methods need to touch the underlying `LazyHashMap` again.
+pub struct OccupiedEntry<'a, K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// The key stored in this entry.
+    key: K,
+    /// The already resolved value entry of the map.
+    value: &'a mut ValueEntry<V>,
+}
+
+impl<'a, K, V, H> OccupiedEntry<'a, K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Gets a reference to the key in the entry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Gets a reference to the value in the entry.
+    pub fn get(&self) -> &V {
+        &self.value.value
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    ///
+    /// If you need a reference which may outlive the destruction of the
+    /// `Entry` value, see [`OccupiedEntry::into_mut`].
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.value.value
+    }
+
+    /// Converts the entry into a mutable reference to its value.
+    ///
+    /// If you need multiple references to the `OccupiedEntry`, see
+    /// [`OccupiedEntry::get_mut`].
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.value.value
+    }
+
+    /// Sets the value of the entry, and returns the entry's old value.
+    pub fn insert(&mut self, new_value: V) -> V {
+        core::mem::replace(&mut self.value.value, new_value)
+    }
+}
+
+/// A vacant entry of a storage hash map.
+pub struct VacantEntry<'a, K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// The key stored in this entry.
+    key: K,
+    /// The underlying hash map that the vacant entry belongs to.
+    map: &'a mut HashMap<K, V, H>,
+}
+
+impl<'a, K, V, H> VacantEntry<'a, K, V, H>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Gets a reference to the key that would be used when inserting a value
+    /// through the `VacantEntry`.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Takes ownership of the key.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Sets the value of the entry with the `VacantEntry`'s key, performing
+    /// the `keys.put` and `values.put` storage operations exactly once, and
+    /// returns a mutable reference to it.
+    pub fn insert(self, value: V) -> &'a mut V {
+        let key_index = self.map.keys.put(self.key.clone());
+        self.map.values.put(
+            self.key.clone(),
+            Some(ValueEntry { value, key_index }),
+        );
+        self.map
+            .values
+            .get_mut(&self.key)
+            .map(|entry| &mut entry.value)
+            .expect("`insert` was just executed; qed")
+    }
+
+    /// Sets the value of the entry with the `VacantEntry`'s key, and returns
+    /// an [`OccupiedEntry`] for the same, now-occupied entry, so that a
+    /// follow-up `.key()`, `.get()`/`.get_mut()`, or `.insert()` reuses the
+    /// cache slot `insert_entry` already paid the cache miss for, instead of
+    /// re-deriving a fresh `Entry` and re-hashing the key.
+    pub fn insert_entry(self, value: V) -> OccupiedEntry<'a, K, V, H> {
+        let key_index = self.map.keys.put(self.key.clone());
+        self.map.values.put(
+            self.key.clone(),
+            Some(ValueEntry { value, key_index }),
+        );
+        let value = self
+            .map
+            .values
+            .get_mut(&self.key)
+            .expect("`insert` was just executed; qed");
+        OccupiedEntry {
+            key: self.key,
+            value,
+        }
+    }
+}
+
+/// An entry of a [`HashMap`] reached through a borrowed key via
+/// [`HashMap::entry_ref`].
+pub enum EntryRef<'a, 'b, K, V, Q, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+    Q: 'b + ?Sized,
+{
+    /// An occupied entry that already holds a value.
+    Occupied(OccupiedEntryRef<'a, K, V, H>),
+    /// A vacant entry that holds on to the originally queried borrowed key.
+    Vacant(VacantEntryRef<'a, 'b, K, V, Q, H>),
+}
+
+impl<'a, 'b, K, V, Q, H> EntryRef<'a, 'b, K, V, Q, H>
+where
+    K: Ord + Eq + Clone + Borrow<Q> + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+    Q: Ord + scale::Encode + ToOwned<Owned = K> + ?Sized,
+{
+    /// Ensures a value is in the entry by inserting the default value if empty,
+    /// and returns a mutable reference to the value in the entry.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default
+    /// function if empty, and returns a mutable reference to the value in the
+    /// entry.
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result of
+    /// the default function, which takes the borrowed key as its argument,
+    /// and returns a mutable reference to the value in the entry.
+    pub fn or_insert_with_key<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce(&Q) -> V,
+    {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => {
+                let value = default(entry.key);
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map.
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            EntryRef::Occupied(mut entry) => {
+                f(entry.get_mut());
+                EntryRef::Occupied(entry)
+            }
+            EntryRef::Vacant(entry) => EntryRef::Vacant(entry),
+        }
+    }
+}
+
+impl<'a, 'b, K, V, Q, H> EntryRef<'a, 'b, K, V, Q, H>
+where
+    K: Ord + Eq + Clone + Borrow<Q> + PackedLayout,
+    V: PackedLayout + Default,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+    Q: Ord + scale::Encode + ToOwned<Owned = K> + ?Sized,
+{
+    /// Ensures a value is in the entry by inserting the default value if empty,
+    /// and returns a mutable reference to the value in the entry.
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+/// An occupied entry of a [`HashMap`], reached through [`HashMap::entry_ref`].
+///
+/// # Note
+///
+/// Unlike [`OccupiedEntry`] this does not own `K`: the key is already stored
+/// in the map's `keys` [`Stash`], so this just borrows it from there instead
+/// of cloning it for a lookup that never needed an owned key to begin with.
+pub struct OccupiedEntryRef<'a, K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// The key stored in the map's `keys` `Stash`, borrowed from there.
+    key: &'a K,
+    /// The already resolved value entry of the map.
+    value: &'a mut ValueEntry<V>,
+}
+
+impl<'a, K, V, H> OccupiedEntryRef<'a, K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Gets a reference to the key in the entry.
+    pub fn key(&self) -> &K {
+        self.key
+    }
+
+    /// Gets a reference to the value in the entry.
+    pub fn get(&self) -> &V {
+        &self.value.value
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    ///
+    /// If you need a reference which may outlive the destruction of the
+    /// `EntryRef` value, see [`OccupiedEntryRef::into_mut`].
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.value.value
+    }
+
+    /// Converts the entry into a mutable reference to its value.
+    ///
+    /// If you need multiple references to the `OccupiedEntryRef`, see
+    /// [`OccupiedEntryRef::get_mut`].
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.value.value
+    }
+
+    /// Sets the value of the entry, and returns the entry's old value.
+    pub fn insert(&mut self, new_value: V) -> V {
+        core::mem::replace(&mut self.value.value, new_value)
+    }
+}
+
+/// A vacant entry of a [`HashMap`], reached through [`HashMap::entry_ref`].
+///
+/// Holds on to the original borrowed key instead of an owned `K`; an owned
+/// `K` is only materialized, via [`ToOwned::to_owned`], if
+/// [`VacantEntryRef::insert`] actually fires.
+pub struct VacantEntryRef<'a, 'b, K, V, Q, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+    Q: 'b + ?Sized,
+{
+    /// The borrowed key that would be used to construct an owned key upon insertion.
+    key: &'b Q,
+    /// The underlying hash map that the vacant entry belongs to.
+    map: &'a mut HashMap<K, V, H>,
+}
+
+impl<'a, 'b, K, V, Q, H> VacantEntryRef<'a, 'b, K, V, Q, H>
+where
+    K: Ord + Eq + Clone + Borrow<Q> + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+    Q: Ord + scale::Encode + ToOwned<Owned = K> + ?Sized,
+{
+    /// Gets a reference to the borrowed key that would be used when inserting
+    /// a value through the `VacantEntryRef`.
+    pub fn key(&self) -> &Q {
+        self.key
+    }
+
+    /// Sets the value of the entry with the `VacantEntryRef`'s key,
+    /// materializing an owned key via [`ToOwned::to_owned`] and performing
+    /// the `keys.put` and `values.put` storage operations exactly once, and
+    /// returns a mutable reference to it.
+    pub fn insert(self, value: V) -> &'a mut V {
+        let owned_key = self.key.to_owned();
+        let key_index = self.map.keys.put(owned_key.clone());
+        self.map.values.put(
+            owned_key.clone(),
+            Some(ValueEntry { value, key_index }),
+        );
+        self.map
+            .values
+            .get_mut(&owned_key)
+            .map(|entry| &mut entry.value)
+            .expect("`insert` was just executed; qed")
+    }
+}
+
+/// A builder for raw entries of a [`HashMap`], returned by
+/// [`HashMap::raw_entry_mut`].
+///
+/// # Note
+///
+/// Unlike `hashbrown`'s raw entry API this builder has no `from_hash`
+/// constructor: this map keeps no hash-bucket index over its keys (only the
+/// `keys` [`Stash`] and the by-encoded-key-bytes `values` cache), and every
+/// [`VacantEntry`] already carries the key it would insert, so there is no
+/// way to hand back a vacant entry for a hash that matched no key.
+pub struct RawEntryBuilderMut<'a, K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// The underlying hash map that the raw entry is resolved against.
+    map: &'a mut HashMap<K, V, H>,
+}
+
+impl<'a, K, V, H> RawEntryBuilderMut<'a, K, V, H>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Looks up `key`'s entry, computing its on-chain storage slot via the
+    /// `Hasher` as usual on a cache miss.
+    ///
+    /// This is equivalent to [`HashMap::entry`]; the raw-entry and the
+    /// ordinary entry API share the same `Entry`/`OccupiedEntry`/`VacantEntry`
+    /// surface, since the only thing the raw entry API changes is how a cache
+    /// miss resolves its on-chain storage slot.
+    pub fn from_key<Q>(self, key: &Q) -> Entry<'a, K, V, H>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K> + ?Sized,
+    {
+        self.map.entry(key.to_owned())
+    }
+
+    /// Looks up `key`'s entry on a cache miss by pulling directly from
+    /// `hashed_key`, the caller-supplied on-chain storage slot, instead of
+    /// recomputing it via the map's internal key-hashing step.
+    ///
+    /// # Note
+    ///
+    /// The caller must guarantee that `hashed_key` is the storage slot that
+    /// this map would otherwise have computed for `key`; supplying a
+    /// mismatched key only ever yields incorrect (or vacant) results, it is
+    /// not a memory-safety hazard.
+    pub fn from_key_hashed_nocheck<Q>(self, hashed_key: Key, key: &Q) -> Entry<'a, K, V, H>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K> + ?Sized,
+    {
+        let key = key.to_owned();
+        match self
+            .map
+            .values
+            .raw_entry_mut()
+            .from_key_hashed_nocheck(hashed_key, key.clone())
+        {
+            LazyEntry::Occupied(occupied) => {
+                Entry::Occupied(OccupiedEntry {
+                    key,
+                    value: occupied.into_mut(),
+                })
+            }
+            LazyEntry::Vacant(_) => Entry::Vacant(VacantEntry { key, map: self.map }),
+        }
+    }
+}
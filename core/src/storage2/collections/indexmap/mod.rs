@@ -0,0 +1,243 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An insertion-order-stable map that allows addressing entries by their
+//! stable positional index in addition to their key.
+
+use crate::{
+    hash::hasher::{
+        Blake2x256Hasher,
+        Hasher,
+    },
+    storage2::{
+        collections::Stash,
+        lazy::LazyHashMap,
+        traits::PackedLayout,
+    },
+};
+use core::{
+    borrow::Borrow,
+    cmp::Eq,
+};
+use ink_prelude::borrow::ToOwned;
+use ink_primitives::Key;
+
+/// The index type within an index map.
+type KeyIndex = u32;
+
+/// A map that preserves the insertion order of its entries and allows
+/// addressing them by a stable `u32` index in addition to their key.
+///
+/// # Note
+///
+/// This reuses the same layout as [`super::HashMap`]: keys are held in a
+/// [`Stash`] whose slot index (`key_index`) never changes for the lifetime
+/// of an entry, while values are kept in a [`LazyHashMap`] keyed by the
+/// actual map key. Addressing by index is therefore just a `Stash::get`
+/// away and never requires hashing.
+#[derive(Debug)]
+pub struct IndexMap<K, V, H = Blake2x256Hasher>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// The keys of the index map, in insertion order modulo removals.
+    keys: Stash<K>,
+    /// The values of the index map.
+    values: LazyHashMap<K, ValueEntry<V>, H>,
+}
+
+/// An entry within the index map.
+#[derive(Debug, scale::Encode, scale::Decode)]
+struct ValueEntry<V> {
+    /// The value stored in this entry.
+    value: V,
+    /// The index of the key associated with this value.
+    key_index: KeyIndex,
+}
+
+impl<K, V, H> Default for IndexMap<K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, H> IndexMap<K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Creates a new empty index map.
+    pub fn new() -> Self {
+        Self {
+            keys: Stash::new(),
+            values: LazyHashMap::new(),
+        }
+    }
+
+    /// Returns the number of key/value pairs stored in the index map.
+    pub fn len(&self) -> u32 {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the index map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl<K, V, H> IndexMap<K, V, H>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<H::Output>,
+{
+    /// Inserts a key/value pair into the map.
+    ///
+    /// Returns the previous value associated with the same key if any.
+    pub fn insert(&mut self, key: K, new_value: V) -> Option<V> {
+        if let Some(occupied) = self.values.get_mut(&key) {
+            return Some(core::mem::replace(&mut occupied.value, new_value))
+        }
+        let key_index = self.keys.put(key.to_owned());
+        self.values.put(
+            key,
+            Some(ValueEntry {
+                value: new_value,
+                key_index,
+            }),
+        );
+        None
+    }
+
+    /// Removes the key/value pair from the map associated with the given key.
+    pub fn take<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        let entry = self.values.put_get(key, None)?;
+        self.keys
+            .take(entry.key_index)
+            .expect("`key_index` must point to a valid key entry");
+        Some(entry.value)
+    }
+
+    /// Returns a shared reference to the value corresponding to the key.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        self.values.get(key).map(|entry| &entry.value)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        self.values.get_mut(key).map(|entry| &mut entry.value)
+    }
+
+    /// Returns the key/value pair stored at the given stable slot index.
+    pub fn get_index(&self, index: u32) -> Option<(&K, &V)> {
+        let key = self.keys.get(index)?;
+        let value = self
+            .values
+            .get(key)
+            .expect("a key held by the stash must have an associated value");
+        Some((key, &value.value))
+    }
+
+    /// Returns a mutable reference to the value stored at the given stable
+    /// slot index, alongside a shared reference to its key.
+    pub fn get_index_mut(&mut self, index: u32) -> Option<(&K, &mut V)> {
+        let key = self.keys.get(index)?.clone();
+        let value = self
+            .values
+            .get_mut(&key)
+            .expect("a key held by the stash must have an associated value");
+        Some((self.keys.get(index).expect("key just confirmed to exist"), &mut value.value))
+    }
+
+    /// Returns the stable slot index, key and value for the given key.
+    pub fn get_full<Q>(&self, key: &Q) -> Option<(u32, &K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        let entry = self.values.get(key)?;
+        let stored_key = self
+            .keys
+            .get(entry.key_index)
+            .expect("`key_index` must point to a valid key entry");
+        Some((entry.key_index, stored_key, &entry.value))
+    }
+
+    /// Returns an iterator yielding shared references to all key/value pairs
+    /// of the index map in ascending slot-index (i.e. insertion) order.
+    pub fn iter(&self) -> Iter<K, V, H> {
+        Iter {
+            map: self,
+            index: 0,
+        }
+    }
+}
+
+/// An iterator over the key/value pairs of an [`IndexMap`] in ascending
+/// slot-index order.
+pub struct Iter<'a, K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    map: &'a IndexMap<K, V, H>,
+    index: u32,
+}
+
+impl<'a, K, V, H> Iterator for Iter<'a, K, V, H>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.map.keys.capacity() {
+            let index = self.index;
+            self.index += 1;
+            if let Some(kv) = self.map.get_index(index) {
+                return Some(kv)
+            }
+        }
+        None
+    }
+}
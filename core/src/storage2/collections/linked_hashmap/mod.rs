@@ -0,0 +1,479 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage hash map that iterates its entries in insertion order.
+
+use crate::{
+    hash::hasher::{
+        Blake2x256Hasher,
+        Hasher,
+    },
+    storage2::{
+        collections::{
+            extend_lifetime,
+            Stash,
+        },
+        lazy::LazyHashMap,
+        traits::{
+            forward_clear_packed,
+            forward_pull_packed,
+            forward_push_packed,
+            KeyPtr,
+            PackedLayout,
+            SpreadLayout,
+        },
+    },
+};
+use core::{
+    borrow::Borrow,
+    cmp::Eq,
+};
+use ink_prelude::borrow::ToOwned;
+use ink_primitives::Key;
+
+/// The index type within a linked hash map.
+///
+/// # Note
+///
+/// Used for key indices internal to the linked hash map, and doubles as the
+/// addressing scheme for the doubly linked list threaded through the nodes.
+type KeyIndex = u32;
+
+/// A hash map that additionally remembers the order in which its entries
+/// were first inserted, analogous to the `linked-hash-map` crate.
+///
+/// # Note
+///
+/// This reuses the same keys-[`Stash`]-plus-values-[`LazyHashMap`] layout as
+/// [`super::HashMap`]. Every value is wrapped in a [`Node`] that, besides the
+/// user value, carries the `prev`/`next` slot indices of its neighbors in
+/// insertion order, and the map itself stores the `head`/`tail` indices of
+/// the list. Inserting a new key always appends it at the tail; removing a
+/// key splices its neighbors together. Updating the value of an already
+/// present key leaves its position in the order untouched.
+#[derive(Debug)]
+pub struct LinkedHashMap<K, V, H = Blake2x256Hasher>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// The slot index of the first entry in insertion order, if any.
+    head: Option<KeyIndex>,
+    /// The slot index of the last entry in insertion order, if any.
+    tail: Option<KeyIndex>,
+    /// The keys of the linked hash map.
+    keys: Stash<K>,
+    /// The values of the linked hash map.
+    values: LazyHashMap<K, Node<V>, H>,
+}
+
+/// An entry within the linked hash map.
+///
+/// Stores the value, the index of its associated key, and the indices of
+/// its neighbors in insertion order.
+#[derive(Debug, scale::Encode, scale::Decode)]
+struct Node<V> {
+    /// The value stored in this entry.
+    value: V,
+    /// The index of the key associated with this value.
+    key_index: KeyIndex,
+    /// The slot index of the previous entry in insertion order, if any.
+    prev: Option<KeyIndex>,
+    /// The slot index of the next entry in insertion order, if any.
+    next: Option<KeyIndex>,
+}
+
+impl<K, V, H> Default for LinkedHashMap<K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, H> LinkedHashMap<K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Creates a new empty linked hash map.
+    pub fn new() -> Self {
+        Self {
+            head: None,
+            tail: None,
+            keys: Stash::new(),
+            values: LazyHashMap::new(),
+        }
+    }
+
+    /// Returns the number of key/value pairs stored in the linked hash map.
+    pub fn len(&self) -> u32 {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the linked hash map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns an iterator yielding shared references to all key/value pairs
+    /// of the linked hash map in insertion order.
+    ///
+    /// # Note
+    ///
+    /// - Avoid unbounded iteration over big linked hash maps.
+    /// - Prefer using methods like `Iterator::take` in order to limit the number
+    ///   of yielded elements.
+    pub fn iter(&self) -> Iter<K, V, H> {
+        Iter {
+            map: self,
+            next: self.head,
+        }
+    }
+
+    /// Returns an iterator yielding exclusive references to all key/value
+    /// pairs of the linked hash map in insertion order.
+    ///
+    /// # Note
+    ///
+    /// - Avoid unbounded iteration over big linked hash maps.
+    /// - Prefer using methods like `Iterator::take` in order to limit the number
+    ///   of yielded elements.
+    pub fn iter_mut(&mut self) -> IterMut<K, V, H> {
+        IterMut {
+            next: self.head,
+            map: self,
+        }
+    }
+
+    /// Returns an iterator yielding shared references to all keys of the
+    /// linked hash map in insertion order.
+    pub fn keys(&self) -> Keys<K, V, H> {
+        Keys { iter: self.iter() }
+    }
+}
+
+impl<K, V, H> LinkedHashMap<K, V, H>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<H::Output>,
+{
+    /// Inserts a key/value pair into the map.
+    ///
+    /// Returns the previous value associated with the same key if any.
+    ///
+    /// # Note
+    ///
+    /// If the map did already have this key present, only the value is
+    /// updated and the key's position in the insertion order is left
+    /// unchanged; this matches the behavior of the `linked-hash-map` crate.
+    pub fn insert(&mut self, key: K, new_value: V) -> Option<V> {
+        if let Some(node) = self.values.get_mut(&key) {
+            return Some(core::mem::replace(&mut node.value, new_value))
+        }
+        let key_index = self.keys.put(key.to_owned());
+        let prev = self.tail;
+        self.values.put(
+            key,
+            Some(Node {
+                value: new_value,
+                key_index,
+                prev,
+                next: None,
+            }),
+        );
+        match prev {
+            Some(prev_index) => {
+                let prev_key = self
+                    .keys
+                    .get(prev_index)
+                    .expect("tail must point to a valid key entry")
+                    .clone();
+                self.values
+                    .get_mut(&prev_key)
+                    .expect("a key held by the stash must have an associated value")
+                    .next = Some(key_index);
+            }
+            None => self.head = Some(key_index),
+        }
+        self.tail = Some(key_index);
+        None
+    }
+
+    /// Removes the key/value pair from the map associated with the given key.
+    ///
+    /// Returns the removed value if any.
+    pub fn take<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        let node = self.values.put_get(key, None)?;
+        self.keys
+            .take(node.key_index)
+            .expect("`key_index` must point to a valid key entry");
+        match node.prev {
+            Some(prev_index) => {
+                let prev_key = self
+                    .keys
+                    .get(prev_index)
+                    .expect("`prev` must point to a valid key entry")
+                    .clone();
+                self.values
+                    .get_mut(&prev_key)
+                    .expect("a key held by the stash must have an associated value")
+                    .next = node.next;
+            }
+            None => self.head = node.next,
+        }
+        match node.next {
+            Some(next_index) => {
+                let next_key = self
+                    .keys
+                    .get(next_index)
+                    .expect("`next` must point to a valid key entry")
+                    .clone();
+                self.values
+                    .get_mut(&next_key)
+                    .expect("a key held by the stash must have an associated value")
+                    .prev = node.prev;
+            }
+            None => self.tail = node.prev,
+        }
+        Some(node.value)
+    }
+
+    /// Returns a shared reference to the value corresponding to the key.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        self.values.get(key).map(|node| &node.value)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        self.values.get_mut(key).map(|node| &mut node.value)
+    }
+
+    /// Returns `true` if there is an entry corresponding to the key in the map.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        self.values.get(key).is_some()
+    }
+}
+
+/// An iterator over the key/value pairs of a [`LinkedHashMap`] in insertion order.
+pub struct Iter<'a, K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    map: &'a LinkedHashMap<K, V, H>,
+    next: Option<KeyIndex>,
+}
+
+impl<'a, K, V, H> Iterator for Iter<'a, K, V, H>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.next?;
+        let key = self
+            .map
+            .keys
+            .get(index)
+            .expect("`next` must point to a valid key entry");
+        let node = self
+            .map
+            .values
+            .get(key)
+            .expect("a key held by the stash must have an associated value");
+        self.next = node.next;
+        Some((key, &node.value))
+    }
+}
+
+/// An iterator over the keys of a [`LinkedHashMap`] in insertion order.
+pub struct Keys<'a, K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    iter: Iter<'a, K, V, H>,
+}
+
+impl<'a, K, V, H> Iterator for Keys<'a, K, V, H>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, _)| key)
+    }
+}
+
+/// An iterator over exclusive references to the key/value pairs of a
+/// [`LinkedHashMap`] in insertion order.
+pub struct IterMut<'a, K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    map: &'a mut LinkedHashMap<K, V, H>,
+    next: Option<KeyIndex>,
+}
+
+impl<'a, K, V, H> Iterator for IterMut<'a, K, V, H>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    type Item = (K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.next?;
+        let key = self
+            .map
+            .keys
+            .get(index)
+            .expect("`next` must point to a valid key entry")
+            .clone();
+        let node = self
+            .map
+            .values
+            .get_mut(&key)
+            .expect("a key held by the stash must have an associated value");
+        self.next = node.next;
+        // SAFETY: We extend the lifetime of the reference here.
+        //
+        //         This is safe because the iterator yields an exclusive
+        //         reference to every element of the iterated map just
+        //         once and there can be only one such iterator for the
+        //         same map at the same time, which is guaranteed by the
+        //         constructor of the iterator.
+        let value = unsafe { extend_lifetime::<'_, 'a, V>(&mut node.value) };
+        Some((key, value))
+    }
+}
+
+impl<T> SpreadLayout for Node<T>
+where
+    T: PackedLayout,
+{
+    const FOOTPRINT: u64 = 1;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        forward_pull_packed::<Self>(ptr)
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        forward_push_packed::<Self>(self, ptr)
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        forward_clear_packed::<Self>(self, ptr)
+    }
+}
+
+impl<T> PackedLayout for Node<T>
+where
+    T: PackedLayout,
+{
+    fn pull_packed(&mut self, at: &Key) {
+        <T as PackedLayout>::pull_packed(&mut self.value, at)
+    }
+
+    fn push_packed(&self, at: &Key) {
+        <T as PackedLayout>::push_packed(&self.value, at)
+    }
+
+    fn clear_packed(&self, at: &Key) {
+        <T as PackedLayout>::clear_packed(&self.value, at)
+    }
+}
+
+impl<K, V, H, O> SpreadLayout for LinkedHashMap<K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher<Output = O>,
+    O: Default,
+    Key: From<O>,
+{
+    const FOOTPRINT: u64 = 2 + <Stash<K> as SpreadLayout>::FOOTPRINT;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        Self {
+            head: SpreadLayout::pull_spread(ptr),
+            tail: SpreadLayout::pull_spread(ptr),
+            keys: SpreadLayout::pull_spread(ptr),
+            values: SpreadLayout::pull_spread(ptr),
+        }
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        SpreadLayout::push_spread(&self.head, ptr);
+        SpreadLayout::push_spread(&self.tail, ptr);
+        SpreadLayout::push_spread(&self.keys, ptr);
+        SpreadLayout::push_spread(&self.values, ptr);
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        for key in self.keys() {
+            // Walking `keys()` follows the insertion-order list and so
+            // visits only occupied entries, loading each node once on the way.
+            self.values.clear_packed_at(key);
+        }
+        SpreadLayout::clear_spread(&self.head, ptr);
+        SpreadLayout::clear_spread(&self.tail, ptr);
+        SpreadLayout::clear_spread(&self.keys, ptr);
+        SpreadLayout::clear_spread(&self.values, ptr);
+    }
+}
@@ -0,0 +1,477 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A bounded, least-recently-used storage cache.
+
+use crate::{
+    hash::hasher::{
+        Blake2x256Hasher,
+        Hasher,
+    },
+    storage2::{
+        collections::Stash,
+        lazy::LazyHashMap,
+        traits::{
+            forward_clear_packed,
+            forward_pull_packed,
+            forward_push_packed,
+            KeyPtr,
+            PackedLayout,
+            SpreadLayout,
+        },
+    },
+};
+use core::{
+    borrow::Borrow,
+    cmp::Eq,
+};
+use ink_prelude::borrow::ToOwned;
+use ink_primitives::Key;
+
+/// The index type within an LRU cache.
+///
+/// # Note
+///
+/// Used for key indices internal to the cache, and doubles as the addressing
+/// scheme for the intrusive doubly linked recency list threaded through the
+/// occupied stash slots.
+type KeyIndex = u32;
+
+/// A fixed-capacity storage cache that evicts its least-recently-used entry
+/// once full, porting the `lru-cache` crate's design to contract storage.
+///
+/// # Note
+///
+/// This reuses the same keys-[`Stash`]-plus-values-[`LazyHashMap`] layout as
+/// [`super::HashMap`]. Every value is wrapped in a [`Node`] that, besides the
+/// user value, carries the `prev`/`next` slot indices of its neighbors in
+/// the recency list, while the cache itself stores the `head` (least
+/// recently used) and `tail` (most recently used) indices of that list.
+/// [`LruCache::get`] and [`LruCache::get_mut`] splice the touched node to the
+/// tail in `O(1)`; [`LruCache::insert`] on a full cache first unlinks and
+/// clears the head node before inserting the new entry at the tail.
+#[derive(Debug)]
+pub struct LruCache<K, V, H = Blake2x256Hasher>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// The slot index of the least-recently-used entry, if any.
+    head: Option<KeyIndex>,
+    /// The slot index of the most-recently-used entry, if any.
+    tail: Option<KeyIndex>,
+    /// The maximum number of entries the cache may hold at once.
+    capacity: u32,
+    /// The keys of the LRU cache.
+    keys: Stash<K>,
+    /// The values of the LRU cache.
+    values: LazyHashMap<K, Node<V>, H>,
+}
+
+/// An entry within the LRU cache.
+///
+/// Stores the value, the index of its associated key, and the indices of
+/// its neighbors in the recency list.
+#[derive(Debug, scale::Encode, scale::Decode)]
+struct Node<V> {
+    /// The value stored in this entry.
+    value: V,
+    /// The index of the key associated with this value.
+    key_index: KeyIndex,
+    /// The slot index of the less-recently-used neighbor, if any.
+    prev: Option<KeyIndex>,
+    /// The slot index of the more-recently-used neighbor, if any.
+    next: Option<KeyIndex>,
+}
+
+impl<K, V, H> LruCache<K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Creates a new empty LRU cache that holds at most `capacity` entries.
+    pub fn new(capacity: u32) -> Self {
+        Self {
+            head: None,
+            tail: None,
+            capacity,
+            keys: Stash::new(),
+            values: LazyHashMap::new(),
+        }
+    }
+
+    /// Returns the number of key/value pairs currently stored in the cache.
+    pub fn len(&self) -> u32 {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the maximum number of entries the cache may hold at once.
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Returns an iterator yielding shared references to all key/value pairs
+    /// of the cache, ordered from most-recently-used to least-recently-used.
+    ///
+    /// # Note
+    ///
+    /// - Avoid unbounded iteration over big LRU caches.
+    /// - Prefer using methods like `Iterator::take` in order to limit the number
+    ///   of yielded elements.
+    pub fn iter(&self) -> Iter<K, V, H> {
+        Iter {
+            cache: self,
+            next: self.tail,
+        }
+    }
+}
+
+impl<K, V, H> LruCache<K, V, H>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<H::Output>,
+{
+    /// Detaches the entry for `key` from the recency list without touching
+    /// its storage entry.
+    fn detach<Q>(&mut self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K> + ?Sized,
+    {
+        let node = self
+            .values
+            .get(key)
+            .expect("a key held by the stash must have an associated value");
+        let prev = node.prev;
+        let next = node.next;
+        match prev {
+            Some(prev_index) => {
+                let prev_key = self
+                    .keys
+                    .get(prev_index)
+                    .expect("`prev` must point to a valid key entry")
+                    .clone();
+                self.values
+                    .get_mut(&prev_key)
+                    .expect("a key held by the stash must have an associated value")
+                    .next = next;
+            }
+            None => self.head = next,
+        }
+        match next {
+            Some(next_index) => {
+                let next_key = self
+                    .keys
+                    .get(next_index)
+                    .expect("`next` must point to a valid key entry")
+                    .clone();
+                self.values
+                    .get_mut(&next_key)
+                    .expect("a key held by the stash must have an associated value")
+                    .prev = prev;
+            }
+            None => self.tail = prev,
+        }
+    }
+
+    /// Attaches the entry for `key`, whose slot index is `key_index`, to the
+    /// tail of the recency list, making it the most-recently-used entry.
+    fn attach_tail<Q>(&mut self, key: &Q, key_index: KeyIndex)
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K> + ?Sized,
+    {
+        let prev = self.tail;
+        match prev {
+            Some(prev_index) => {
+                let prev_key = self
+                    .keys
+                    .get(prev_index)
+                    .expect("tail must point to a valid key entry")
+                    .clone();
+                self.values
+                    .get_mut(&prev_key)
+                    .expect("a key held by the stash must have an associated value")
+                    .next = Some(key_index);
+            }
+            None => self.head = Some(key_index),
+        }
+        let node = self
+            .values
+            .get_mut(key)
+            .expect("a key held by the stash must have an associated value");
+        node.prev = prev;
+        node.next = None;
+        self.tail = Some(key_index);
+    }
+
+    /// Moves the entry for `key` to the tail of the recency list, making it
+    /// the most-recently-used entry, unless it already is.
+    fn touch<Q>(&mut self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K> + ?Sized,
+    {
+        let node = self
+            .values
+            .get(key)
+            .expect("a key held by the stash must have an associated value");
+        let key_index = node.key_index;
+        if self.tail == Some(key_index) {
+            return
+        }
+        self.detach(key);
+        self.attach_tail(key, key_index);
+    }
+
+    /// Unlinks and removes the least-recently-used entry, if any.
+    fn evict_lru(&mut self) {
+        let head_index = match self.head {
+            Some(head_index) => head_index,
+            None => return,
+        };
+        let head_key = self
+            .keys
+            .get(head_index)
+            .expect("head must point to a valid key entry")
+            .clone();
+        self.detach(&head_key);
+        self.values.put_get(&head_key, None);
+        self.keys
+            .take(head_index)
+            .expect("head must point to a valid key entry");
+    }
+
+    /// Inserts a key/value pair into the cache, making it the
+    /// most-recently-used entry.
+    ///
+    /// Returns the previous value associated with the same key if any. If
+    /// the cache is full and `key` is not already present, the
+    /// least-recently-used entry is evicted to make room.
+    pub fn insert(&mut self, key: K, new_value: V) -> Option<V> {
+        if self.values.get(&key).is_some() {
+            let old_value = core::mem::replace(
+                &mut self
+                    .values
+                    .get_mut(&key)
+                    .expect("key was just confirmed to be occupied; qed")
+                    .value,
+                new_value,
+            );
+            self.touch(&key);
+            return Some(old_value)
+        }
+        if self.capacity == 0 {
+            return None
+        }
+        if self.len() >= self.capacity {
+            self.evict_lru();
+        }
+        let key_index = self.keys.put(key.to_owned());
+        self.values.put(
+            key.to_owned(),
+            Some(Node {
+                value: new_value,
+                key_index,
+                prev: None,
+                next: None,
+            }),
+        );
+        self.attach_tail(&key, key_index);
+        None
+    }
+
+    /// Shrinks or grows the capacity of the cache, evicting
+    /// least-recently-used entries if the new capacity is smaller than the
+    /// current length.
+    pub fn set_capacity(&mut self, new_capacity: u32) {
+        while self.len() > new_capacity {
+            self.evict_lru();
+        }
+        self.capacity = new_capacity;
+    }
+
+    /// Returns a shared reference to the value corresponding to the key
+    /// without changing its position in the recency list.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        self.values.get(key).map(|node| &node.value)
+    }
+
+    /// Returns a shared reference to the value corresponding to the key,
+    /// marking it as the most-recently-used entry.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        if self.values.get(key).is_some() {
+            self.touch(key);
+        }
+        self.values.get(key).map(|node| &node.value)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key,
+    /// marking it as the most-recently-used entry.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        if self.values.get(key).is_some() {
+            self.touch(key);
+        }
+        self.values.get_mut(key).map(|node| &mut node.value)
+    }
+
+    /// Returns `true` if there is an entry corresponding to the key in the cache.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        self.values.get(key).is_some()
+    }
+}
+
+/// An iterator over the key/value pairs of an [`LruCache`], ordered from
+/// most-recently-used to least-recently-used.
+pub struct Iter<'a, K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    cache: &'a LruCache<K, V, H>,
+    next: Option<KeyIndex>,
+}
+
+impl<'a, K, V, H> Iterator for Iter<'a, K, V, H>
+where
+    K: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.next?;
+        let key = self
+            .cache
+            .keys
+            .get(index)
+            .expect("`next` must point to a valid key entry");
+        let node = self
+            .cache
+            .values
+            .get(key)
+            .expect("a key held by the stash must have an associated value");
+        self.next = node.prev;
+        Some((key, &node.value))
+    }
+}
+
+impl<T> SpreadLayout for Node<T>
+where
+    T: PackedLayout,
+{
+    const FOOTPRINT: u64 = 1;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        forward_pull_packed::<Self>(ptr)
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        forward_push_packed::<Self>(self, ptr)
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        forward_clear_packed::<Self>(self, ptr)
+    }
+}
+
+impl<T> PackedLayout for Node<T>
+where
+    T: PackedLayout,
+{
+    fn pull_packed(&mut self, at: &Key) {
+        <T as PackedLayout>::pull_packed(&mut self.value, at)
+    }
+
+    fn push_packed(&self, at: &Key) {
+        <T as PackedLayout>::push_packed(&self.value, at)
+    }
+
+    fn clear_packed(&self, at: &Key) {
+        <T as PackedLayout>::clear_packed(&self.value, at)
+    }
+}
+
+impl<K, V, H, O> SpreadLayout for LruCache<K, V, H>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: Hasher<Output = O>,
+    O: Default,
+    Key: From<O>,
+{
+    const FOOTPRINT: u64 = 3 + <Stash<K> as SpreadLayout>::FOOTPRINT;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        Self {
+            head: SpreadLayout::pull_spread(ptr),
+            tail: SpreadLayout::pull_spread(ptr),
+            capacity: SpreadLayout::pull_spread(ptr),
+            keys: SpreadLayout::pull_spread(ptr),
+            values: SpreadLayout::pull_spread(ptr),
+        }
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        SpreadLayout::push_spread(&self.head, ptr);
+        SpreadLayout::push_spread(&self.tail, ptr);
+        SpreadLayout::push_spread(&self.capacity, ptr);
+        SpreadLayout::push_spread(&self.keys, ptr);
+        SpreadLayout::push_spread(&self.values, ptr);
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        for key in self.iter().map(|(key, _)| key) {
+            // Walking `iter()` follows the recency list and so visits only
+            // occupied entries, loading each node once on the way.
+            self.values.clear_packed_at(key);
+        }
+        SpreadLayout::clear_spread(&self.head, ptr);
+        SpreadLayout::clear_spread(&self.tail, ptr);
+        SpreadLayout::clear_spread(&self.capacity, ptr);
+        SpreadLayout::clear_spread(&self.keys, ptr);
+        SpreadLayout::clear_spread(&self.values, ptr);
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A key-less storage mapping that allows to associate keys with values
+//! without the ability to iterate over them.
+
+use crate::{
+    hash::hasher::{
+        Blake2x256Hasher,
+        Hasher,
+    },
+    storage2::{
+        lazy::LazyHashMap,
+        traits::PackedLayout,
+    },
+};
+use core::borrow::Borrow;
+use ink_prelude::borrow::ToOwned;
+use ink_primitives::Key;
+
+/// A mapping between keys and values stored directly under the contract
+/// storage without any additional bookkeeping.
+///
+/// # Note
+///
+/// Unlike [`super::HashMap`] this does not track its set of keys in a
+/// [`super::Stash`] and therefore does not support iteration, a `len` or
+/// `is_empty`, or any other operation that requires knowledge of the
+/// complete set of inserted keys.
+///
+/// What it gains in return is that every operation performs exactly one
+/// hashed storage cell access: `values` stores entries directly under
+/// `hash(root_key ++ encode(key))` using the low-level [`LazyHashMap`] with
+/// no `ValueEntry` wrapper placed around each value.
+///
+/// This trade-off makes `Mapping` the better choice for the common case of
+/// contracts that only ever perform point lookups, e.g. balances or
+/// allowances, and never need to enumerate their entries.
+#[derive(Debug)]
+pub struct Mapping<K, V, H = Blake2x256Hasher>
+where
+    K: Ord + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// The values of the mapping.
+    values: LazyHashMap<K, V, H>,
+}
+
+impl<K, V, H> Default for Mapping<K, V, H>
+where
+    K: Ord + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, H> Mapping<K, V, H>
+where
+    K: Ord + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Creates a new empty mapping.
+    pub fn new() -> Self {
+        Self {
+            values: LazyHashMap::new(),
+        }
+    }
+}
+
+impl<K, V, H> Mapping<K, V, H>
+where
+    K: Ord + Eq + scale::Encode + PackedLayout,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Insert the given `value` under `key` and return the old value, if any.
+    ///
+    /// This is a single hashed storage cell write, there is no separate key
+    /// stash to update as there is in [`super::HashMap::insert`].
+    pub fn insert<Q>(&mut self, key: Q, value: V) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        self.values.put_get(&key, Some(value))
+    }
+
+    /// Removes the value under `key` and returns it, if any.
+    pub fn take<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        self.values.put_get(key, None)
+    }
+
+    /// Returns a shared reference to the value corresponding to `key`.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        self.values.get(key)
+    }
+
+    /// Returns an exclusive reference to the value corresponding to `key`.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        self.values.get_mut(key)
+    }
+
+    /// Returns `true` if there is a value stored under `key`.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        self.values.get(key).is_some()
+    }
+
+    /// Clears the value stored under `key`, if any.
+    ///
+    /// # Note
+    ///
+    /// Since a `Mapping` never tracks the set of keys it has been used with
+    /// there is no way to clear *all* of its entries at once; only removal
+    /// of individual entries that the caller still knows the key of is
+    /// supported.
+    pub fn clear<Q>(&mut self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        self.values.clear_packed_at(key);
+        let _ = self.values.put_get(key, None);
+    }
+}
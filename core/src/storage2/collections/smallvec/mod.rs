@@ -0,0 +1,254 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage vector with a statically sized inline capacity that spills into
+//! an unbounded region once that capacity is exceeded.
+
+mod iter;
+mod storage;
+
+pub use self::iter::{
+    Iter,
+    IterMut,
+};
+
+use crate::storage2::{
+    lazy::{
+        LazyArray,
+        LazyArrayLength,
+        LazyIndexMap,
+    },
+    traits::PackedLayout,
+};
+
+/// A contiguous storage vector with an inline capacity of `N` elements.
+///
+/// # Note
+///
+/// The first `N` elements are stored in a [`LazyArray`] which keeps the
+/// common case of small vectors cheap: a footprint of `1 + N` storage cells
+/// that is known at compile time. Once more than `N` elements are pushed, the
+/// additional elements spill into an unbounded [`LazyIndexMap`] so that the
+/// vector keeps growing instead of panicking or refusing further pushes,
+/// mirroring how `smallvec` transparently moves from an inline array to a
+/// heap allocation once it overflows.
+#[derive(Debug)]
+pub struct SmallVec<T, N>
+where
+    T: PackedLayout,
+    N: LazyArrayLength<T>,
+{
+    /// The number of elements currently stored in the vector.
+    ///
+    /// This may exceed the inline capacity `N`, in which case the elements
+    /// at and beyond `N` are stored in `overflow`.
+    len: u32,
+    /// The elements within the inline capacity of the vector.
+    elems: LazyArray<T, N>,
+    /// The elements beyond the inline capacity of the vector.
+    overflow: LazyIndexMap<T>,
+}
+
+impl<T, N> Default for SmallVec<T, N>
+where
+    T: PackedLayout,
+    N: LazyArrayLength<T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, N> SmallVec<T, N>
+where
+    T: PackedLayout,
+    N: LazyArrayLength<T>,
+{
+    /// Creates a new empty storage vector.
+    pub fn new() -> Self {
+        Self {
+            len: 0,
+            elems: LazyArray::new(),
+            overflow: LazyIndexMap::new(),
+        }
+    }
+
+    /// Returns the number of elements in the vector.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the inline capacity of the vector.
+    ///
+    /// # Note
+    ///
+    /// Pushing beyond this capacity does not fail. Instead, further elements
+    /// are kept in an unbounded overflow region.
+    pub fn capacity(&self) -> u32 {
+        self.elems.capacity()
+    }
+
+    /// Clears the underlying storage cells of the storage vector.
+    ///
+    /// # Note
+    ///
+    /// This completely invalidates the storage vector's invariants about
+    /// its elements and should only be called as part of the `ClearForward`
+    /// trait implementation.
+    ///
+    /// # Safety
+    ///
+    /// This operation should be exclusively called by the `ClearForward`
+    /// trait implementation of `SmallVec`.
+    fn clear_cells(&self) {
+        let capacity = self.capacity();
+        if self.elems.key().is_some() {
+            for index in 0..core::cmp::min(self.len(), capacity) {
+                self.elems.clear_packed_at(index);
+            }
+        }
+        if self.overflow.key().is_some() {
+            for index in capacity..self.len() {
+                self.overflow.clear_packed_at(index - capacity);
+            }
+        }
+    }
+}
+
+impl<T, N> SmallVec<T, N>
+where
+    T: PackedLayout,
+    N: LazyArrayLength<T>,
+{
+    /// Returns a shared reference to the element at the given index, if any.
+    pub fn get(&self, index: u32) -> Option<&T> {
+        if index >= self.len() {
+            return None
+        }
+        match index < self.capacity() {
+            true => self.elems.get(index),
+            false => self.overflow.get(index - self.capacity()),
+        }
+    }
+
+    /// Returns an exclusive reference to the element at the given index, if any.
+    pub fn get_mut(&mut self, index: u32) -> Option<&mut T> {
+        if index >= self.len() {
+            return None
+        }
+        let capacity = self.capacity();
+        match index < capacity {
+            true => self.elems.get_mut(index),
+            false => self.overflow.get_mut(index - capacity),
+        }
+    }
+
+    /// Pushes the given value onto the vector.
+    ///
+    /// # Note
+    ///
+    /// The first [`SmallVec::capacity`] elements are kept inline. Any
+    /// further elements spill into the unbounded overflow region.
+    pub fn push(&mut self, value: T) {
+        let index = self.len;
+        let capacity = self.capacity();
+        match index < capacity {
+            true => self.elems.put(index, Some(value)),
+            false => self.overflow.put(index - capacity, Some(value)),
+        }
+        self.len += 1;
+    }
+
+    /// Removes and returns the last element of the vector, if any.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None
+        }
+        let last_index = self.len - 1;
+        let capacity = self.capacity();
+        let popped = match last_index < capacity {
+            true => self.elems.put_get(last_index, None),
+            false => self.overflow.put_get(last_index - capacity, None),
+        };
+        self.len = last_index;
+        popped
+    }
+
+    /// Returns an iterator over the shared references of the elements of the storage vector.
+    pub fn iter(&self) -> Iter<T, N> {
+        Iter::new(self)
+    }
+
+    /// Returns an iterator over the exclusive references of the elements of the storage vector.
+    pub fn iter_mut(&mut self) -> IterMut<T, N> {
+        IterMut::new(self)
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, removes all elements `e` for which `f(&mut e)`
+    /// returns `false`.
+    ///
+    /// # Note
+    ///
+    /// This walks the vector once using the same bounded indexing
+    /// [`Iter`] uses, shifting retained elements down to close any gaps
+    /// left by removed ones so that the vector stays contiguous, and
+    /// clears the now-unused tail cells.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let len = self.len();
+        let capacity = self.capacity();
+        let mut new_len = 0;
+        for index in 0..len {
+            let keep = {
+                let value = match index < capacity {
+                    true => self.elems.get_mut(index),
+                    false => self.overflow.get_mut(index - capacity),
+                }
+                .expect("index is within bounds");
+                f(value)
+            };
+            if !keep {
+                continue
+            }
+            if new_len != index {
+                let value = match index < capacity {
+                    true => self.elems.put_get(index, None),
+                    false => self.overflow.put_get(index - capacity, None),
+                }
+                .expect("index is within bounds");
+                match new_len < capacity {
+                    true => self.elems.put(new_len, Some(value)),
+                    false => self.overflow.put(new_len - capacity, Some(value)),
+                }
+            }
+            new_len += 1;
+        }
+        for index in new_len..len {
+            match index < capacity {
+                true => self.elems.clear_packed_at(index),
+                false => self.overflow.clear_packed_at(index - capacity),
+            }
+        }
+        self.len = new_len;
+    }
+}
@@ -17,6 +17,7 @@ use crate::storage2::{
     lazy::{
         LazyArray,
         LazyArrayLength,
+        LazyIndexMap,
     },
     traits2::{
         KeyPtr as KeyPtr2,
@@ -43,18 +44,21 @@ where
         Self {
             len: SpreadLayout::pull_spread(ptr),
             elems: SpreadLayout::pull_spread(ptr),
+            overflow: PullForward::pull_forward(&mut KeyPtr::from(ptr.next_for::<LazyIndexMap<T>>())),
         }
     }
 
     fn push_spread(&self, ptr: &mut KeyPtr2) {
         SpreadLayout::push_spread(&self.len, ptr);
         SpreadLayout::push_spread(&self.elems, ptr);
+        PushForward::push_forward(&self.overflow, &mut KeyPtr::from(ptr.next_for::<LazyIndexMap<T>>()));
     }
 
     fn clear_spread(&self, ptr: &mut KeyPtr2) {
         self.clear_cells();
         SpreadLayout::clear_spread(&self.len, ptr);
         SpreadLayout::clear_spread(&self.elems, ptr);
+        let _ = ptr.next_for::<LazyIndexMap<T>>();
     }
 }
 
@@ -75,6 +79,7 @@ where
         Self {
             len: PullForward::pull_forward(ptr),
             elems: PullForward::pull_forward(ptr),
+            overflow: PullForward::pull_forward(ptr),
         }
     }
 }
@@ -87,6 +92,7 @@ where
     fn push_forward(&self, ptr: &mut KeyPtr) {
         PushForward::push_forward(&self.len, ptr);
         PushForward::push_forward(&self.elems, ptr);
+        PushForward::push_forward(&self.overflow, ptr);
     }
 }
 
@@ -97,19 +103,32 @@ where
 {
     fn clear_forward(&self, ptr: &mut KeyPtr) {
         ClearForward::clear_forward(&self.len, ptr);
-        // ClearForward::clear_forward(&self.elems, ptr);
-        if self.elems.key().is_none() {
-            return
+        if self.elems.key().is_some() {
+            for (index, elem) in self.iter().take(self.capacity() as usize).enumerate() {
+                <T as ClearForward>::clear_forward(
+                    elem,
+                    &mut KeyPtr::from(
+                        self.elems
+                            .key_at(index as u32)
+                            .expect("expected a key mapping since self.elems.key() is some"),
+                    ),
+                )
+            }
         }
-        for (index, elem) in self.iter().enumerate() {
-            <T as ClearForward>::clear_forward(
-                elem,
-                &mut KeyPtr::from(
-                    self.elems
-                        .key_at(index as u32)
-                        .expect("expected a key mapping since self.elems.key() is some"),
-                ),
-            )
+        if self.overflow.key().is_some() {
+            for index in self.capacity()..self.len() {
+                let elem = self
+                    .get(index)
+                    .expect("expected an element since index is within bounds");
+                <T as ClearForward>::clear_forward(
+                    elem,
+                    &mut KeyPtr::from(
+                        self.overflow
+                            .key_at(index - self.capacity())
+                            .expect("expected a key mapping since self.overflow.key() is some"),
+                    ),
+                )
+            }
         }
     }
 }
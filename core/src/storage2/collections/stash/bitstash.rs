@@ -0,0 +1,209 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A compact bit-vector based free-slot allocator.
+//!
+//! Unlike [`super::Stash`], which threads vacant slots through a linked list
+//! stored inline in each entry, a [`BitStash`] records occupied/vacant state
+//! as a single bit per slot, packed 64 bits to a storage cell. A coarse
+//! population count kept alongside every 256 indices lets [`BitStash::take`]
+//! skip a fully occupied range in O(1) instead of scanning its bits.
+
+use crate::storage2::{
+    lazy::LazyIndexMap,
+    traits::{
+        KeyPtr,
+        SpreadLayout,
+    },
+};
+
+/// The number of occupancy bits packed into a single storage cell.
+const BITS_PER_CELL: u32 = 64;
+
+/// The number of indices tracked by a single population count.
+///
+/// # Note
+///
+/// Chosen to span a handful of occupancy cells so that [`BitStash::take`]
+/// only has to consult one `u32` count, instead of scanning
+/// `INDICES_PER_COUNT` bits, to determine whether an entire range is full.
+const INDICES_PER_COUNT: u32 = BITS_PER_CELL * 4;
+
+/// A free-slot allocator backed by a packed bit-vector of occupied slots.
+///
+/// # Note
+///
+/// This is meant to back index allocation for collections such as
+/// [`super::Stash`]'s key stash: [`BitStash::take`] returns the first vacant
+/// index (allocating a new cell lazily if every existing cell is full), and
+/// [`BitStash::free`] gives an index back with a single bit flip.
+#[derive(Debug)]
+pub struct BitStash {
+    /// The number of occupied slots.
+    len: u32,
+    /// The occupied/vacant bits, 64 slots per storage cell.
+    bits: LazyIndexMap<u64>,
+    /// The number of occupied bits among every [`INDICES_PER_COUNT`] indices.
+    ///
+    /// Caching this count is what allows [`BitStash::take`] to skip a fully
+    /// occupied range without loading and scanning its bits.
+    counts: LazyIndexMap<u32>,
+}
+
+impl SpreadLayout for BitStash {
+    const FOOTPRINT: u64 = 1
+        + <LazyIndexMap<u64> as SpreadLayout>::FOOTPRINT
+        + <LazyIndexMap<u32> as SpreadLayout>::FOOTPRINT;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        Self {
+            len: SpreadLayout::pull_spread(ptr),
+            bits: SpreadLayout::pull_spread(ptr),
+            counts: SpreadLayout::pull_spread(ptr),
+        }
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        SpreadLayout::push_spread(&self.len, ptr);
+        SpreadLayout::push_spread(&self.bits, ptr);
+        SpreadLayout::push_spread(&self.counts, ptr);
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        SpreadLayout::clear_spread(&self.len, ptr);
+        SpreadLayout::clear_spread(&self.bits, ptr);
+        SpreadLayout::clear_spread(&self.counts, ptr);
+    }
+}
+
+impl Default for BitStash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BitStash {
+    /// Creates a new empty bit stash.
+    pub fn new() -> Self {
+        Self {
+            len: 0,
+            bits: LazyIndexMap::new(),
+            counts: LazyIndexMap::new(),
+        }
+    }
+
+    /// Returns the number of occupied slots.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if the bit stash has no occupied slots.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn cell_index(index: u32) -> u32 {
+        index / BITS_PER_CELL
+    }
+
+    fn bit_index(index: u32) -> u32 {
+        index % BITS_PER_CELL
+    }
+
+    fn count_index(index: u32) -> u32 {
+        index / INDICES_PER_COUNT
+    }
+
+    /// Returns `true` if the slot at `index` is occupied.
+    pub fn is_set(&self, index: u32) -> bool {
+        self.bits
+            .get(Self::cell_index(index))
+            .map(|cell| (*cell >> Self::bit_index(index)) & 1 == 1)
+            .unwrap_or(false)
+    }
+
+    /// Sets or clears the bit at `index`, updating the occupied count and
+    /// the index's population count in lockstep.
+    fn set(&mut self, index: u32, value: bool) {
+        let cell_index = Self::cell_index(index);
+        let bit_index = Self::bit_index(index);
+        let mut cell = self.bits.get(cell_index).copied().unwrap_or(0);
+        let was_set = (cell >> bit_index) & 1 == 1;
+        if value {
+            cell |= 1 << bit_index;
+        } else {
+            cell &= !(1 << bit_index);
+        }
+        self.bits.put(cell_index, Some(cell));
+        if was_set == value {
+            return
+        }
+        let count_index = Self::count_index(index);
+        let mut count = self.counts.get(count_index).copied().unwrap_or(0);
+        if value {
+            count += 1;
+        } else {
+            count -= 1;
+        }
+        self.counts.put(count_index, Some(count));
+        if value {
+            self.len += 1;
+        } else {
+            self.len -= 1;
+        }
+    }
+
+    /// Marks `index` as occupied.
+    ///
+    /// # Panics
+    ///
+    /// If the slot at `index` was already occupied.
+    pub fn put(&mut self, index: u32) {
+        assert!(!self.is_set(index), "index is already occupied");
+        self.set(index, true);
+    }
+
+    /// Allocates and returns the first vacant index, marking it occupied.
+    ///
+    /// Skips whole ranges of [`INDICES_PER_COUNT`] indices at a time as long
+    /// as their population count shows them to be fully occupied, so only
+    /// the cells making up the range that is ultimately allocated from are
+    /// ever loaded.
+    pub fn take(&mut self) -> u32 {
+        let mut count_index = 0;
+        loop {
+            let count = self.counts.get(count_index).copied().unwrap_or(0);
+            if count < INDICES_PER_COUNT {
+                let base = count_index * INDICES_PER_COUNT;
+                for index in base..base + INDICES_PER_COUNT {
+                    if !self.is_set(index) {
+                        self.set(index, true);
+                        return index
+                    }
+                }
+            }
+            count_index += 1;
+        }
+    }
+
+    /// Frees the slot at `index`, marking it vacant again.
+    ///
+    /// # Panics
+    ///
+    /// If the slot at `index` was not occupied.
+    pub fn free(&mut self, index: u32) {
+        assert!(self.is_set(index), "index was not occupied");
+        self.set(index, false);
+    }
+}
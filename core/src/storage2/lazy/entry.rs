@@ -0,0 +1,164 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The low-level entry abstraction shared by the lazy storage caches
+//! ([`super::LazyCell`], [`super::LazyIndexMap`], [`super::LazyHashMap`]).
+//!
+//! Every lazily loaded value is cached behind one of these entries so that
+//! a cache can tell, once it needs to flush, whether the value was ever
+//! written to or merely read.
+
+use crate::storage2::traits::{
+    push_packed_root,
+    PackedLayout,
+};
+use ink_primitives::Key;
+
+/// An error that can occur while lazily loading an entry from the contract
+/// storage.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StorageError {
+    /// The encoded contract storage cell could not be decoded into the type
+    /// expected by the lazy storage abstraction, e.g. because the storage
+    /// layout of the contract changed since the cell was written.
+    Decode,
+}
+
+/// The dirty state of a cached entry.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EntryState {
+    /// The entry's value was freshly loaded from storage, or has not been
+    /// touched since the last flush, and therefore matches storage exactly.
+    Preserved,
+    /// The entry's value was mutated since it was loaded, or since the last
+    /// flush, and must be written back to storage.
+    Mutated,
+    /// The entry's value failed to decode from storage and the entry must
+    /// not be treated as absent (`None`): reading it again should surface
+    /// the same error instead of silently masking it as an empty cell.
+    Poisoned(StorageError),
+}
+
+impl EntryState {
+    /// Returns `true` if the entry must be written back to storage.
+    pub fn requires_flush(self) -> bool {
+        matches!(self, Self::Mutated)
+    }
+}
+
+/// An entry within a lazy storage cache, holding the cached value of a
+/// single storage cell alongside its dirty state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry<V> {
+    /// The cached value, `None` if the cell is (or will become) empty.
+    value: Option<V>,
+    /// The entry's dirty state.
+    state: EntryState,
+}
+
+/// An alias for [`Entry`] for callers that expose their own public `Entry`
+/// API (e.g. a BTreeMap-style `entry()` method) and would otherwise clash
+/// with this low-level cache entry.
+pub use Entry as InternalEntry;
+
+impl<V> Entry<V> {
+    /// Creates a new entry with the given value and dirty state.
+    pub fn new(value: Option<V>, state: EntryState) -> Self {
+        Self { value, state }
+    }
+
+    /// Returns a shared reference to the cached value.
+    pub fn value(&self) -> &Option<V> {
+        &self.value
+    }
+
+    /// Returns the entry's dirty state.
+    pub fn state(&self) -> EntryState {
+        self.state
+    }
+
+    /// Returns a shared reference to the cached value, or the original
+    /// decode error if this entry is [`EntryState::Poisoned`].
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Entry::value`] this never treats a poisoned entry as `None`,
+    /// so a previous decode failure can never be silently masked by a
+    /// caller that only inspects the cached value.
+    pub fn try_value(&self) -> Result<&Option<V>, StorageError> {
+        match self.state {
+            EntryState::Poisoned(error) => Err(error),
+            EntryState::Preserved | EntryState::Mutated => Ok(&self.value),
+        }
+    }
+
+    /// Returns an exclusive reference to the cached value.
+    pub fn value_mut(&mut self) -> &mut Option<V> {
+        &mut self.value
+    }
+
+    /// Takes the cached value out of the entry, consuming it.
+    pub fn into_value(self) -> Option<V> {
+        self.value
+    }
+
+    /// Replaces the entry's dirty state, returning the old one.
+    pub fn replace_state(&mut self, new_state: EntryState) -> EntryState {
+        core::mem::replace(&mut self.state, new_state)
+    }
+
+    /// Replaces the cached value, marking the entry as mutated, and returns
+    /// the old value.
+    ///
+    /// Putting `None` into an entry whose value is already `None` changes
+    /// nothing and leaves the entry's dirty state as it was.
+    ///
+    /// # Panics
+    ///
+    /// If the entry is [`EntryState::Poisoned`], since the "old value" this
+    /// would otherwise return is not actually `None` but undecodable.
+    pub fn put(&mut self, new_value: Option<V>) -> Option<V> {
+        if let EntryState::Poisoned(error) = self.state {
+            panic!("encountered poisoned storage entry: {:?}", error)
+        }
+        if new_value.is_none() && self.value.is_none() {
+            return None
+        }
+        self.replace_state(EntryState::Mutated);
+        core::mem::replace(&mut self.value, new_value)
+    }
+}
+
+impl<V> Entry<V>
+where
+    V: PackedLayout,
+{
+    /// Writes the entry's cached value to the given root key.
+    ///
+    /// # Panics
+    ///
+    /// If the entry is [`EntryState::Poisoned`]: its value was never
+    /// successfully decoded, so there is nothing sound to write back, and
+    /// silently clearing the cell would permanently discard whatever is
+    /// actually stored there.
+    pub fn push_packed_root(&self, root_key: &Key) {
+        if let EntryState::Poisoned(error) = self.state {
+            panic!("cannot push a poisoned storage entry: {:?}", error)
+        }
+        match &self.value {
+            Some(value) => push_packed_root(value, root_key),
+            None => crate::env::clear_contract_storage(*root_key),
+        }
+    }
+}
@@ -0,0 +1,405 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::storage2::{
+    traits2::{
+        clear_packed_root,
+        pull_packed_root_opt,
+        KeyPtr as KeyPtr2,
+        PackedLayout,
+        SpreadLayout,
+    },
+    KeyPtr,
+    PullForward,
+    PushForward,
+};
+use core::{
+    cell::UnsafeCell,
+    fmt,
+    fmt::Debug,
+    marker::PhantomData,
+    ptr::NonNull,
+};
+use generic_array::typenum::Unsigned;
+use ink_prelude::{
+    boxed::Box,
+    collections::BTreeMap,
+};
+use ink_primitives::Key;
+
+/// The index type used in the lazy storage array.
+pub type Index = u32;
+
+/// Types that are allowed to be used as the statically known capacity of a
+/// [`LazyArray`].
+///
+/// # Note
+///
+/// This is just a convenience trait alias over [`generic_array`]'s
+/// [`Unsigned`] so that [`LazyArray`]'s capacity can be a type-level number
+/// the same way [`crate::storage2::collections::SmallVec`]'s inline capacity
+/// is.
+pub trait LazyArrayLength<T>: Unsigned {}
+impl<T, N> LazyArrayLength<T> for N where N: Unsigned {}
+
+/// A lazy storage array that spans over a limited range of `N` storage cells.
+///
+/// # Note
+///
+/// This is mainly used as a low-level storage primitive by
+/// [`crate::storage2::collections::SmallVec`] in order to manage the
+/// contract storage for its inline elements.
+pub struct LazyArray<T, N>
+where
+    N: LazyArrayLength<T>,
+{
+    /// The offset key for the chunk of cells.
+    ///
+    /// If the lazy array has been initialized during contract initialization
+    /// the key will be `None` since there won't be a storage region associated
+    /// to the lazy array which prevents it from lazily loading elements. This,
+    /// however, is only checked at contract runtime. We might incorporate
+    /// compile-time checks for this particular use case later on.
+    key: Option<Key>,
+    /// The subset of currently cached entries of the lazy storage array.
+    ///
+    /// An entry is cached as soon as it is loaded or written.
+    cached_entries: UnsafeCell<EntryMap<T>>,
+    /// The statically known capacity of the lazy array.
+    capacity: PhantomData<fn() -> N>,
+}
+
+/// The map for the contract storage entries.
+///
+/// # Note
+///
+/// We keep the whole entry in a `Box<T>` in order to prevent pointer
+/// invalidation upon updating the cache through `&self` methods as in
+/// [`LazyArray::get`].
+pub type EntryMap<T> = BTreeMap<Index, Box<Entry<T>>>;
+
+use super::{
+    Entry,
+    EntryState,
+};
+
+struct DebugEntryMap<'a, T>(&'a UnsafeCell<EntryMap<T>>);
+
+impl<'a, T> Debug for DebugEntryMap<'a, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(unsafe { &*self.0.get() }.iter())
+            .finish()
+    }
+}
+
+impl<T, N> Debug for LazyArray<T, N>
+where
+    T: Debug,
+    N: LazyArrayLength<T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LazyArray")
+            .field("key", &self.key)
+            .field("cached_entries", &DebugEntryMap(&self.cached_entries))
+            .finish()
+    }
+}
+
+impl<T, N> Default for LazyArray<T, N>
+where
+    N: LazyArrayLength<T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, N> LazyArray<T, N>
+where
+    N: LazyArrayLength<T>,
+{
+    /// Creates a new empty lazy array.
+    ///
+    /// # Note
+    ///
+    /// A lazy array created this way cannot be used to load from the contract storage.
+    /// All operations that directly or indirectly load from storage will panic.
+    pub fn new() -> Self {
+        Self {
+            key: None,
+            cached_entries: UnsafeCell::new(EntryMap::new()),
+            capacity: PhantomData,
+        }
+    }
+
+    /// Creates a new empty lazy array positioned at the given key.
+    ///
+    /// # Note
+    ///
+    /// This constructor is private and should never need to be called from
+    /// outside this module. It is used to construct a lazy array from a key
+    /// that is only useful upon a contract call. Use [`LazyArray::new`] for
+    /// construction during contract initialization.
+    fn lazy(key: Key) -> Self {
+        Self {
+            key: Some(key),
+            cached_entries: UnsafeCell::new(EntryMap::new()),
+            capacity: PhantomData,
+        }
+    }
+
+    /// Returns the offset key of the lazy array if any.
+    pub fn key(&self) -> Option<&Key> {
+        self.key.as_ref()
+    }
+
+    /// Returns the statically known capacity of the lazy array.
+    pub fn capacity(&self) -> u32 {
+        <N as Unsigned>::U64 as u32
+    }
+}
+
+impl<T, N> SpreadLayout for LazyArray<T, N>
+where
+    T: PackedLayout,
+    N: LazyArrayLength<T>,
+{
+    const FOOTPRINT: u64 = <N as Unsigned>::U64;
+
+    fn pull_spread(ptr: &mut KeyPtr2) -> Self {
+        Self::lazy(ptr.next_for::<Self>())
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr2) {
+        let offset_key = ptr.next_for::<Self>();
+        for (&index, entry) in self.entries().iter() {
+            let root_key = offset_key + index;
+            entry.push_packed_root(&root_key);
+        }
+    }
+
+    #[inline]
+    fn clear_spread(&self, _ptr: &mut KeyPtr2) {
+        // Low-level lazy abstractions won't perform automated clean-up since
+        // they generally are not aware of their entire set of associated
+        // elements. The high-level abstractions that build upon them are
+        // responsible for cleaning up.
+    }
+}
+
+impl<T, N> PullForward for LazyArray<T, N>
+where
+    N: LazyArrayLength<T>,
+{
+    fn pull_forward(ptr: &mut KeyPtr) -> Self {
+        Self::lazy(ptr.next_for::<Self>())
+    }
+}
+
+impl<T, N> PushForward for LazyArray<T, N>
+where
+    T: PackedLayout,
+    N: LazyArrayLength<T>,
+{
+    fn push_forward(&self, ptr: &mut KeyPtr) {
+        let offset_key = ptr.next_for::<Self>();
+        for (&index, entry) in self.entries().iter() {
+            let root_key = offset_key + index;
+            entry.push_packed_root(&root_key);
+        }
+    }
+}
+
+impl<T, N> LazyArray<T, N>
+where
+    N: LazyArrayLength<T>,
+{
+    /// Returns a shared reference to the underlying entries.
+    fn entries(&self) -> &EntryMap<T> {
+        // SAFETY: It is safe to return a `&` reference from a `&self` receiver.
+        unsafe { &*self.cached_entries.get() }
+    }
+
+    /// Puts the new value at the given index.
+    ///
+    /// # Note
+    ///
+    /// - Use [`LazyArray::put`]`(None)` in order to remove an element.
+    /// - Prefer this method over [`LazyArray::put_get`] if you are not interested
+    ///   in the old value of the same cell index.
+    ///
+    /// # Panics
+    ///
+    /// - If the lazy array is in an invalid state that forbids interaction.
+    ///
+    /// The old element at the given index is never loaded or decoded.
+    pub fn put(&mut self, index: Index, new_value: Option<T>) {
+        // SAFETY: It is safe to mutate the cache through a `&mut self` receiver.
+        unsafe { &mut *self.cached_entries.get() }
+            .insert(index, Box::new(Entry::new(new_value, EntryState::Mutated)));
+    }
+}
+
+impl<T, N> LazyArray<T, N>
+where
+    T: PackedLayout,
+    N: LazyArrayLength<T>,
+{
+    /// Returns an offset key for the given index.
+    pub fn key_at(&self, index: Index) -> Option<Key> {
+        let key = self.key?;
+        let offset_key = key + index as u64;
+        Some(offset_key)
+    }
+
+    /// Clears the underlying storage of the entry at the given index.
+    ///
+    /// # Safety
+    ///
+    /// For performance reasons this does not synchronize the lazy array's
+    /// memory-side cache which invalidates future accesses to the cleared
+    /// entry. Care should be taken when using this API.
+    ///
+    /// The general use of this API is to streamline `Drop` implementations of
+    /// high-level abstractions that build upon this low-level data structure.
+    pub fn clear_packed_at(&self, index: Index) {
+        let root_key = self.key_at(index).expect("cannot clear in lazy state");
+        if <T as SpreadLayout>::REQUIRES_DEEP_CLEAN_UP {
+            let entity = self.get(index).expect("cannot clear a non existing entity");
+            clear_packed_root::<T>(&entity, &root_key);
+        } else {
+            crate::env::clear_contract_storage(root_key);
+        }
+    }
+
+    /// Lazily loads the value at the given index.
+    ///
+    /// # Note
+    ///
+    /// Only loads a value if `key` is set and if the value has not been loaded yet.
+    /// Returns the freshly loaded or already loaded entry of the value.
+    ///
+    /// # Safety
+    ///
+    /// This is an `unsafe` operation because it has a `&self` receiver but returns
+    /// a `*mut Entry<T>` pointer that allows for exclusive access. This is safe
+    /// within internal use only and should never be given outside of the lazy
+    /// entity for public `&self` methods.
+    unsafe fn lazily_load(&self, index: Index) -> NonNull<Entry<T>> {
+        // SAFETY: See `LazyIndexMap::lazily_load` for the safety argument for
+        //         why mutating the cache through a `&self` receiver is safe.
+        #[allow(unused_unsafe)]
+        let cached_entries = unsafe { &mut *self.cached_entries.get() };
+        use ink_prelude::collections::btree_map::Entry as BTreeMapEntry;
+        match cached_entries.entry(index) {
+            BTreeMapEntry::Occupied(occupied) => {
+                NonNull::from(&mut **occupied.into_mut())
+            }
+            BTreeMapEntry::Vacant(vacant) => {
+                let value = self
+                    .key_at(index)
+                    .map(|key| pull_packed_root_opt::<T>(&key))
+                    .unwrap_or(None);
+                NonNull::from(
+                    &mut **vacant
+                        .insert(Box::new(Entry::new(value, EntryState::Preserved))),
+                )
+            }
+        }
+    }
+
+    /// Lazily loads the value at the given index.
+    ///
+    /// # Panics
+    ///
+    /// - If the lazy array is in an invalid state that forbids interaction.
+    /// - If the lazy array is not in a state that allows lazy loading.
+    fn lazily_load_mut(&mut self, index: Index) -> &mut Entry<T> {
+        // SAFETY: Returning a `&mut Entry<T>` is safe because entities inside the
+        //         cache are stored within a `Box` to not invalidate references into
+        //         them upon operating on the outer cache.
+        unsafe { &mut *self.lazily_load(index).as_ptr() }
+    }
+
+    /// Returns a shared reference to the element at the given index if any.
+    ///
+    /// # Panics
+    ///
+    /// - If the lazy array is in an invalid state that forbids interaction.
+    /// - If the decoding of the element at the given index failed.
+    pub fn get(&self, index: Index) -> Option<&T> {
+        // SAFETY: Dereferencing the `*mut T` pointer into a `&T` is safe
+        //         since this method's receiver is `&self` so we do not
+        //         leak non-shared references to the outside.
+        unsafe { &*self.lazily_load(index).as_ptr() }.value().into()
+    }
+
+    /// Returns an exclusive reference to the element at the given index if any.
+    ///
+    /// # Panics
+    ///
+    /// - If the lazy array is in an invalid state that forbids interaction.
+    /// - If the decoding of the element at the given index failed.
+    pub fn get_mut(&mut self, index: Index) -> Option<&mut T> {
+        self.lazily_load_mut(index).value_mut().into()
+    }
+
+    /// Puts the new value at the given index and returns the old value if any.
+    ///
+    /// # Note
+    ///
+    /// - Use [`LazyArray::put_get`]`(None)` in order to remove an element
+    ///   and retrieve the old element back.
+    ///
+    /// # Panics
+    ///
+    /// - If the lazy array is in an invalid state that forbids interaction.
+    /// - If the decoding of the old element at the given index failed.
+    pub fn put_get(&mut self, index: Index, new_value: Option<T>) -> Option<T> {
+        self.lazily_load_mut(index).put(new_value)
+    }
+
+    /// Swaps the values at indices `x` and `y`.
+    ///
+    /// This operation tries to be as efficient as possible and reuse allocations.
+    ///
+    /// # Panics
+    ///
+    /// - If the lazy array is in an invalid state that forbids interaction.
+    /// - If the decoding of one of the elements failed.
+    pub fn swap(&mut self, x: Index, y: Index) {
+        if x == y {
+            // Bail out early if both indices are the same.
+            return
+        }
+        let (loaded_x, loaded_y) =
+            // SAFETY: The loaded `x` and `y` entries are distinct from each
+            //         other guaranteed by the previous check.
+            unsafe { (
+                &mut *self.lazily_load(x).as_ptr(),
+                &mut *self.lazily_load(y).as_ptr(),
+            ) };
+        if loaded_x.value().is_none() && loaded_y.value().is_none() {
+            // Bail out since nothing has to be swapped if both values are `None`.
+            return
+        }
+        loaded_x.replace_state(EntryState::Mutated);
+        loaded_y.replace_state(EntryState::Mutated);
+        core::mem::swap(loaded_x.value_mut(), loaded_y.value_mut());
+    }
+}
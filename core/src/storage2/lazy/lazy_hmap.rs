@@ -32,15 +32,11 @@ use crate::{
     },
 };
 use core::{
-    borrow::Borrow,
     cell::RefCell,
-    cmp::{
-        Eq,
-        Ord,
-    },
     fmt,
     fmt::Debug,
     iter::FromIterator,
+    marker::PhantomData,
     ptr::NonNull,
 };
 use ink_prelude::{
@@ -55,10 +51,16 @@ use ink_primitives::Key;
 ///
 /// # Note
 ///
+/// Entries are keyed by the SCALE-encoded bytes of their logical key instead
+/// of the key itself. This lets every lookup probe the cache through a
+/// borrowed, already-encoded key without ever needing to own or clone the
+/// original key, sidestepping the lack of the unstable raw entry API for
+/// `BTreeMap` (see rust-lang/rust#56167).
+///
 /// We keep the whole entry in a `Box<T>` in order to prevent pointer
 /// invalidation upon updating the cache through `&self` methods as in
 /// [`LazyMap::get`].
-pub type EntryMap<K, V> = BTreeMap<K, Box<InternalEntry<V>>>;
+pub type EntryMap<V> = BTreeMap<Vec<u8>, Box<InternalEntry<V>>>;
 
 /// A lazy storage mapping that stores entries under their SCALE encoded key hashes.
 ///
@@ -78,19 +80,21 @@ pub struct LazyHashMap<K, V, H> {
     /// so that all lazy hash map instances store equal entries at different
     /// locations of the contract storage and avoid collissions.
     key: Option<Key>,
-    /// The currently cached entries of the lazy storage mapping.
+    /// The currently cached entries of the lazy storage mapping, keyed by the
+    /// SCALE-encoded bytes of their logical key.
     ///
     /// This normally only represents a subset of the total set of elements.
     /// An entry is cached as soon as it is loaded or written.
-    cached_entries: CacheCell<EntryMap<K, V>>,
+    cached_entries: CacheCell<EntryMap<V>>,
     /// The used hash builder.
     hash_builder: RefCell<HashBuilder<H, Vec<u8>>>,
+    /// Marks the logical key type associated with the encoded cache above.
+    key_marker: PhantomData<fn() -> K>,
 }
 
-/// A vacant entry with previous and next vacant indices.
+/// An occupied entry of a [`LazyHashMap`].
 pub struct OccupiedEntry<'a, K, V, H>
 where
-    K: Clone,
     H: Hasher,
     Key: From<<H as Hasher>::Output>,
 {
@@ -100,11 +104,9 @@ where
     key: K,
 }
 
-/// A vacant entry with previous and next vacant indices.
+/// A vacant entry of a [`LazyHashMap`].
 pub struct VacantEntry<'a, K, V, H>
 where
-    K: Ord + Clone + PackedLayout,
-    V: PackedLayout,
     H: Hasher,
     Key: From<<H as Hasher>::Output>,
 {
@@ -114,14 +116,62 @@ where
     key: K,
 }
 
+/// An occupied entry of a [`LazyHashMap`], reached through
+/// [`LazyHashMap::entry_ref`].
+///
+/// Unlike [`OccupiedEntry`] this never owns `K`, only the SCALE-encoded bytes
+/// that were already needed to probe the cache.
+pub struct OccupiedEntryRef<'a, K, V, H>
+where
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// A reference to the used `HashMap` instance.
+    base: &'a mut LazyHashMap<K, V, H>,
+    /// The SCALE-encoded bytes of the key stored in this entry.
+    encoded_key: Vec<u8>,
+}
+
+/// A vacant entry of a [`LazyHashMap`], reached through
+/// [`LazyHashMap::entry_ref`].
+///
+/// Holds on to the original borrowed key instead of an owned `K`; an owned
+/// `K` is only materialized, via `ToOwned::to_owned`, if [`VacantEntryRef::insert`]
+/// actually fires.
+pub struct VacantEntryRef<'a, 'b, K, Q, V, H>
+where
+    Q: 'b + ?Sized,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// A reference to the used `HashMap` instance.
+    base: &'a mut LazyHashMap<K, V, H>,
+    /// The borrowed key that would be used to construct an owned key upon insertion.
+    key: &'b Q,
+    /// The SCALE-encoded bytes of `key`.
+    encoded_key: Vec<u8>,
+}
+
+/// An entry of a [`LazyHashMap`] reached through a borrowed key via
+/// [`LazyHashMap::entry_ref`].
+pub enum EntryRef<'a, 'b, K, Q, V, H>
+where
+    Q: 'b + ?Sized,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// A vacant entry that holds on to the originally queried borrowed key.
+    Vacant(VacantEntryRef<'a, 'b, K, Q, V, H>),
+    /// An occupied entry that holds the value.
+    Occupied(OccupiedEntryRef<'a, K, V, H>),
+}
+
 /// An entry within the stash.
 ///
 /// The vacant entries within a storage stash form a doubly linked list of
 /// vacant entries that is used to quickly re-use their vacant storage.
 pub enum Entry<'a, K: 'a, V: 'a, H>
 where
-    K: Ord + Clone + PackedLayout,
-    V: PackedLayout,
     H: Hasher,
     Key: From<<H as Hasher>::Output>,
 {
@@ -131,11 +181,10 @@ where
     Occupied(OccupiedEntry<'a, K, V, H>),
 }
 
-struct DebugEntryMap<'a, K, V>(&'a CacheCell<EntryMap<K, V>>);
+struct DebugEntryMap<'a, V>(&'a CacheCell<EntryMap<V>>);
 
-impl<'a, K, V> Debug for DebugEntryMap<'a, K, V>
+impl<'a, V> Debug for DebugEntryMap<'a, V>
 where
-    K: Debug,
     V: Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -145,7 +194,6 @@ where
 
 impl<K, V, H> Debug for LazyHashMap<K, V, H>
 where
-    K: Debug,
     V: Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -170,20 +218,22 @@ fn debug_impl_works() {
     hmap.put('A', Some(1));
     hmap.put('B', Some(2));
     hmap.put('C', None);
+    // The cache is keyed on each key's SCALE-encoded bytes rather than the
+    // key itself, so `char` keys show up as their 4-byte `u32` encoding.
     assert_eq!(
         format!("{:?}", &hmap),
         "LazyHashMap { \
             key: None, \
             cached_entries: {\
-                'A': Entry { \
+                [65, 0, 0, 0]: Entry { \
                     value: Some(1), \
                     state: Mutated \
                 }, \
-                'B': Entry { \
+                [66, 0, 0, 0]: Entry { \
                     value: Some(2), \
                     state: Mutated \
                 }, \
-                'C': Entry { \
+                [67, 0, 0, 0]: Entry { \
                     value: None, \
                     state: Mutated \
                 }\
@@ -209,7 +259,6 @@ const _: () = {
 
     impl<K, V, H> StorageLayout for LazyHashMap<K, V, H>
     where
-        K: Ord + scale::Encode,
         V: TypeInfo + 'static,
         H: Hasher + LayoutCryptoHasher,
         Key: From<<H as Hasher>::Output>,
@@ -232,7 +281,7 @@ const _: () = {
 
 impl<K, V, H> SpreadLayout for LazyHashMap<K, V, H>
 where
-    K: Ord + scale::Encode,
+    K: scale::Encode,
     V: PackedLayout,
     H: Hasher,
     Key: From<<H as Hasher>::Output>,
@@ -245,9 +294,25 @@ where
 
     fn push_spread(&self, ptr: &mut KeyPtr) {
         let offset_key = ExtKeyPtr::next_for::<Self>(ptr);
-        for (index, entry) in self.entries().iter() {
-            let root_key = self.to_offset_key(&offset_key, index);
+        // SAFETY: No other reference into the cache is alive while this
+        //         loop runs, so taking `&mut` here from a `&self` receiver
+        //         does not create a conflicting alias.
+        for (encoded_key, entry) in unsafe { self.entries_mut_via_ref() }.iter_mut() {
+            if !entry.state().requires_flush() {
+                // Only entries that were actually mutated need to be
+                // written back: re-pushing an untouched, merely-read entry
+                // would just rewrite storage with the value it already
+                // holds.
+                continue
+            }
+            // The cache is already keyed on the SCALE-encoded key bytes, so
+            // we hash those bytes in directly rather than re-encoding them a
+            // second time through `to_offset_key`, which would otherwise
+            // wrap them in an extra length-prefixed `Vec<u8>` encoding and
+            // produce a different (and wrong) storage key.
+            let root_key = self.to_offset_key_bytes(&offset_key, encoded_key);
             entry.push_packed_root(&root_key);
+            entry.replace_state(EntryState::Preserved);
         }
     }
 
@@ -271,7 +336,7 @@ where
 
 impl<K, V, H> Default for LazyHashMap<K, V, H>
 where
-    K: Ord,
+    K: scale::Encode,
 {
     fn default() -> Self {
         Self::new()
@@ -280,7 +345,7 @@ where
 
 impl<K, V, H> FromIterator<(K, V)> for LazyHashMap<K, V, H>
 where
-    K: Ord + Clone + PackedLayout,
+    K: scale::Encode,
     V: PackedLayout,
     H: Hasher,
     Key: From<<H as Hasher>::Output>,
@@ -297,7 +362,7 @@ where
 
 impl<K, V, H> Extend<(K, V)> for LazyHashMap<K, V, H>
 where
-    K: Ord + Clone + PackedLayout,
+    K: scale::Encode,
     V: PackedLayout,
     H: Hasher,
     Key: From<<H as Hasher>::Output>,
@@ -306,15 +371,37 @@ where
     where
         I: IntoIterator<Item = (K, V)>,
     {
+        // `insert_unique_unchecked` is sound to use here for the same reason
+        // `put` was before it: both unconditionally overwrite whatever was
+        // cached under `key`, so a duplicate key in `iter` still behaves as
+        // a plain last-write-wins overwrite, not undefined behavior.
         for (key, value) in iter {
-            self.put(key, Some(value));
+            self.insert_unique_unchecked(key, value);
         }
     }
 }
 
+impl<'a, K, V, H> Extend<(&'a K, &'a V)> for LazyHashMap<K, V, H>
+where
+    K: scale::Encode + Clone,
+    V: PackedLayout + Clone,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (&'a K, &'a V)>,
+    {
+        self.extend(
+            iter.into_iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        )
+    }
+}
+
 impl<K, V, H> LazyHashMap<K, V, H>
 where
-    K: Ord,
+    K: scale::Encode,
 {
     /// Creates a new empty lazy hash map.
     ///
@@ -327,6 +414,7 @@ where
             key: None,
             cached_entries: CacheCell::new(EntryMap::new()),
             hash_builder: RefCell::new(HashBuilder::from(Vec::new())),
+            key_marker: PhantomData,
         }
     }
 
@@ -343,6 +431,7 @@ where
             key: Some(key),
             cached_entries: CacheCell::new(EntryMap::new()),
             hash_builder: RefCell::new(HashBuilder::from(Vec::new())),
+            key_marker: PhantomData,
         }
     }
 
@@ -358,15 +447,44 @@ where
     }
 
     /// Returns a shared reference to the underlying entries.
-    fn entries(&self) -> &EntryMap<K, V> {
+    fn entries(&self) -> &EntryMap<V> {
         self.cached_entries.as_inner()
     }
 
     /// Returns an exclusive reference to the underlying entries.
-    fn entries_mut(&mut self) -> &mut EntryMap<K, V> {
+    fn entries_mut(&mut self) -> &mut EntryMap<V> {
         self.cached_entries.as_inner_mut()
     }
 
+    /// Returns an exclusive reference to the underlying entries from a
+    /// `&self` receiver, for use by the flush path to transition pushed
+    /// entries back to [`EntryState::Preserved`].
+    ///
+    /// # Safety
+    ///
+    /// Just like [`LazyHashMap::entry`], this is only sound as long as the
+    /// returned reference does not outlive a call that itself never hands
+    /// out a conflicting reference into the cache.
+    unsafe fn entries_mut_via_ref(&self) -> &mut EntryMap<V> {
+        &mut *self.cached_entries.get_ptr().as_ptr()
+    }
+
+    /// Returns the number of currently cached entries that are dirty and
+    /// still need to be written back to storage on the next flush.
+    ///
+    /// # Note
+    ///
+    /// This only accounts for entries that have already been loaded into
+    /// the cache; it says nothing about entries that have not been
+    /// touched at all. Higher-level collections can use this to reason
+    /// about the write cost of their next flush.
+    pub fn dirty_count(&self) -> usize {
+        self.entries()
+            .values()
+            .filter(|entry| entry.state().requires_flush())
+            .count()
+    }
+
     /// Puts the new value under the given key.
     ///
     /// # Note
@@ -382,15 +500,49 @@ where
     /// - If the decoding of the old element at the given index failed.
     pub fn put(&mut self, key: K, new_value: Option<V>) {
         self.entries_mut().insert(
-            key,
+            key.encode(),
             Box::new(InternalEntry::new(new_value, EntryState::Mutated)),
         );
     }
+
+    /// Inserts `value` under `key`, assuming `key` is not already present,
+    /// and returns a mutable reference to it.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`LazyHashMap::put_get`] this never speculatively loads the
+    /// key's old value from storage first, since it assumes there is none to
+    /// find. Prefer this over `put_get` when bulk-inserting keys that are
+    /// already known to be unique, e.g. when building a map from a freshly
+    /// collected iterator.
+    ///
+    /// # Panics
+    ///
+    /// - If the lazy hash map is in an invalid state that forbids interaction
+    ///   with the underlying contract storage.
+    ///
+    /// # Logic Error
+    ///
+    /// It is a logic error to call this with a `key` that is already present:
+    /// doing so silently overwrites the cached entry (and its eventual
+    /// storage slot) without ever reporting the value that was there before.
+    pub fn insert_unique_unchecked(&mut self, key: K, value: V) -> &mut V {
+        let encoded_key = key.encode();
+        self.entries_mut().insert(
+            encoded_key.clone(),
+            Box::new(InternalEntry::new(Some(value), EntryState::Mutated)),
+        );
+        self.entries_mut()
+            .get_mut(&encoded_key)
+            .map(|boxed| &mut **boxed)
+            .and_then(|entry| entry.value_mut().as_mut())
+            .expect("just inserted; qed")
+    }
 }
 
 impl<K, V, H> LazyHashMap<K, V, H>
 where
-    K: Clone + Ord + PackedLayout,
+    K: scale::Encode,
     V: PackedLayout,
     H: Hasher,
     Key: From<<H as Hasher>::Output>,
@@ -409,12 +561,11 @@ where
         unsafe {
             let cached_entries = &mut *self.cached_entries.get_ptr().as_ptr();
             use ink_prelude::collections::btree_map::Entry as BTreeMapEntry;
-            // We have to clone the key here because we do not have access to the unsafe
-            // raw entry API for Rust hash maps, yet since it is unstable. We can remove
-            // the contraints on `K: Clone` once we have access to this API.
-            // Read more about the issue here: https://github.com/rust-lang/rust/issues/56167
-            // match cached_entries.entry(key.to_owned()) {
-            match cached_entries.entry(key.to_owned()) {
+            // The cache is probed with the key's SCALE-encoded bytes, so this
+            // never needs to own or clone `key` itself: the `BTreeMap`'s own
+            // `entry` API only ever takes ownership of the (freshly
+            // allocated) encoded bytes, not of `key`.
+            match cached_entries.entry(key.encode()) {
                 BTreeMapEntry::Occupied(occupied) => {
                     match occupied.get().value() {
                         Some(_) => Entry::Occupied(OccupiedEntry { key, base: self }),
@@ -427,32 +578,179 @@ where
             }
         }
     }
+
+    /// Gets the given borrowed key's corresponding entry in the map for
+    /// in-place manipulation.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`LazyHashMap::entry`] this never requires an owned `K` up
+    /// front: an owned key is only materialized, via `key.to_owned()`, if the
+    /// returned [`EntryRef`] actually ends up inserting a new value through
+    /// [`VacantEntryRef::insert`].
+    pub fn entry_ref<'b, Q>(&mut self, key: &'b Q) -> EntryRef<'_, 'b, K, Q, V, H>
+    where
+        Q: scale::Encode + ToOwned<Owned = K> + ?Sized,
+    {
+        // SAFETY: Same reasoning as `LazyHashMap::entry` above.
+        unsafe {
+            let cached_entries = &mut *self.cached_entries.get_ptr().as_ptr();
+            use ink_prelude::collections::btree_map::Entry as BTreeMapEntry;
+            match cached_entries.entry(key.encode()) {
+                BTreeMapEntry::Occupied(occupied) => {
+                    match occupied.get().value() {
+                        Some(_) => {
+                            EntryRef::Occupied(OccupiedEntryRef {
+                                base: self,
+                                encoded_key: key.encode(),
+                            })
+                        }
+                        None => {
+                            EntryRef::Vacant(VacantEntryRef {
+                                base: self,
+                                key,
+                                encoded_key: key.encode(),
+                            })
+                        }
+                    }
+                }
+                BTreeMapEntry::Vacant(vacant) => {
+                    let value = self
+                        .key_at(&key)
+                        .map(|key| pull_packed_root_opt::<V>(&key))
+                        .unwrap_or(None);
+                    let is_occupied = value.is_some();
+                    vacant.insert(Box::new(InternalEntry::new(value, EntryState::Preserved)));
+                    if is_occupied {
+                        EntryRef::Occupied(OccupiedEntryRef {
+                            base: self,
+                            encoded_key: key.encode(),
+                        })
+                    } else {
+                        EntryRef::Vacant(VacantEntryRef {
+                            base: self,
+                            key,
+                            encoded_key: key.encode(),
+                        })
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns a builder for raw entries of this map, for advanced callers
+    /// that already know (or can cheaply recompute) a key's on-chain storage
+    /// slot and want to avoid re-running the [`Hasher`] for it.
+    pub fn raw_entry_mut(&mut self) -> RawEntryBuilderMut<K, V, H> {
+        RawEntryBuilderMut { base: self }
+    }
+}
+
+/// A builder for raw entries of a [`LazyHashMap`], returned by
+/// [`LazyHashMap::raw_entry_mut`].
+pub struct RawEntryBuilderMut<'a, K, V, H>
+where
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// A reference to the used `HashMap` instance.
+    base: &'a mut LazyHashMap<K, V, H>,
+}
+
+impl<'a, K, V, H> RawEntryBuilderMut<'a, K, V, H>
+where
+    K: scale::Encode,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Looks up `key`'s entry, computing its on-chain storage slot via the
+    /// `Hasher` as usual on a cache miss and loading the cell from there.
+    ///
+    /// The raw-entry and the ordinary entry API share the same
+    /// `Entry`/`OccupiedEntry`/`VacantEntry` surface. Unlike
+    /// [`LazyHashMap::entry`], which returns a vacant entry on a cache miss
+    /// without reading the contract storage, this loads the cell first, the
+    /// same way [`Self::from_key_hashed_nocheck`] does from a caller-supplied
+    /// slot.
+    pub fn from_key(self, key: K) -> Entry<'a, K, V, H> {
+        match self.base.key_at(&key) {
+            Some(hashed_key) => self.from_key_hashed_nocheck(hashed_key, key),
+            None => self.base.entry(key),
+        }
+    }
+
+    /// Looks up `key`'s entry on a cache miss by pulling directly from
+    /// `hashed_key`, the caller-supplied on-chain storage slot, instead of
+    /// recomputing it via the map's internal key-hashing step.
+    ///
+    /// # Note
+    ///
+    /// The caller must guarantee that `hashed_key` is the storage slot that
+    /// this map would otherwise have computed for `key`; supplying a
+    /// mismatched key only ever yields incorrect (or vacant) results, it is
+    /// not a memory-safety hazard.
+    pub fn from_key_hashed_nocheck(self, hashed_key: Key, key: K) -> Entry<'a, K, V, H> {
+        // SAFETY: Same reasoning as `LazyHashMap::entry`.
+        unsafe {
+            let cached_entries = &mut *self.base.cached_entries.get_ptr().as_ptr();
+            use ink_prelude::collections::btree_map::Entry as BTreeMapEntry;
+            match cached_entries.entry(key.encode()) {
+                BTreeMapEntry::Occupied(occupied) => {
+                    match occupied.get().value() {
+                        Some(_) => {
+                            Entry::Occupied(OccupiedEntry { key, base: self.base })
+                        }
+                        None => Entry::Vacant(VacantEntry { key, base: self.base }),
+                    }
+                }
+                BTreeMapEntry::Vacant(vacant) => {
+                    let value = pull_packed_root_opt::<V>(&hashed_key);
+                    let is_occupied = value.is_some();
+                    vacant.insert(Box::new(InternalEntry::new(value, EntryState::Preserved)));
+                    if is_occupied {
+                        Entry::Occupied(OccupiedEntry { key, base: self.base })
+                    } else {
+                        Entry::Vacant(VacantEntry { key, base: self.base })
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl<K, V, H> LazyHashMap<K, V, H>
 where
-    K: Ord + scale::Encode,
     H: Hasher,
     Key: From<<H as Hasher>::Output>,
 {
-    /// Returns an offset key for the given key pair.
-    fn to_offset_key<Q>(&self, storage_key: &Key, key: &Q) -> Key
-    where
-        K: Borrow<Q>,
-        Q: scale::Encode,
-    {
-        #[derive(scale::Encode)]
-        struct KeyPair<'a, Q> {
+    /// Returns an offset key for the given, already SCALE-encoded key bytes.
+    ///
+    /// # Note
+    ///
+    /// Both [`LazyHashMap::to_offset_key`] and [`LazyHashMap::push_spread`]
+    /// route through this method so that a key's on-chain storage slot only
+    /// ever depends on its raw SCALE encoding, whether the caller started out
+    /// with the typed key or with its already-encoded bytes.
+    fn to_offset_key_bytes(&self, storage_key: &Key, encoded_key: &[u8]) -> Key {
+        struct KeyPair<'a> {
             prefix: [u8; 11],
             storage_key: &'a Key,
-            value_key: &'a Q,
+            value_key: &'a [u8],
+        }
+        impl<'a> scale::Encode for KeyPair<'a> {
+            fn encode_to<W: parity_scale_codec::Output>(&self, dest: &mut W) {
+                self.prefix.encode_to(dest);
+                self.storage_key.encode_to(dest);
+                dest.write(self.value_key);
+            }
         }
         let key_pair = KeyPair {
             prefix: [
                 b'i', b'n', b'k', b' ', b'h', b'a', b's', b'h', b'm', b'a', b'p',
             ],
             storage_key,
-            value_key: key,
+            value_key: encoded_key,
         };
         self.hash_builder
             .borrow_mut()
@@ -460,20 +758,32 @@ where
             .into()
     }
 
+    /// Returns an offset key for the given key pair.
+    fn to_offset_key<Q>(&self, storage_key: &Key, key: &Q) -> Key
+    where
+        Q: scale::Encode,
+    {
+        self.to_offset_key_bytes(storage_key, &key.encode())
+    }
+
     /// Returns an offset key for the given key.
     fn key_at<Q>(&self, key: &Q) -> Option<Key>
     where
-        K: Borrow<Q>,
         Q: scale::Encode,
     {
         self.key
             .map(|storage_key| self.to_offset_key(&storage_key, key))
     }
+
+    /// Returns an offset key for the given, already SCALE-encoded key bytes.
+    fn key_at_bytes(&self, encoded_key: &[u8]) -> Option<Key> {
+        self.key
+            .map(|storage_key| self.to_offset_key_bytes(&storage_key, encoded_key))
+    }
 }
 
 impl<K, V, H> LazyHashMap<K, V, H>
 where
-    K: Ord + Eq + scale::Encode,
     V: PackedLayout,
     H: Hasher,
     Key: From<<H as Hasher>::Output>,
@@ -499,8 +809,7 @@ where
     /// entity for public `&self` methods.
     unsafe fn lazily_load<Q>(&self, key: &Q) -> NonNull<InternalEntry<V>>
     where
-        K: Borrow<Q>,
-        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+        Q: scale::Encode,
     {
         // SAFETY: We have put the whole `cached_entries` mapping into an
         //         `UnsafeCell` because of this caching functionality. The
@@ -513,11 +822,10 @@ where
         //         caller.
         let cached_entries = &mut *self.cached_entries.get_ptr().as_ptr();
         use ink_prelude::collections::btree_map::Entry as BTreeMapEntry;
-        // We have to clone the key here because we do not have access to the unsafe
-        // raw entry API for Rust hash maps, yet since it is unstable. We can remove
-        // the contraints on `K: Clone` once we have access to this API.
-        // Read more about the issue here: https://github.com/rust-lang/rust/issues/56167
-        match cached_entries.entry(key.to_owned()) {
+        // Probing the cache with the encoded key bytes means a lookup never
+        // has to own or clone `key`; the encoded `Vec<u8>` is allocated on
+        // every lookup and, on a cache miss, becomes the map's own key.
+        match cached_entries.entry(key.encode()) {
             BTreeMapEntry::Occupied(occupied) => {
                 NonNull::from(&mut **occupied.into_mut())
             }
@@ -549,8 +857,7 @@ where
     /// - If the lazy chunk is not in a state that allows lazy loading.
     fn lazily_load_mut<Q>(&mut self, index: &Q) -> &mut InternalEntry<V>
     where
-        K: Borrow<Q>,
-        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+        Q: scale::Encode,
     {
         // SAFETY:
         // - Returning a `&mut Entry<T>` is safe because entities inside the
@@ -571,9 +878,7 @@ where
     /// high-level abstractions that build upon this low-level data strcuture.
     pub fn clear_packed_at<Q>(&self, index: &Q)
     where
-        K: Borrow<Q>,
-        V: PackedLayout,
-        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+        Q: scale::Encode,
     {
         let root_key = self.key_at(index).expect("cannot clear in lazy state");
         if <V as SpreadLayout>::REQUIRES_DEEP_CLEAN_UP {
@@ -597,13 +902,14 @@ where
     /// - If the decoding of the element at the given index failed.
     pub fn get<Q>(&self, index: &Q) -> Option<&V>
     where
-        K: Borrow<Q>,
-        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+        Q: scale::Encode,
     {
         // SAFETY: Dereferencing the `*mut T` pointer into a `&T` is safe
         //         since this method's receiver is `&self` so we do not
         //         leak non-shared references to the outside.
-        unsafe { &*self.lazily_load(index).as_ptr() }.value().into()
+        unsafe { &*self.lazily_load(index).as_ptr() }
+            .value()
+            .as_ref()
     }
 
     /// Returns an exclusive reference to the value associated with the given key if any.
@@ -614,10 +920,13 @@ where
     /// - If the decoding of the element at the given index failed.
     pub fn get_mut<Q>(&mut self, index: &Q) -> Option<&mut V>
     where
-        K: Borrow<Q>,
-        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+        Q: scale::Encode,
     {
-        self.lazily_load_mut(index).value_mut().into()
+        let entry = self.lazily_load_mut(index);
+        if entry.value().is_some() {
+            entry.replace_state(EntryState::Mutated);
+        }
+        entry.value_mut().as_mut()
     }
 
     /// Puts the new value under the given key and returns the old value if any.
@@ -633,8 +942,7 @@ where
     /// - If the decoding of the old element at the given index failed.
     pub fn put_get<Q>(&mut self, key: &Q, new_value: Option<V>) -> Option<V>
     where
-        K: Borrow<Q>,
-        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+        Q: scale::Encode,
     {
         self.lazily_load_mut(key).put(new_value)
     }
@@ -649,12 +957,11 @@ where
     /// - If the decoding of one of the elements failed.
     pub fn swap<Q1, Q2>(&mut self, x: &Q1, y: &Q2)
     where
-        K: Borrow<Q1> + Borrow<Q2>,
-        Q1: Ord + PartialEq<Q2> + scale::Encode + ToOwned<Owned = K>,
-        Q2: Ord + PartialEq<Q1> + scale::Encode + ToOwned<Owned = K>,
+        Q1: scale::Encode,
+        Q2: scale::Encode,
     {
-        if x == y {
-            // Bail out early if both indices are the same.
+        if x.encode() == y.encode() {
+            // Bail out early if both indices encode to the same bytes.
             return
         }
         let (loaded_x, loaded_y) =
@@ -671,35 +978,576 @@ where
             // Bail out since nothing has to be swapped if both values are `None`.
             return
         }
-        // Set the `mutate` flag since at this point at least one of the loaded
-        // values is guaranteed to be `Some`.
-        loaded_x.replace_state(EntryState::Mutated);
-        loaded_y.replace_state(EntryState::Mutated);
-        core::mem::swap(loaded_x.value_mut(), loaded_y.value_mut());
+        // Set the `mutate` flag since at this point at least one of the loaded
+        // values is guaranteed to be `Some`.
+        loaded_x.replace_state(EntryState::Mutated);
+        loaded_y.replace_state(EntryState::Mutated);
+        core::mem::swap(loaded_x.value_mut(), loaded_y.value_mut());
+    }
+
+    /// Returns mutable references to the values associated with each of `keys`,
+    /// in the same order as `keys`.
+    ///
+    /// Returns `None` if `keys` contains any duplicate (by SCALE encoding) or
+    /// if any of `keys` has no associated value.
+    ///
+    /// # Panics
+    ///
+    /// - If the lazy hashmap is in an invalid state that forbids interaction.
+    /// - If the decoding of one of the elements failed.
+    pub fn get_many_mut<Q>(&mut self, keys: &[&Q]) -> Option<Vec<&mut V>>
+    where
+        Q: scale::Encode,
+    {
+        let encoded_keys: Vec<_> = keys.iter().map(|key| key.encode()).collect();
+        for i in 0..encoded_keys.len() {
+            for j in 0..i {
+                if encoded_keys[i] == encoded_keys[j] {
+                    // Bail out early since two of `keys` encode to the same
+                    // bytes: handing out two `&mut` into the same entry would
+                    // violate aliasing.
+                    return None
+                }
+            }
+        }
+        // SAFETY: The keys are pairwise distinct, guaranteed by the check
+        //         above. Also `lazily_load` guarantees to return a pointer to
+        //         a pinned entity so that the returned references do not
+        //         conflict with each other.
+        let loaded: Vec<_> = keys
+            .iter()
+            .map(|key| unsafe { &mut *self.lazily_load(*key).as_ptr() })
+            .collect();
+        if loaded.iter().any(|entry| entry.value().is_none()) {
+            return None
+        }
+        Some(
+            loaded
+                .into_iter()
+                .map(|entry| {
+                    entry.replace_state(EntryState::Mutated);
+                    entry
+                        .value_mut()
+                        .as_mut()
+                        .expect("just checked that all values are `Some`; qed")
+                })
+                .collect(),
+        )
+    }
+
+    /// Like [`LazyHashMap::get_many_mut`] but does not check that `keys` are
+    /// pairwise distinct or that every key maps to a value.
+    ///
+    /// # Safety
+    ///
+    /// Calling this with duplicate keys is undefined behavior, since it would
+    /// produce multiple `&mut` references into the same value.
+    ///
+    /// # Panics
+    ///
+    /// - If the lazy hashmap is in an invalid state that forbids interaction.
+    /// - If the decoding of one of the elements failed.
+    /// - If any of `keys` has no associated value.
+    pub unsafe fn get_many_unchecked_mut<Q>(&mut self, keys: &[&Q]) -> Vec<&mut V>
+    where
+        Q: scale::Encode,
+    {
+        keys.iter()
+            .map(|key| &mut *self.lazily_load(*key).as_ptr())
+            .map(|entry| {
+                entry.replace_state(EntryState::Mutated);
+                entry
+                    .value_mut()
+                    .as_mut()
+                    .expect("caller must guarantee that every key maps to a value")
+            })
+            .collect()
+    }
+}
+
+impl<K, V, H> LazyHashMap<K, V, H>
+where
+    K: scale::Encode + scale::Decode,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Retains only the cached entries specified by the predicate.
+    ///
+    /// In other words, removes all cached `(k, v)` for which `f(&k, &mut v)`
+    /// returns `false`, deep-cleaning nested fields of a removed value (via
+    /// the same path [`LazyHashMap::clear_packed_at`] uses) if `V` requires it.
+    ///
+    /// # Note
+    ///
+    /// A [`LazyHashMap`] is not aware of its full key set, so this only ever
+    /// visits entries that are already present in the in-memory cache. This
+    /// is a building block for higher-level collections that do track their
+    /// complete index set.
+    ///
+    /// # Panics
+    ///
+    /// If decoding a cached entry's key fails.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        // SAFETY: No other reference into the cache is alive while this
+        //         loop runs, so taking `&mut` here from a `&self` receiver
+        //         does not create a conflicting alias.
+        for (encoded_key, entry) in unsafe { self.entries_mut_via_ref() }.iter_mut() {
+            let key = K::decode(&mut &encoded_key[..])
+                .expect("encountered invalid encoding of a cached key");
+            let retain = match entry.value_mut().as_mut() {
+                Some(value) => f(&key, value),
+                None => true,
+            };
+            if retain {
+                continue
+            }
+            if <V as SpreadLayout>::REQUIRES_DEEP_CLEAN_UP {
+                if let (Some(root_key), Some(value)) =
+                    (self.key_at_bytes(encoded_key), entry.value().as_ref())
+                {
+                    clear_packed_root::<V>(value, &root_key);
+                }
+            }
+            entry.put(None);
+        }
+    }
+
+    /// Removes and returns all cached key/value pairs for which
+    /// `f(&k, &mut v)` returns `true`.
+    ///
+    /// # Note
+    ///
+    /// See [`LazyHashMap::retain`] for the same in-memory-cache-only caveat.
+    /// This is the same operation that other standard and `hashbrown`-style
+    /// map APIs call `extract_if`; this crate keeps the `drain_filter` name
+    /// to match [`storage2::collections::hashmap::HashMap::drain_filter`](
+    /// crate::storage2::collections::hashmap::HashMap::drain_filter).
+    pub fn drain_filter<F>(&mut self, f: F) -> DrainFilter<K, V, H, F>
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        let keys = self.entries().keys().cloned().collect::<Vec<_>>();
+        DrainFilter {
+            map: self,
+            keys,
+            pred: f,
+            key_marker: PhantomData,
+        }
+    }
+}
+
+/// An iterator that removes and yields all cached key/value pairs of a
+/// [`LazyHashMap`] for which the predicate passed to
+/// [`LazyHashMap::drain_filter`] returns `true`.
+///
+/// Created through [`LazyHashMap::drain_filter`].
+pub struct DrainFilter<'a, K, V, H, F>
+where
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+    F: FnMut(&K, &mut V) -> bool,
+{
+    /// The map the entries are drained from.
+    map: &'a mut LazyHashMap<K, V, H>,
+    /// The still to be visited, already SCALE-encoded keys, captured at
+    /// construction time.
+    keys: Vec<Vec<u8>>,
+    /// The predicate deciding whether a cached entry is drained.
+    pred: F,
+    /// Marker that binds this iterator to the original key type `K`.
+    key_marker: PhantomData<fn() -> K>,
+}
+
+impl<'a, K, V, H, F> Iterator for DrainFilter<'a, K, V, H, F>
+where
+    K: scale::Encode + scale::Decode,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+    F: FnMut(&K, &mut V) -> bool,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(encoded_key) = self.keys.pop() {
+            let key = K::decode(&mut &encoded_key[..])
+                .expect("encountered invalid encoding of a cached key");
+            let matches = match self
+                .map
+                .entries_mut()
+                .get_mut(&encoded_key)
+                .and_then(|entry| entry.value_mut().as_mut())
+            {
+                Some(value) => (self.pred)(&key, value),
+                None => continue,
+            };
+            if !matches {
+                continue
+            }
+            if <V as SpreadLayout>::REQUIRES_DEEP_CLEAN_UP {
+                if let (Some(root_key), Some(value)) = (
+                    self.map.key_at_bytes(&encoded_key),
+                    self.map
+                        .entries()
+                        .get(&encoded_key)
+                        .and_then(|entry| entry.value().as_ref()),
+                ) {
+                    clear_packed_root::<V>(value, &root_key);
+                }
+            }
+            let old_value = self
+                .map
+                .entries_mut()
+                .get_mut(&encoded_key)
+                .expect("entry was just resolved to an occupied value; qed")
+                .put(None);
+            return Some((
+                key,
+                old_value.expect("entry was just confirmed to hold a value; qed"),
+            ))
+        }
+        None
+    }
+}
+
+impl<'a, K, V, H, F> Drop for DrainFilter<'a, K, V, H, F>
+where
+    K: scale::Encode + scale::Decode,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+    F: FnMut(&K, &mut V) -> bool,
+{
+    fn drop(&mut self) {
+        // Exhaust the iterator so that every remaining matching entry is
+        // still removed even if the caller drops this `DrainFilter` before
+        // fully consuming it, matching `std`'s own `drain_filter`/
+        // `extract_if` iterators.
+        while self.next().is_some() {}
+    }
+}
+
+impl<'a, K, V, H> Entry<'a, K, V, H>
+where
+    K: scale::Encode,
+    V: PackedLayout + Debug + Eq + Default,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Returns a reference to this entry's key.
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => &entry.key,
+            Entry::Vacant(entry) => &entry.key,
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the default value if empty, and returns
+    /// a reference to the value in the entry.
+    pub fn or_default(self) -> &'a V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(V::default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the default if empty, and returns
+    /// a mutable reference to the value in the entry.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default function if empty,
+    /// and returns mutable references to the key and value in the entry.
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => Entry::insert(default(), entry),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result of the default
+    /// function, which takes the key as its argument, and returns a mutable reference to
+    /// the value in the entry.
+    pub fn or_insert_with_key<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce(&K) -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => Entry::insert(default(&entry.key), entry),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map.
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            Entry::Occupied(mut entry) => {
+                let encoded_key = entry.key.encode();
+                {
+                    let v = entry.get_mut();
+                    f(v);
+                }
+                // Mark the entry `Mutated` now that `f` has had its chance to
+                // mutate the value, so that the change is written back by
+                // `push_spread`'s dirty-only flush.
+                entry
+                    .base
+                    .entries_mut()
+                    .get_mut(&encoded_key)
+                    .expect("entry behind `OccupiedEntry` must always exist")
+                    .replace_state(EntryState::Mutated);
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+
+    /// Inserts `value` into `entry`.
+    fn insert(value: V, entry: VacantEntry<'a, K, V, H>) -> &'a mut V {
+        let VacantEntry { base, key } = entry;
+        // Compute the encoded key bytes before moving `key` into `put` so
+        // that we can look the just-inserted entry back up without ever
+        // cloning `key`.
+        let encoded_key = key.encode();
+        base.put(key, Some(value));
+        base.entries_mut()
+            .get_mut(&encoded_key)
+            .map(|boxed| &mut **boxed)
+            .and_then(|entry| entry.value_mut().as_mut())
+            .expect("encountered invalid vacant entry")
+    }
+}
+
+impl<'a, K, V, H> VacantEntry<'a, K, V, H>
+where
+    K: scale::Encode,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Gets a reference to the key that would be used when inserting a value through the VacantEntry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Take ownership of the key.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Sets the value of the entry with the VacantEntry's key, and returns a mutable reference to it.
+    pub fn insert(self, value: V) -> &'a mut V {
+        // Compute the encoded key bytes before moving `self.key` into `put`
+        // so that we can look the just-inserted entry back up without ever
+        // cloning `self.key`.
+        let encoded_key = self.key.encode();
+        self.base.put(self.key, Some(value));
+        self.base
+            .entries_mut()
+            .get_mut(&encoded_key)
+            .map(|boxed| &mut **boxed)
+            .and_then(|entry| entry.value_mut().as_mut())
+            .expect("put was just executed; qed")
+    }
+}
+
+impl<'a, K, V, H> VacantEntry<'a, K, V, H>
+where
+    K: scale::Encode + Clone,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Sets the value of the entry with the `VacantEntry`'s key, and returns
+    /// an [`OccupiedEntry`] for the same, now-occupied entry, so that a
+    /// follow-up `.key()`, `.get()`/`.get_mut()`, `.remove()`, or
+    /// `.remove_entry()` reuses the cache slot `insert_entry` already paid
+    /// the cache miss for, instead of re-deriving a fresh `Entry` and
+    /// re-hashing the key.
+    pub fn insert_entry(self, value: V) -> OccupiedEntry<'a, K, V, H> {
+        self.base.put(self.key.clone(), Some(value));
+        OccupiedEntry {
+            base: self.base,
+            key: self.key,
+        }
+    }
+}
+
+impl<'a, K, V, H> OccupiedEntry<'a, K, V, H>
+where
+    K: scale::Encode,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Gets a reference to the key in the entry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Take the ownership of the key and value from the map.
+    pub fn remove_entry(self) -> (K, V) {
+        let value = self
+            .base
+            .put_get(&self.key, None)
+            .expect("`key` must exist");
+        (self.key, value)
+    }
+
+    /// Gets a reference to the value in the entry.
+    pub fn get(&self) -> &V {
+        self.base
+            .get(&self.key)
+            .expect("entry behind `OccupiedEntry` must always exist")
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    ///
+    /// If you need a reference to the `OccupiedEntry` which may outlive the destruction of the
+    /// `Entry` value, see `into_mut`.
+    pub fn get_mut(&mut self) -> &mut V {
+        self.base
+            .get_mut(&self.key)
+            .expect("entry behind `OccupiedEntry` must always exist")
+    }
+
+    /// Sets the value of the entry, and returns the entry's old value.
+    pub fn insert(&mut self, new_value: V) -> V {
+        let occupied = self
+            .base
+            .get_mut(&self.key)
+            .expect("entry behind `OccupiedEntry` must always exist");
+        core::mem::replace(occupied, new_value)
+    }
+
+    /// Takes the value out of the entry, and returns it.
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Converts the OccupiedEntry into a mutable reference to the value in the entry
+    /// with a lifetime bound to the map itself.
+    pub fn into_mut(self) -> &'a mut V {
+        self.base
+            .get_mut(&self.key)
+            .expect("entry behind `OccupiedEntry` must always exist")
+    }
+
+    /// Takes ownership of the key this entry was looked up with.
+    ///
+    /// # Note
+    ///
+    /// Unlike `std`/`hashbrown`'s `replace_key`, this map never stores `K`
+    /// itself in its cache -- only the value behind its SCALE-encoded bytes
+    /// -- so there is no separately-stored key to swap this one in for. This
+    /// simply hands back the key `entry(...)` was called with, consuming the
+    /// entry.
+    pub fn replace_key(self) -> K {
+        self.key
+    }
+
+    /// Replaces the value of the entry with `value`, and returns the key
+    /// this entry was looked up with together with the entry's old value.
+    ///
+    /// # Note
+    ///
+    /// See [`OccupiedEntry::replace_key`] for why the returned key is simply
+    /// the key this entry was looked up with, not a distinct previously-stored
+    /// one: callers intending to canonicalize a key must ensure the
+    /// replacement key they passed to `entry(...)` still encodes to the same
+    /// storage slot as the one actually occupying this entry.
+    pub fn replace_entry(mut self, value: V) -> (K, V) {
+        let old_value = self.insert(value);
+        (self.key, old_value)
+    }
+}
+
+impl<'a, K, V, H> OccupiedEntry<'a, K, V, H>
+where
+    K: scale::Encode + Clone,
+    V: PackedLayout,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
+    /// Replaces the entry's value with the result of `f`, which is handed
+    /// the key and the current value by move, and returns an [`Entry`] for
+    /// further chaining.
+    ///
+    /// `Some(new_value)` writes `new_value` back and keeps the entry
+    /// occupied; `None` removes the entry from the map entirely. Both the
+    /// read and the write-back go to the entry already cached for this key,
+    /// so this never reads the contract storage.
+    pub fn replace_entry_with<F>(self, f: F) -> Entry<'a, K, V, H>
+    where
+        F: FnOnce(&K, V) -> Option<V>,
+    {
+        let OccupiedEntry { base, key } = self;
+        let old_value = base
+            .put_get(&key, None)
+            .expect("entry behind `OccupiedEntry` must always exist");
+        match f(&key, old_value) {
+            Some(new_value) => {
+                base.put(key.clone(), Some(new_value));
+                Entry::Occupied(OccupiedEntry { base, key })
+            }
+            None => Entry::Vacant(VacantEntry { base, key }),
+        }
     }
 }
 
 impl<'a, K, V, H> Entry<'a, K, V, H>
 where
-    K: Ord + Clone + PackedLayout,
-    V: PackedLayout + core::fmt::Debug + core::cmp::Eq + Default,
+    K: scale::Encode + Clone,
+    V: PackedLayout,
     H: Hasher,
     Key: From<<H as Hasher>::Output>,
 {
-    /// Returns a reference to this entry's key.
-    pub fn key(&self) -> &K {
+    /// Provides in-place mutable access to an occupied entry, replacing or
+    /// removing it, before any potential inserts into the map.
+    ///
+    /// This is [`OccupiedEntry::replace_entry_with`] lifted onto `Entry`
+    /// itself: a vacant entry is passed through untouched.
+    pub fn and_replace_entry_with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&K, V) -> Option<V>,
+    {
         match self {
-            Entry::Occupied(entry) => &entry.key,
-            Entry::Vacant(entry) => &entry.key,
+            Entry::Occupied(entry) => entry.replace_entry_with(f),
+            Entry::Vacant(entry) => Entry::Vacant(entry),
         }
     }
+}
 
+impl<'a, 'b, K, Q, V, H> EntryRef<'a, 'b, K, Q, V, H>
+where
+    Q: scale::Encode + ToOwned<Owned = K> + ?Sized,
+    K: scale::Encode,
+    V: PackedLayout + Debug + Eq + Default,
+    H: Hasher,
+    Key: From<<H as Hasher>::Output>,
+{
     /// Ensures a value is in the entry by inserting the default value if empty, and returns
     /// a reference to the value in the entry.
     pub fn or_default(self) -> &'a V {
         match self {
-            Entry::Occupied(entry) => entry.into_mut(),
-            Entry::Vacant(entry) => entry.insert(V::default()),
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(V::default()),
         }
     }
 
@@ -707,33 +1555,36 @@ where
     /// a mutable reference to the value in the entry.
     pub fn or_insert(self, default: V) -> &'a mut V {
         match self {
-            Entry::Occupied(entry) => entry.into_mut(),
-            Entry::Vacant(entry) => entry.insert(default),
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(default),
         }
     }
 
     /// Ensures a value is in the entry by inserting the result of the default function if empty,
-    /// and returns mutable references to the key and value in the entry.
+    /// and returns a mutable reference to the value in the entry.
     pub fn or_insert_with<F>(self, default: F) -> &'a mut V
     where
         F: FnOnce() -> V,
     {
         match self {
-            Entry::Occupied(entry) => entry.into_mut(),
-            Entry::Vacant(entry) => Entry::insert(default(), entry),
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(default()),
         }
     }
 
     /// Ensures a value is in the entry by inserting, if empty, the result of the default
-    /// function, which takes the key as its argument, and returns a mutable reference to
-    /// the value in the entry.
+    /// function, which takes the borrowed key as its argument, and returns a mutable
+    /// reference to the value in the entry.
     pub fn or_insert_with_key<F>(self, default: F) -> &'a mut V
     where
-        F: FnOnce(&K) -> V,
+        F: FnOnce(&Q) -> V,
     {
         match self {
-            Entry::Occupied(entry) => entry.into_mut(),
-            Entry::Vacant(entry) => Entry::insert(default(&entry.key), entry),
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => {
+                let value = default(entry.key);
+                entry.insert(value)
+            }
         }
     }
 
@@ -744,124 +1595,104 @@ where
         F: FnOnce(&mut V),
     {
         match self {
-            Entry::Occupied(mut entry) => {
-                {
-                    let v = entry.get_mut();
-                    f(v);
-                }
-                Entry::Occupied(entry)
+            EntryRef::Occupied(mut entry) => {
+                f(entry.get_mut());
+                // `OccupiedEntryRef::get_mut` hands out a `&mut V` without
+                // marking the entry dirty, so we mark it `Mutated` ourselves
+                // now that `f` has had its chance to mutate the value; see
+                // `Entry::and_modify` above for the same reasoning.
+                entry
+                    .base
+                    .entries_mut()
+                    .get_mut(&entry.encoded_key)
+                    .expect("entry behind `OccupiedEntryRef` must always exist")
+                    .replace_state(EntryState::Mutated);
+                EntryRef::Occupied(entry)
             }
-            Entry::Vacant(entry) => Entry::Vacant(entry),
+            EntryRef::Vacant(entry) => EntryRef::Vacant(entry),
         }
     }
-
-    /// Inserts `value` into `entry`.
-    fn insert(value: V, entry: VacantEntry<'a, K, V, H>) -> &'a mut V {
-        let _old_value = entry.base.put(entry.key.clone(), Some(value));
-        // debug_assert!(old_value.is_none());
-        entry
-            .base
-            .get_mut(&entry.key)
-            .expect("encountered invalid vacant entry")
-    }
 }
 
-impl<'a, K, V, H> VacantEntry<'a, K, V, H>
+impl<'a, 'b, K, Q, V, H> VacantEntryRef<'a, 'b, K, Q, V, H>
 where
-    K: Ord + Clone + PackedLayout,
+    Q: scale::Encode + ToOwned<Owned = K> + ?Sized,
+    K: scale::Encode,
     V: PackedLayout,
     H: Hasher,
     Key: From<<H as Hasher>::Output>,
 {
-    /// Gets a reference to the key that would be used when inserting a value through the VacantEntry.
-    pub fn key(&self) -> &K {
-        &self.key
-    }
-
-    /// Take ownership of the key.
-    pub fn into_key(self) -> K {
+    /// Gets a reference to the borrowed key that would be used when inserting
+    /// a value through the `VacantEntryRef`.
+    pub fn key(&self) -> &Q {
         self.key
     }
 
-    /// Sets the value of the entry with the VacantEntry's key, and returns a mutable reference to it.
+    /// Sets the value of the entry with the `VacantEntryRef`'s key, materializing
+    /// an owned key via `ToOwned::to_owned`, and returns a mutable reference to it.
     pub fn insert(self, value: V) -> &'a mut V {
-        // At this point we know that `key` does not yet exist in the map.
-        // let key_index = self.base.keys.put(self.key.clone());
-        self.base
-            //.values
-            //.put(self.key.clone(), Some(ValueEntry { value, key_index }));
-            .put(self.key.clone(), Some(value));
+        let owned_key = self.key.to_owned();
+        self.base.put(owned_key, Some(value));
         self.base
-            .get_mut(&self.key)
+            .entries_mut()
+            .get_mut(&self.encoded_key)
+            .map(|boxed| &mut **boxed)
+            .and_then(|entry| entry.value_mut().as_mut())
             .expect("put was just executed; qed")
     }
 }
 
-impl<'a, K, V, H> OccupiedEntry<'a, K, V, H>
+impl<'a, K, V, H> OccupiedEntryRef<'a, K, V, H>
 where
-    K: Ord + Clone + PackedLayout,
+    K: scale::Encode,
     V: PackedLayout,
     H: Hasher,
     Key: From<<H as Hasher>::Output>,
 {
-    /// Gets a reference to the key in the entry.
-    pub fn key(&self) -> &K {
-        &self.key
-    }
-
-    /// Take the ownership of the key and value from the map.
-    pub fn remove_entry(self) -> (K, V) {
-        let value = self
-            .base
-            //.values
-            .put_get(&self.key, None)
-            .expect("`key` must exist");
-        // self.base
-        // .keys
-        // .take(self.key_index)
-        // .expect("`key_index` must point to a valid key entry");
-        (self.key, value)
-    }
-
     /// Gets a reference to the value in the entry.
     pub fn get(&self) -> &V {
-        &self
-            .base
-            .get(&self.key)
-            .expect("entry behind `OccupiedEntry` must always exist")
+        self.base
+            .entries()
+            .get(&self.encoded_key)
+            .and_then(|entry| entry.value().as_ref())
+            .expect("entry behind `OccupiedEntryRef` must always exist")
     }
 
     /// Gets a mutable reference to the value in the entry.
     ///
-    /// If you need a reference to the `OccupiedEntry` which may outlive the destruction of the
-    /// `Entry` value, see `into_mut`.
+    /// If you need a reference to the value which may outlive the destruction of the
+    /// `OccupiedEntryRef` value, see `into_mut`.
     pub fn get_mut(&mut self) -> &mut V {
         self.base
-            .get_mut(&self.key)
-            .expect("entry behind `OccupiedEntry` must always exist")
+            .entries_mut()
+            .get_mut(&self.encoded_key)
+            .and_then(|entry| entry.value_mut().as_mut())
+            .expect("entry behind `OccupiedEntryRef` must always exist")
     }
 
     /// Sets the value of the entry, and returns the entry's old value.
     pub fn insert(&mut self, new_value: V) -> V {
-        let mut occupied = self
-            .base
-            //.values
-            .get_mut(&self.key)
-            .expect("entry behind `OccupiedEntry` must always exist");
-        core::mem::replace(&mut occupied, new_value)
+        core::mem::replace(self.get_mut(), new_value)
     }
 
     /// Takes the value out of the entry, and returns it.
     pub fn remove(self) -> V {
-        self.remove_entry().1
+        self.base
+            .entries_mut()
+            .get_mut(&self.encoded_key)
+            .expect("entry behind `OccupiedEntryRef` must always exist")
+            .put(None)
+            .expect("entry behind `OccupiedEntryRef` must always hold a value")
     }
 
-    /// Converts the OccupiedEntry into a mutable reference to the value in the entry
-    /// with a lifetime bound to the map itself.
+    /// Converts the `OccupiedEntryRef` into a mutable reference to the value
+    /// in the entry with a lifetime bound to the map itself.
     pub fn into_mut(self) -> &'a mut V {
         self.base
-            .get_mut(&self.key)
-            .expect("entry behind `OccupiedEntry` must always exist")
+            .entries_mut()
+            .get_mut(&self.encoded_key)
+            .and_then(|entry| entry.value_mut().as_mut())
+            .expect("entry behind `OccupiedEntryRef` must always exist")
     }
 }
 
@@ -883,9 +1714,14 @@ mod tests {
             SpreadLayout,
         },
     };
+    use core::iter::FromIterator as _;
     use ink_primitives::Key;
+    use scale::Encode as _;
 
     /// Asserts that the cached entries of the given `imap` is equal to the `expected` slice.
+    ///
+    /// The cache is keyed on each key's SCALE-encoded bytes, so `expected`'s
+    /// typed keys are encoded here for the comparison.
     fn assert_cached_entries<H>(
         hmap: &LazyHashMap<i32, u8, H>,
         expected: &[(i32, InternalEntry<u8>)],
@@ -894,8 +1730,12 @@ mod tests {
         for (given, expected) in hmap
             .entries()
             .iter()
-            .map(|(index, boxed_entry)| (*index, &**boxed_entry))
-            .zip(expected.iter().map(|(index, entry)| (*index, entry)))
+            .map(|(encoded_key, boxed_entry)| (encoded_key.clone(), &**boxed_entry))
+            .zip(
+                expected
+                    .iter()
+                    .map(|(key, entry)| (key.encode(), entry)),
+            )
         {
             assert_eq!(given, expected);
         }
@@ -1172,6 +2012,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_many_mut_works() {
+        let mut hmap = new_hmap();
+        assert_eq!(hmap.put_get(&1, Some(b'A')), None);
+        assert_eq!(hmap.put_get(&2, Some(b'B')), None);
+        assert_eq!(hmap.put_get(&3, Some(b'C')), None);
+        // Disjoint keys that all map to a value: succeeds.
+        if let Some(values) = hmap.get_many_mut(&[&1, &3]) {
+            *values[0] = b'X';
+            *values[1] = b'Y';
+        } else {
+            panic!("expected `Some`");
+        }
+        assert_eq!(hmap.get(&1), Some(&b'X'));
+        assert_eq!(hmap.get(&2), Some(&b'B'));
+        assert_eq!(hmap.get(&3), Some(&b'Y'));
+        // A duplicated key must be rejected.
+        assert!(hmap.get_many_mut(&[&1, &1]).is_none());
+        // A key with no associated value must be rejected.
+        assert!(hmap.get_many_mut(&[&1, &4]).is_none());
+    }
+
+    #[test]
+    fn insert_unique_unchecked_works() {
+        let mut hmap = new_hmap();
+        assert_cached_entries(&hmap, &[]);
+        let value = hmap.insert_unique_unchecked(1, b'A');
+        assert_eq!(value, &b'A');
+        assert_cached_entries(
+            &hmap,
+            &[(1, InternalEntry::new(Some(b'A'), EntryState::Mutated))],
+        );
+        assert_eq!(hmap.get(&1), Some(&b'A'));
+    }
+
+    #[test]
+    fn extend_uses_insert_unique_unchecked() {
+        let hmap = <LazyHashMap<i32, u8, Blake2x256Hasher>>::from_iter(
+            ink_prelude::vec![(1, b'A'), (2, b'B'), (1, b'C')],
+        );
+        // Last write for a duplicated key wins, matching `Extend`'s usual
+        // overwrite semantics.
+        assert_eq!(hmap.get(&1), Some(&b'C'));
+        assert_eq!(hmap.get(&2), Some(&b'B'));
+    }
+
+    #[test]
+    fn retain_works() {
+        let mut hmap = new_hmap();
+        assert_eq!(hmap.put_get(&1, Some(b'A')), None);
+        assert_eq!(hmap.put_get(&2, Some(b'B')), None);
+        assert_eq!(hmap.put_get(&3, Some(b'C')), None);
+        assert_eq!(hmap.put_get(&4, None), None);
+        // Keep only odd keys.
+        hmap.retain(|key, _value| key % 2 == 1);
+        assert_cached_entries(
+            &hmap,
+            &[
+                (1, InternalEntry::new(Some(b'A'), EntryState::Mutated)),
+                (2, InternalEntry::new(None, EntryState::Mutated)),
+                (3, InternalEntry::new(Some(b'C'), EntryState::Mutated)),
+                (4, InternalEntry::new(None, EntryState::Preserved)),
+            ],
+        );
+        assert_eq!(hmap.get(&1), Some(&b'A'));
+        assert_eq!(hmap.get(&2), None);
+        assert_eq!(hmap.get(&3), Some(&b'C'));
+        assert_eq!(hmap.get(&4), None);
+    }
+
+    #[test]
+    fn drain_filter_works() {
+        let mut hmap = new_hmap();
+        assert_eq!(hmap.put_get(&1, Some(b'A')), None);
+        assert_eq!(hmap.put_get(&2, Some(b'B')), None);
+        assert_eq!(hmap.put_get(&3, Some(b'C')), None);
+        assert_eq!(hmap.put_get(&4, None), None);
+        // Drain only odd keys that carry a value.
+        let mut drained = hmap
+            .drain_filter(|key, _value| key % 2 == 1)
+            .collect::<ink_prelude::vec::Vec<_>>();
+        drained.sort();
+        assert_eq!(drained, [(1, b'A'), (3, b'C')]);
+        assert_eq!(hmap.get(&1), None);
+        assert_eq!(hmap.get(&2), Some(&b'B'));
+        assert_eq!(hmap.get(&3), None);
+        assert_eq!(hmap.get(&4), None);
+    }
+
     #[test]
     fn spread_layout_works() -> env::Result<()> {
         env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
@@ -1188,11 +2117,24 @@ mod tests {
             assert_eq!(hmap.put_get(&3, None), None);
             assert_eq!(hmap.put_get(&4, None), None);
             assert_cached_entries(&hmap, nothing_changed);
+            assert_eq!(hmap.dirty_count(), 2);
             // Push the lazy index map onto the contract storage and then load
             // another instance of it from the contract stoarge.
             // Then: Compare both instances to be equal.
             let root_key = Key::from([0x42; 32]);
             SpreadLayout::push_spread(&hmap, &mut KeyPtr::from(root_key));
+            // Entries that were pushed are no longer dirty, and untouched
+            // `Preserved` entries were never re-written to storage.
+            assert_eq!(hmap.dirty_count(), 0);
+            assert_cached_entries(
+                &hmap,
+                &[
+                    (1, InternalEntry::new(Some(b'A'), EntryState::Preserved)),
+                    (2, InternalEntry::new(Some(b'B'), EntryState::Preserved)),
+                    (3, InternalEntry::new(None, EntryState::Preserved)),
+                    (4, InternalEntry::new(None, EntryState::Preserved)),
+                ],
+            );
             let hmap2 =
                 <LazyHashMap<i32, u8, Blake2x256Hasher> as SpreadLayout>::pull_spread(
                     &mut KeyPtr::from(root_key),
@@ -1245,6 +2187,34 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn raw_entry_mut_from_key_hashed_nocheck_works() -> env::Result<()> {
+        env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+            // given
+            let root_key = Key::from([0x42; 32]);
+            let mut hmap = <LazyHashMap<i32, u8, Blake2x256Hasher>>::lazy(root_key);
+            let hashed_key = hmap.key_at(&1).expect("map is in lazy state");
+
+            // when
+            match hmap.raw_entry_mut().from_key_hashed_nocheck(hashed_key, 1) {
+                super::Entry::Occupied(_) => panic!("expected a vacant entry"),
+                super::Entry::Vacant(v) => {
+                    v.insert(b'A');
+                }
+            }
+
+            // then
+            assert_eq!(hmap.get(&1), Some(&b'A'));
+            // A subsequent lookup via the regular entry API must see the
+            // same, already-cached value.
+            match hmap.entry(1) {
+                super::Entry::Occupied(o) => assert_eq!(o.get(), &b'A'),
+                super::Entry::Vacant(_) => panic!("expected an occupied entry"),
+            }
+            Ok(())
+        })
+    }
 }
 
 #[cfg(test)]
@@ -1532,11 +2502,140 @@ mod entry_api_tests {
                     Occupied,
                     Vacant,
                 },
+                EntryRef,
                 LazyHashMap,
             },
         };
 
         gen_tests_for_backend!(LazyHashMap<u8, i32, Blake2x256Hasher>);
+
+        #[test]
+        fn entry_api_and_modify_persists_with_push_pull() -> env::Result<()> {
+            env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+                // given
+                let mut hmap1 = prefilled_hmap();
+                push_hmap(&hmap1);
+                let mut hmap2 = pull_hmap();
+
+                // when
+                hmap2.entry(b'B').and_modify(|e| *e += 1).or_insert(7);
+                push_hmap(&hmap2);
+
+                // then
+                // `and_modify` must mark the occupied entry as mutated, or
+                // else this push would have been skipped as not dirty and
+                // the mutation would be lost.
+                let hmap3 = pull_hmap();
+                assert_eq!(hmap3.get(&b'B'), Some(&24));
+                Ok(())
+            })
+        }
+
+        #[test]
+        fn entry_ref_api_vacant_works() {
+            // given
+            let mut hmap = LazyHashMap::<u8, i32, Blake2x256Hasher>::new();
+
+            // when
+            match hmap.entry_ref(&b'A') {
+                EntryRef::Occupied(_) => panic!(),
+                EntryRef::Vacant(v) => {
+                    assert_eq!(v.key(), &b'A');
+                    let val = v.insert(42);
+                    *val += 1;
+                }
+            }
+
+            // then
+            assert_eq!(hmap.get(&b'A'), Some(&43));
+        }
+
+        #[test]
+        fn entry_ref_api_occupied_works() {
+            // given
+            let mut hmap = prefilled_hmap();
+
+            // when
+            assert_eq!(hmap.get(&b'A'), Some(&13));
+            match hmap.entry_ref(&b'A') {
+                EntryRef::Occupied(mut o) => {
+                    assert_eq!(o.insert(15), 13);
+                }
+                EntryRef::Vacant(_) => panic!(),
+            }
+
+            // then
+            assert_eq!(hmap.get(&b'A'), Some(&15));
+        }
+
+        #[test]
+        fn entry_ref_api_and_modify_or_insert_works() {
+            // given
+            let mut hmap = prefilled_hmap();
+
+            // when
+            assert_eq!(hmap.get(&b'B'), Some(&23));
+            hmap.entry_ref(&b'B').and_modify(|v| *v += 1).or_insert(7);
+            hmap.entry_ref(&b'C').and_modify(|v| *v += 1).or_insert(7);
+
+            // then
+            assert_eq!(hmap.get(&b'B'), Some(&24));
+            assert_eq!(hmap.get(&b'C'), Some(&7));
+        }
+
+        #[test]
+        fn entry_ref_api_does_not_require_owned_key_unless_inserting() -> env::Result<()> {
+            env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+                // given
+                let mut hmap1 = prefilled_hmap();
+                push_hmap(&hmap1);
+                let mut hmap2 = pull_hmap();
+
+                // when
+                // Only querying an occupied entry through a borrowed key must
+                // never materialize an owned `u8` via `to_owned`.
+                hmap2.entry_ref(&b'A').and_modify(|v| *v += 1);
+                push_hmap(&hmap2);
+
+                // then
+                let hmap3 = pull_hmap();
+                assert_eq!(hmap3.get(&b'A'), Some(&14));
+                Ok(())
+            })
+        }
+
+        #[test]
+        fn occupied_entry_replace_entry_works() {
+            // given
+            let mut hmap = prefilled_hmap();
+
+            // when
+            let (old_key, old_value) = match hmap.entry(b'A') {
+                Entry::Occupied(o) => o.replace_entry(99),
+                Entry::Vacant(_) => panic!(),
+            };
+
+            // then
+            assert_eq!(old_key, b'A');
+            assert_eq!(old_value, 13);
+            assert_eq!(hmap.get(&b'A'), Some(&99));
+        }
+
+        #[test]
+        fn occupied_entry_replace_key_works() {
+            // given
+            let mut hmap = prefilled_hmap();
+
+            // when
+            let old_key = match hmap.entry(b'A') {
+                Entry::Occupied(o) => o.replace_key(),
+                Entry::Vacant(_) => panic!(),
+            };
+
+            // then
+            assert_eq!(old_key, b'A');
+            assert_eq!(hmap.get(&b'A'), Some(&13));
+        }
     }
 
     mod hashmap_backend {
@@ -12,22 +12,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::storage2::traits::{
-    clear_packed_root,
-    pull_packed_root_opt,
-    KeyPtr,
-    PackedLayout,
-    SpreadLayout,
+use crate::storage2::{
+    traits::{
+        clear_packed_root,
+        pull_packed_root_opt,
+        try_pull_packed_root_opt,
+        KeyPtr,
+        PackedLayout,
+        SpreadLayout,
+    },
+    PullForward,
+    PushForward,
 };
 use core::{
     cell::UnsafeCell,
     fmt,
     fmt::Debug,
+    mem::MaybeUninit,
     ptr::NonNull,
 };
 use ink_prelude::{
     boxed::Box,
     collections::BTreeMap,
+    vec::Vec,
 };
 use ink_primitives::Key;
 
@@ -123,19 +130,233 @@ impl<V> Default for LazyIndexMap<V> {
     }
 }
 
+use super::{
+    EntryState,
+    InternalEntry,
+    StorageError,
+};
+
+/// The id of a slot within an [`EntrySlab`].
+type SlotId = u32;
+
+/// The number of entry slots stored in a single [`EntrySlab`] page.
+///
+/// # Note
+///
+/// Chosen so that the slab grows in comparatively large, infrequent
+/// allocations instead of the one-allocation-per-entry scheme it replaces.
+const PAGE_LEN: usize = 64;
+
+/// A fixed-capacity, heap-allocated page of slab slots.
+///
+/// # Note
+///
+/// Boxed so that appending a new page never moves the already allocated
+/// pages around, which is what lets pointers into previously inserted slots
+/// stay valid for the lifetime of the slab.
+type Page<V> = Box<[MaybeUninit<InternalEntry<V>>; PAGE_LEN]>;
+
+/// Stores cached entries in fixed-capacity pages instead of behind one
+/// `Box` per entry.
+///
+/// # Note
+///
+/// Pages are only ever appended, never reallocated in place, so a pointer
+/// into a slot that has already been handed out stays valid across later
+/// insertions into other slots, preserving the same pointer-stability
+/// invariant the previous per-entry `Box` indirection provided for
+/// [`LazyIndexMap::lazily_load`].
+struct EntrySlab<V> {
+    /// The appended pages. Only ever grows.
+    pages: Vec<Page<V>>,
+    /// Ids of vacated slots, ready to be reused by a future insertion.
+    free: Vec<SlotId>,
+    /// The number of slots ever handed out across `pages`. Freeing a slot
+    /// does not decrease it, so it includes slots that are currently sitting
+    /// in `free` waiting to be reused.
+    len: u32,
+}
+
+impl<V> EntrySlab<V> {
+    /// Creates a new empty slab.
+    fn new() -> Self {
+        Self {
+            pages: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    fn page_of(slot: SlotId) -> usize {
+        slot as usize / PAGE_LEN
+    }
+
+    fn offset_of(slot: SlotId) -> usize {
+        slot as usize % PAGE_LEN
+    }
+
+    /// Inserts `entry` into the slab and returns the id of its slot.
+    fn insert(&mut self, entry: InternalEntry<V>) -> SlotId {
+        if let Some(slot) = self.free.pop() {
+            self.pages[Self::page_of(slot)][Self::offset_of(slot)] = MaybeUninit::new(entry);
+            return slot
+        }
+        let slot = self.len;
+        if Self::offset_of(slot) == 0 {
+            self.pages.push(Box::new(
+                // SAFETY: An array of `MaybeUninit` never requires
+                // initialization, regardless of `V`.
+                unsafe { MaybeUninit::uninit().assume_init() },
+            ));
+        }
+        self.pages[Self::page_of(slot)][Self::offset_of(slot)] = MaybeUninit::new(entry);
+        self.len += 1;
+        slot
+    }
+
+    /// Returns a pinned pointer to the entry in the given slot.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that `slot` was returned by a previous
+    /// call to [`EntrySlab::insert`] and has not since been passed to
+    /// [`EntrySlab::free`].
+    fn ptr(&self, slot: SlotId) -> NonNull<InternalEntry<V>> {
+        let page = &self.pages[Self::page_of(slot)];
+        // SAFETY: The slot is guaranteed by the caller to be occupied, so
+        // the `MaybeUninit` at this position is guaranteed to be init.
+        let entry = page[Self::offset_of(slot)].as_ptr() as *mut InternalEntry<V>;
+        unsafe { NonNull::new_unchecked(entry) }
+    }
+
+    /// Returns a shared reference to the entry in the given slot.
+    fn get(&self, slot: SlotId) -> &InternalEntry<V> {
+        unsafe { &*self.ptr(slot).as_ptr() }
+    }
+
+    /// Returns an exclusive reference to the entry in the given slot.
+    fn get_mut(&mut self, slot: SlotId) -> &mut InternalEntry<V> {
+        unsafe { &mut *self.ptr(slot).as_ptr() }
+    }
+
+    /// Vacates the given slot, dropping its entry and making the slot
+    /// available for reuse by a later [`EntrySlab::insert`].
+    #[allow(unused)]
+    fn free(&mut self, slot: SlotId) {
+        let page = &mut self.pages[Self::page_of(slot)];
+        unsafe { core::ptr::drop_in_place(page[Self::offset_of(slot)].as_mut_ptr()) };
+        self.free.push(slot);
+    }
+}
+
+impl<V> Drop for EntrySlab<V> {
+    fn drop(&mut self) {
+        for slot in 0..self.len {
+            if self.free.contains(&slot) {
+                // Already dropped by a previous call to `EntrySlab::free`.
+                continue
+            }
+            let page = &mut self.pages[Self::page_of(slot)];
+            unsafe { core::ptr::drop_in_place(page[Self::offset_of(slot)].as_mut_ptr()) };
+        }
+    }
+}
+
 /// The map for the contract storage entries.
 ///
 /// # Note
 ///
-/// We keep the whole entry in a `Box<T>` in order to prevent pointer
+/// The actual entries live in an [`EntrySlab`] and are addressed through a
+/// `BTreeMap<Index, SlotId>` indirection so that caching an entry does not
+/// require its own heap allocation, while still preventing pointer
 /// invalidation upon updating the cache through `&self` methods as in
 /// [`LazyIndexMap::get`].
-pub type EntryMap<V> = BTreeMap<Index, Box<Entry<V>>>;
+pub struct EntryMap<V> {
+    /// Maps a cell index to the slot holding its cached entry.
+    indices: BTreeMap<Index, SlotId>,
+    /// The paged storage backing the cached entries.
+    slab: EntrySlab<V>,
+}
 
-use super::{
-    Entry,
-    EntryState,
-};
+impl<V> EntryMap<V> {
+    /// Creates a new empty entry map.
+    fn new() -> Self {
+        Self {
+            indices: BTreeMap::new(),
+            slab: EntrySlab::new(),
+        }
+    }
+
+    /// Returns the number of cached entries.
+    fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns an iterator over the cached indices and their entries.
+    fn iter(&self) -> impl Iterator<Item = (&Index, &InternalEntry<V>)> + '_ {
+        let slab = &self.slab;
+        self.indices.iter().map(move |(index, &slot)| (index, slab.get(slot)))
+    }
+
+    /// Returns an iterator over the cached indices and their entries,
+    /// yielding an exclusive reference to each entry.
+    fn iter_mut(&mut self) -> impl Iterator<Item = (&Index, &mut InternalEntry<V>)> + '_ {
+        let slab = &mut self.slab;
+        self.indices
+            .iter()
+            .map(move |(index, &slot)| (index, slab.get_mut(slot)))
+    }
+
+    /// Inserts `entry` at `index`, reusing its slot if already cached.
+    fn insert(&mut self, index: Index, entry: InternalEntry<V>) {
+        match self.indices.get(&index) {
+            Some(&slot) => *self.slab.get_mut(slot) = entry,
+            None => {
+                let slot = self.slab.insert(entry);
+                self.indices.insert(index, slot);
+            }
+        }
+    }
+}
+
+/// A vacant entry of a [`LazyIndexMap`], ready for insertion.
+pub struct VacantEntry<'a, V>
+where
+    V: PackedLayout,
+{
+    /// A reference to the used `LazyIndexMap` instance.
+    base: &'a mut LazyIndexMap<V>,
+    /// The index of this entry.
+    index: Index,
+}
+
+/// An occupied entry of a [`LazyIndexMap`].
+pub struct OccupiedEntry<'a, V>
+where
+    V: PackedLayout,
+{
+    /// A reference to the used `LazyIndexMap` instance.
+    base: &'a mut LazyIndexMap<V>,
+    /// The index of this entry.
+    index: Index,
+}
+
+/// An entry of a [`LazyIndexMap`] for in-place manipulation of its value.
+///
+/// # Note
+///
+/// Mirrors the entry API of [`super::LazyHashMap`], letting callers avoid a
+/// `get` followed by a `put_get` for a read-modify-write access, which looks
+/// the cell up in the cache twice.
+pub enum Entry<'a, V>
+where
+    V: PackedLayout,
+{
+    /// A vacant entry, i.e. the cell is currently `None`.
+    Vacant(VacantEntry<'a, V>),
+    /// An occupied entry, i.e. the cell currently holds `Some(V)`.
+    Occupied(OccupiedEntry<'a, V>),
+}
 
 impl<V> LazyIndexMap<V> {
     /// Creates a new empty lazy map.
@@ -183,6 +404,19 @@ impl<V> LazyIndexMap<V> {
         unsafe { &mut *self.cached_entries.get() }
     }
 
+    /// Returns an exclusive reference to the underlying entries from a
+    /// `&self` receiver, for use by the flush path to transition pushed
+    /// entries back to [`EntryState::Preserved`].
+    ///
+    /// # Safety
+    ///
+    /// Just like [`LazyIndexMap::lazily_load`], this is only sound as long
+    /// as the returned reference does not outlive a call that itself never
+    /// hands out a conflicting reference into the cache.
+    unsafe fn entries_mut_via_ref(&self) -> &mut EntryMap<V> {
+        &mut *self.cached_entries.get()
+    }
+
     /// Puts the new value at the given index.
     ///
     /// # Note
@@ -197,7 +431,7 @@ impl<V> LazyIndexMap<V> {
     /// - If the decoding of the old element at the given index failed.
     pub fn put(&mut self, index: Index, new_value: Option<V>) {
         self.entries_mut()
-            .insert(index, Box::new(Entry::new(new_value, EntryState::Mutated)));
+            .insert(index, InternalEntry::new(new_value, EntryState::Mutated));
     }
 }
 
@@ -213,9 +447,20 @@ where
 
     fn push_spread(&self, ptr: &mut KeyPtr) {
         let offset_key = ptr.next_for::<Self>();
-        for (&index, entry) in self.entries().iter() {
+        // SAFETY: No other reference into the cache is alive while this
+        //         loop runs, so taking `&mut` here from a `&self` receiver
+        //         does not create a conflicting alias.
+        for (&index, entry) in unsafe { self.entries_mut_via_ref() }.iter_mut() {
+            if !entry.state().requires_flush() {
+                // Only entries that were actually mutated need to be
+                // written back: re-pushing an untouched, merely-read entry
+                // would just rewrite storage with the value it already
+                // holds.
+                continue
+            }
             let root_key = offset_key + index;
             entry.push_packed_root(&root_key);
+            entry.replace_state(EntryState::Preserved);
         }
     }
 
@@ -228,6 +473,30 @@ where
     }
 }
 
+impl<V> PullForward for LazyIndexMap<V> {
+    fn pull_forward(ptr: &mut KeyPtr) -> Self {
+        Self::lazy(ptr.next_for::<Self>())
+    }
+}
+
+impl<V> PushForward for LazyIndexMap<V>
+where
+    V: PackedLayout,
+{
+    fn push_forward(&self, ptr: &mut KeyPtr) {
+        let offset_key = ptr.next_for::<Self>();
+        // SAFETY: See the analogous loop in `SpreadLayout::push_spread`.
+        for (&index, entry) in unsafe { self.entries_mut_via_ref() }.iter_mut() {
+            if !entry.state().requires_flush() {
+                continue
+            }
+            let root_key = offset_key + index;
+            entry.push_packed_root(&root_key);
+            entry.replace_state(EntryState::Preserved);
+        }
+    }
+}
+
 impl<V> LazyIndexMap<V>
 where
     V: PackedLayout,
@@ -288,7 +557,7 @@ where
     /// a `*mut Entry<T>` pointer that allows for exclusive access. This is safe
     /// within internal use only and should never be given outside of the lazy
     /// entity for public `&self` methods.
-    unsafe fn lazily_load(&self, index: Index) -> NonNull<Entry<V>> {
+    unsafe fn lazily_load(&self, index: Index) -> NonNull<InternalEntry<V>> {
         // SAFETY: We have put the whole `cached_entries` mapping into an
         //         `UnsafeCell` because of this caching functionality. The
         //         trick here is that due to using `Box<T>` internally
@@ -299,21 +568,20 @@ where
         //         the caller site to underline that guarantees are given by the
         //         caller.
         #[allow(unused_unsafe)]
-        let cached_entries = unsafe { &mut *self.cached_entries.get() };
+        let cached = unsafe { &mut *self.cached_entries.get() };
         use ink_prelude::collections::btree_map::Entry as BTreeMapEntry;
-        match cached_entries.entry(index) {
-            BTreeMapEntry::Occupied(occupied) => {
-                NonNull::from(&mut **occupied.into_mut())
-            }
+        match cached.indices.entry(index) {
+            BTreeMapEntry::Occupied(occupied) => cached.slab.ptr(*occupied.get()),
             BTreeMapEntry::Vacant(vacant) => {
                 let value = self
                     .key_at(index)
                     .map(|key| pull_packed_root_opt::<V>(&key))
                     .unwrap_or(None);
-                NonNull::from(
-                    &mut **vacant
-                        .insert(Box::new(Entry::new(value, EntryState::Preserved))),
-                )
+                let slot = cached
+                    .slab
+                    .insert(InternalEntry::new(value, EntryState::Preserved));
+                vacant.insert(slot);
+                cached.slab.ptr(slot)
             }
         }
     }
@@ -329,7 +597,7 @@ where
     ///
     /// - If the lazy chunk is in an invalid state that forbids interaction.
     /// - If the lazy chunk is not in a state that allows lazy loading.
-    fn lazily_load_mut(&mut self, index: Index) -> &mut Entry<V> {
+    fn lazily_load_mut(&mut self, index: Index) -> &mut InternalEntry<V> {
         // SAFETY:
         // - Returning a `&mut Entry<T>` is safe because entities inside the
         //   cache are stored within a `Box` to not invalidate references into
@@ -337,6 +605,59 @@ where
         unsafe { &mut *self.lazily_load(index).as_ptr() }
     }
 
+    /// Lazily loads the value at the given index.
+    ///
+    /// # Note
+    ///
+    /// Mirrors [`LazyIndexMap::lazily_load`], except that a decode failure
+    /// does not panic: the entry is cached as [`EntryState::Poisoned`] so
+    /// that later lookups return the same error instead of re-attempting a
+    /// doomed decode, and the error is returned to the caller instead.
+    ///
+    /// # Safety
+    ///
+    /// Same safety contract as [`LazyIndexMap::lazily_load`].
+    unsafe fn try_lazily_load(
+        &self,
+        index: Index,
+    ) -> Result<NonNull<InternalEntry<V>>, StorageError> {
+        #[allow(unused_unsafe)]
+        let cached = unsafe { &mut *self.cached_entries.get() };
+        use ink_prelude::collections::btree_map::Entry as BTreeMapEntry;
+        match cached.indices.entry(index) {
+            BTreeMapEntry::Occupied(occupied) => {
+                let entry = cached.slab.ptr(*occupied.get());
+                unsafe { &*entry.as_ptr() }.try_value()?;
+                Ok(entry)
+            }
+            BTreeMapEntry::Vacant(vacant) => {
+                match self.key_at(index).map(|key| try_pull_packed_root_opt::<V>(&key)) {
+                    None => {
+                        let slot = cached
+                            .slab
+                            .insert(InternalEntry::new(None, EntryState::Preserved));
+                        vacant.insert(slot);
+                        Ok(cached.slab.ptr(slot))
+                    }
+                    Some(Ok(value)) => {
+                        let slot = cached
+                            .slab
+                            .insert(InternalEntry::new(value, EntryState::Preserved));
+                        vacant.insert(slot);
+                        Ok(cached.slab.ptr(slot))
+                    }
+                    Some(Err(error)) => {
+                        let slot = cached
+                            .slab
+                            .insert(InternalEntry::new(None, EntryState::Poisoned(error)));
+                        vacant.insert(slot);
+                        Err(error)
+                    }
+                }
+            }
+        }
+    }
+
     /// Returns a shared reference to the element at the given index if any.
     ///
     /// # Panics
@@ -347,7 +668,10 @@ where
         // SAFETY: Dereferencing the `*mut T` pointer into a `&T` is safe
         //         since this method's receiver is `&self` so we do not
         //         leak non-shared references to the outside.
-        unsafe { &*self.lazily_load(index).as_ptr() }.value().into()
+        unsafe { &*self.lazily_load(index).as_ptr() }
+            .try_value()
+            .expect("encountered poisoned storage entry")
+            .into()
     }
 
     /// Returns an exclusive reference to the element at the given index if any.
@@ -357,7 +681,42 @@ where
     /// - If the lazy chunk is in an invalid state that forbids interaction.
     /// - If the decoding of the element at the given index failed.
     pub fn get_mut(&mut self, index: Index) -> Option<&mut V> {
-        self.lazily_load_mut(index).value_mut().into()
+        let entry = self.lazily_load_mut(index);
+        entry
+            .try_value()
+            .expect("encountered poisoned storage entry");
+        if entry.value().is_some() {
+            entry.replace_state(EntryState::Mutated);
+        }
+        entry.value_mut().into()
+    }
+
+    /// Returns a shared reference to the element at the given index if any.
+    ///
+    /// # Errors
+    ///
+    /// If the decoding of the element at the given index failed. Unlike
+    /// [`LazyIndexMap::get`] this surfaces the error to the caller instead
+    /// of panicking, and never silently treats a poisoned cell as absent.
+    pub fn try_get(&self, index: Index) -> Result<Option<&V>, StorageError> {
+        let entry = unsafe { self.try_lazily_load(index)? };
+        Ok(unsafe { &*entry.as_ptr() }.value().into())
+    }
+
+    /// Returns an exclusive reference to the element at the given index if any.
+    ///
+    /// # Errors
+    ///
+    /// If the decoding of the element at the given index failed. Unlike
+    /// [`LazyIndexMap::get_mut`] this surfaces the error to the caller
+    /// instead of panicking, and never silently treats a poisoned cell as
+    /// absent.
+    pub fn try_get_mut(&mut self, index: Index) -> Result<Option<&mut V>, StorageError> {
+        let entry = unsafe { &mut *self.try_lazily_load(index)?.as_ptr() };
+        if entry.value().is_some() {
+            entry.replace_state(EntryState::Mutated);
+        }
+        Ok(entry.value_mut().into())
     }
 
     /// Puts the new value at the given index and returns the old value if any.
@@ -375,6 +734,23 @@ where
         self.lazily_load_mut(index).put(new_value)
     }
 
+    /// Puts the new value at the given index and returns the old value if any.
+    ///
+    /// # Errors
+    ///
+    /// If the decoding of the old element at the given index failed. Unlike
+    /// [`LazyIndexMap::put_get`] this surfaces the error to the caller
+    /// instead of panicking, and never silently discards a poisoned cell's
+    /// error by overwriting it.
+    pub fn try_put_get(
+        &mut self,
+        index: Index,
+        new_value: Option<V>,
+    ) -> Result<Option<V>, StorageError> {
+        let entry = unsafe { &mut *self.try_lazily_load(index)?.as_ptr() };
+        Ok(entry.put(new_value))
+    }
+
     /// Swaps the values at indices `x` and `y`.
     ///
     /// This operation tries to be as efficient as possible and reuse allocations.
@@ -398,7 +774,17 @@ where
                 &mut *self.lazily_load(x).as_ptr(),
                 &mut *self.lazily_load(y).as_ptr(),
             ) };
-        if loaded_x.value().is_none() && loaded_y.value().is_none() {
+        // Poisoned entries must never be treated as `None`: surface the
+        // decode error as a panic instead of silently swapping past it.
+        let (value_x, value_y) = (
+            loaded_x
+                .try_value()
+                .expect("encountered poisoned storage entry"),
+            loaded_y
+                .try_value()
+                .expect("encountered poisoned storage entry"),
+        );
+        if value_x.is_none() && value_y.is_none() {
             // Bail out since nothing has to be swapped if both values are `None`.
             return
         }
@@ -408,6 +794,133 @@ where
         loaded_y.replace_state(EntryState::Mutated);
         core::mem::swap(loaded_x.value_mut(), loaded_y.value_mut());
     }
+
+    /// Gets the given index's corresponding entry in the map for in-place
+    /// manipulation.
+    pub fn entry(&mut self, index: Index) -> Entry<V> {
+        // SAFETY: `lazily_load` guarantees to return a pointer to a pinned
+        //         entity so that the returned reference does not conflict
+        //         with the `&mut self` we hold here.
+        let loaded = unsafe { &*self.lazily_load(index).as_ptr() };
+        match loaded.value() {
+            Some(_) => Entry::Occupied(OccupiedEntry { base: self, index }),
+            None => Entry::Vacant(VacantEntry { base: self, index }),
+        }
+    }
+}
+
+impl<'a, V> Entry<'a, V>
+where
+    V: PackedLayout,
+{
+    /// Returns the index this entry is for.
+    pub fn key(&self) -> Index {
+        match self {
+            Entry::Occupied(entry) => entry.index,
+            Entry::Vacant(entry) => entry.index,
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting `default` if it is
+    /// vacant, and returns a mutable reference to the value in the entry.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of `default`
+    /// if it is vacant, and returns a mutable reference to the value in the
+    /// entry.
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential insert into the map.
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+impl<'a, V> VacantEntry<'a, V>
+where
+    V: PackedLayout,
+{
+    /// Returns the index this entry would be inserted at.
+    pub fn key(&self) -> Index {
+        self.index
+    }
+
+    /// Sets the value of the entry, and returns a mutable reference to it.
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.base.put(self.index, Some(value));
+        self.base
+            .get_mut(self.index)
+            .expect("put was just executed; qed")
+    }
+}
+
+impl<'a, V> OccupiedEntry<'a, V>
+where
+    V: PackedLayout,
+{
+    /// Returns the index of this entry.
+    pub fn key(&self) -> Index {
+        self.index
+    }
+
+    /// Gets a reference to the value in the entry.
+    pub fn get(&self) -> &V {
+        self.base
+            .get(self.index)
+            .expect("entry behind `OccupiedEntry` must always exist")
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    pub fn get_mut(&mut self) -> &mut V {
+        self.base
+            .get_mut(self.index)
+            .expect("entry behind `OccupiedEntry` must always exist")
+    }
+
+    /// Converts the entry into a mutable reference to its value, bound by
+    /// the entry's lifetime instead of its own.
+    pub fn into_mut(self) -> &'a mut V {
+        self.base
+            .get_mut(self.index)
+            .expect("entry behind `OccupiedEntry` must always exist")
+    }
+
+    /// Sets the value of the entry, and returns the entry's old value.
+    pub fn insert(&mut self, new_value: V) -> V {
+        self.base
+            .put_get(self.index, Some(new_value))
+            .expect("entry behind `OccupiedEntry` must always exist")
+    }
+
+    /// Takes the value out of the entry, and returns it.
+    pub fn remove(self) -> V {
+        self.base
+            .put_get(self.index, None)
+            .expect("entry behind `OccupiedEntry` must always exist")
+    }
 }
 
 #[cfg(test)]
@@ -428,7 +941,7 @@ mod tests {
         for (given, expected) in imap
             .entries()
             .iter()
-            .map(|(index, boxed_entry)| (*index, &**boxed_entry))
+            .map(|(index, entry)| (*index, entry))
             .zip(expected.iter().map(|(index, entry)| (*index, entry)))
         {
             assert_eq!(given, expected);
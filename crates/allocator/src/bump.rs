@@ -12,23 +12,41 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! A simple bump allocator.
+//! A bump allocator backed by a segregated free list.
 //!
-//! It's goal to have a much smaller footprint than the admittedly more full-featured `wee_alloc`
-//! allocator which is currently being used by ink! smart contracts.
+//! Its goal is to have a much smaller footprint than the admittedly more
+//! full-featured `wee_alloc` allocator which was previously used by ink!
+//! smart contracts.
 //!
-//! The heap which will be used by this allocator is a single page of memory, which in Wasm is
-//! 64KiB. We do not expect contracts to use more memory than this (for now), so we will throw an
-//! OOM error instead of requesting more memory.
+//! The heap starts out as a single Wasm page (64KiB) and grows on demand by
+//! requesting further pages from the host whenever the bump pointer would
+//! otherwise run past the end of the mapped memory. Blocks handed back via
+//! `dealloc` are not forgotten: they are pushed onto a free list bucketed by
+//! size class so that later allocations of compatible size and alignment can
+//! be served from recycled memory instead of growing the heap further.
 
-use core::alloc::{
-    GlobalAlloc,
-    Layout,
+use core::{
+    alloc::{
+        GlobalAlloc,
+        Layout,
+    },
+    ptr::NonNull,
 };
 
-/// A page in Wasm is 64KiB
+/// A page in Wasm is 64KiB.
 const PAGE_SIZE: usize = 64 * 1024;
 
+/// The smallest size class is 8 bytes, i.e. `1 << MIN_SIZE_CLASS_BITS`.
+const MIN_SIZE_CLASS_BITS: usize = 3;
+/// The largest size class is 2KiB, i.e. `1 << MAX_SIZE_CLASS_BITS`.
+///
+/// Allocations whose size or alignment does not fit into any size class are
+/// served directly from the bump pointer and are never recycled; we expect
+/// those to be rare and not worth tracking in the free list.
+const MAX_SIZE_CLASS_BITS: usize = 11;
+/// The number of size classes tracked by the free list.
+const NUM_SIZE_CLASSES: usize = MAX_SIZE_CLASS_BITS - MIN_SIZE_CLASS_BITS + 1;
+
 static mut INNER: InnerAlloc = InnerAlloc::new();
 
 pub struct BumpAllocator;
@@ -38,46 +56,147 @@ unsafe impl GlobalAlloc for BumpAllocator {
         INNER.alloc(layout)
     }
 
-    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        INNER.dealloc(ptr, layout)
+    }
+}
+
+/// An intrusive node overlaid onto a freed block's own memory.
+///
+/// # Safety
+///
+/// Only valid for as long as the block it is written into remains free; the
+/// node is overwritten the moment the block is handed back out by `alloc`.
+struct FreeListNode {
+    next: Option<NonNull<FreeListNode>>,
 }
 
 struct InnerAlloc {
-    /// Points to the start of the next available allocation.
+    /// Points to the start of the next bump allocation.
     ///
     /// If the heap hasn't been initialized yet this value will be `None`.
     next: Option<usize>,
+    /// The first address past the end of the memory currently mapped for
+    /// the heap. Grown in units of [`PAGE_SIZE`] as the bump pointer
+    /// catches up to it.
+    upper_limit: usize,
+    /// Segregated free lists, one per size class, each the head of an
+    /// intrusive singly-linked list of blocks of that class's size freed
+    /// by a prior `dealloc`.
+    free_lists: [Option<NonNull<FreeListNode>>; NUM_SIZE_CLASSES],
 }
 
 impl InnerAlloc {
     pub const fn new() -> Self {
-        Self { next: None }
+        Self {
+            next: None,
+            upper_limit: 0,
+            free_lists: [None; NUM_SIZE_CLASSES],
+        }
     }
 
-    unsafe fn alloc(&mut self, layout: Layout) -> *mut u8 {
-        // TODO: Figure out how to properly initalize the heap
-        let alloc_start = if let Some(start) = self.next {
-            start;
-        } else {
-            let prev_page = core::arch::wasm32::memory_grow(0, 1);
-            if prev_page == usize::max_value() {
-                panic!("OOM")
+    /// Returns the size class index able to serve an allocation of `size`
+    /// bytes aligned to `align`, or `None` if it is too large to be
+    /// tracked by the free list.
+    fn size_class(size: usize, align: usize) -> Option<usize> {
+        let required = size.max(align).max(1 << MIN_SIZE_CLASS_BITS);
+        let bits = required.next_power_of_two().trailing_zeros() as usize;
+        if bits > MAX_SIZE_CLASS_BITS {
+            return None
+        }
+        Some(bits - MIN_SIZE_CLASS_BITS)
+    }
+
+    /// Returns the block size served by the size class at `index`.
+    fn size_class_size(index: usize) -> usize {
+        1 << (index + MIN_SIZE_CLASS_BITS)
+    }
+
+    /// Grows the heap by whole pages until it can fit `size` bytes past
+    /// `start`, which must already be aligned to the caller's requirements.
+    ///
+    /// Returns `None` on genuine OOM, i.e. the host refused to grow memory
+    /// any further.
+    unsafe fn grow_heap(&mut self, start: usize, size: usize) -> Option<usize> {
+        let end = start.checked_add(size)?;
+        while end > self.upper_limit {
+            if core::arch::wasm32::memory_grow(0, 1) == usize::max_value() {
+                return None
+            }
+            self.upper_limit = self.upper_limit.checked_add(PAGE_SIZE)?;
+        }
+        Some(end)
+    }
+
+    /// Bumps the heap pointer to serve a fresh block of `size` bytes
+    /// aligned to `align`, growing the heap if necessary.
+    unsafe fn bump(&mut self, size: usize, align: usize) -> *mut u8 {
+        let next = match self.next {
+            Some(next) => next,
+            None => {
+                // First ever allocation: the heap starts out empty, so grow
+                // it by one page before handing out anything. `memory_grow`
+                // returns the *previous* page count, i.e. exactly where the
+                // module's already-reserved statics/stack region ends - that,
+                // not `0`, is the real start of free heap memory.
+                let prev_pages = core::arch::wasm32::memory_grow(0, 1);
+                if prev_pages == usize::max_value() {
+                    return core::ptr::null_mut()
+                }
+                let start = prev_pages * PAGE_SIZE;
+                self.upper_limit = start + PAGE_SIZE;
+                start
             }
-            prev_page.checked_mul(PAGE_SIZE).expect("OOM")
         };
 
-        let aligned_layout = layout.pad_to_align();
-        let alloc_end = match alloc_start.checked_add(aligned_layout.size()) {
+        let alloc_start = match round_up_to(next, align) {
+            Some(start) => start,
+            None => return core::ptr::null_mut(),
+        };
+        let alloc_end = match self.grow_heap(alloc_start, size) {
             Some(end) => end,
             None => return core::ptr::null_mut(),
         };
 
-        // Since we're using a single page as our entire heap if we exceed it we're effectively
-        // out-of-memory.
-        if alloc_end > PAGE_SIZE {
-            return core::ptr::null_mut()
-        }
-
         self.next = Some(alloc_end);
         alloc_start as *mut u8
     }
-}
\ No newline at end of file
+
+    unsafe fn alloc(&mut self, layout: Layout) -> *mut u8 {
+        let aligned_layout = layout.pad_to_align();
+        match Self::size_class(aligned_layout.size(), aligned_layout.align()) {
+            Some(index) => {
+                if let Some(mut node) = self.free_lists[index].take() {
+                    self.free_lists[index] = node.as_mut().next;
+                    return node.as_ptr() as *mut u8
+                }
+                self.bump(Self::size_class_size(index), Self::size_class_size(index))
+            }
+            None => self.bump(aligned_layout.size(), aligned_layout.align()),
+        }
+    }
+
+    unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+        let aligned_layout = layout.pad_to_align();
+        let index =
+            match Self::size_class(aligned_layout.size(), aligned_layout.align()) {
+                Some(index) => index,
+                // Blocks too large for any size class were bump-allocated
+                // directly and can't be recycled.
+                None => return,
+            };
+        let node = match NonNull::new(ptr as *mut FreeListNode) {
+            Some(node) => node,
+            None => return,
+        };
+        (*node.as_ptr()).next = self.free_lists[index];
+        self.free_lists[index] = Some(node);
+    }
+}
+
+/// Rounds `value` up to the nearest multiple of `align`, which must be a
+/// power of two. Returns `None` on overflow.
+fn round_up_to(value: usize, align: usize) -> Option<usize> {
+    debug_assert!(align.is_power_of_two());
+    value.checked_add(align - 1).map(|v| v & !(align - 1))
+}
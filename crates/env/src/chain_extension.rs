@@ -49,6 +49,29 @@ pub trait FromStatusCode: Sized {
     fn from_status_code(status_code: u32) -> Result<(), Self>;
 }
 
+/// Implemented by a chain extension method's declared return type to tell
+/// whether that type is itself a `Result<T, E>`.
+///
+/// # Note
+///
+/// The `#[ink::chain_extension]` procedural macro inspects a method's
+/// syntactic return type and supplies the resulting `IS_RESULT` value as
+/// the const generic argument to [`ChainExtensionMethodInstance::output`]
+/// directly, so this trait only ever needs to be implemented for the one
+/// unambiguous case below: a literal `Result<T, E>` type always answers
+/// `true`. Any other, opaque return type is `false`, but that cannot be
+/// expressed as a blanket impl without conflicting with the one below, so
+/// the macro never goes through this trait for that case; it splices the
+/// literal `false` instead.
+pub trait ReturnType<T> {
+    /// Is `true` if `Self` is itself a `Result<T, E>` type.
+    const IS_RESULT: bool;
+}
+
+impl<T, E> ReturnType<T> for Result<T, E> {
+    const IS_RESULT: bool = true;
+}
+
 /// A concrete instance of a chain extension method.
 ///
 /// This is a utility type used to drive the execution of a chain extension method call.
@@ -59,29 +82,27 @@ pub trait FromStatusCode: Sized {
 ///   All tuple types that may act as input parameters for the chain extension method are valid.
 ///   Examples include `()`, `i32`, `(u8, [u8; 5], i32)`, etc.
 /// - `O` represents the return (or output) type of the chain extension method.
-///   Only `Result<T, E>` or `NoResult<O>` generic types are allowed for `O`.
-///   The `Result<T, E>` type says that the chain extension method returns a `Result` type
-///   whereas the `NoResult<O>` type says that the chain extension method returns a non-`Result` value
-///   of type `O`.
 /// - `ErrorCode` represents how the chain extension method handles the chain extension's error code.
 ///   Only `HandleErrorCode<E>` and `IgnoreErrorCode` types are allowed that each say to either properly
 ///   handle or ignore the chain extension's error code respectively.
+/// - `IS_RESULT` says whether `O` is itself a `Result<T, E>` type. This is supplied once, by
+///   [`Self::output`], instead of being encoded as a second type state for `O`.
 ///
-/// The type states for type parameter `O` and `ErrorCode` represent 4 different states:
+/// The type state for `ErrorCode` together with the `IS_RESULT` const represent 4 different states:
 ///
 /// 1. The chain extension method makes use of the chain extension's error code: `HandleErrorCode(E)`
-///     - **A:** The chain extension method returns a `Result<T, E>` type.
-///     - **B:** The chain extension method returns a type `T` that is not a `Result` type: `NoResult<T>`
+///     - **A:** `IS_RESULT == true`: the chain extension method returns a `Result<T, E>` type.
+///     - **B:** `IS_RESULT == false`: the chain extension method returns a non-`Result` type `O`.
 /// 2. The chain extension ignores the chain extension's error code: `IgnoreErrorCode`
-///     - **A:** The chain extension method returns a `Result<T, E>` type.
-///     - **B:** The chain extension method returns a type `T` that is not a `Result` type: `NoResult<T>`
+///     - **A:** `IS_RESULT == true`: the chain extension method returns a `Result<T, E>` type.
+///     - **B:** `IS_RESULT == false`: the chain extension method returns a non-`Result` type `O`.
 #[derive(Debug)]
-pub struct ChainExtensionMethodInstance<I, O, ErrorCode> {
+pub struct ChainExtensionMethodInstance<I, O, ErrorCode, const IS_RESULT: bool> {
     func_id: u32,
     state: PhantomData<fn() -> (I, O, ErrorCode)>,
 }
 
-impl ChainExtensionMethodInstance<(), (), ()> {
+impl ChainExtensionMethodInstance<(), (), (), false> {
     /// Creates a new chain extension method instance.
     #[inline(always)]
     pub fn build(func_id: u32) -> Self {
@@ -90,59 +111,59 @@ impl ChainExtensionMethodInstance<(), (), ()> {
             state: Default::default(),
         }
     }
-}
 
-impl<O, ErrorCode> ChainExtensionMethodInstance<(), O, ErrorCode> {
-    /// Sets the input types of the chain extension method call to `I`.
+    /// Creates a new chain extension method instance for a method that
+    /// belongs to one of several extensions combined via
+    /// [`combine_extensions!`].
     ///
     /// # Note
     ///
-    /// `I` represents the input type of the chain extension method.
-    /// All tuple types that may act as input parameters for the chain extension method are valid.
-    /// Examples include `()`, `i32`, `(u8, [u8; 5], i32)`, etc.
+    /// `extension_id` selects the constituent extension and `func_id`
+    /// selects the method within it; the two are composed into the single
+    /// `u32` function ID that [`Self::build`] expects by placing
+    /// `extension_id` in the high 16 bits and `func_id` in the low 16
+    /// bits, so that methods from different constituent extensions can
+    /// never collide.
     #[inline(always)]
-    pub fn input<I>(self) -> ChainExtensionMethodInstance<I, O, ErrorCode>
-    where
-        I: scale::Encode,
-    {
-        ChainExtensionMethodInstance {
-            func_id: self.func_id,
-            state: Default::default(),
-        }
+    pub fn build_combined(extension_id: u16, func_id: u16) -> Self {
+        Self::build(((extension_id as u32) << 16) | func_id as u32)
     }
 }
 
-impl<I, ErrorCode> ChainExtensionMethodInstance<I, (), ErrorCode> {
-    /// Sets the output type of the chain extension method call to `Result<T, E>`.
+impl<O, ErrorCode, const IS_RESULT: bool> ChainExtensionMethodInstance<(), O, ErrorCode, IS_RESULT> {
+    /// Sets the input types of the chain extension method call to `I`.
     ///
     /// # Note
     ///
-    /// This indicates that the chain extension method return value might represent a failure.
+    /// `I` represents the input type of the chain extension method.
+    /// All tuple types that may act as input parameters for the chain extension method are valid.
+    /// Examples include `()`, `i32`, `(u8, [u8; 5], i32)`, etc.
     #[inline(always)]
-    pub fn output_result<T, E>(
-        self,
-    ) -> ChainExtensionMethodInstance<I, Result<T, E>, ErrorCode>
+    pub fn input<I>(self) -> ChainExtensionMethodInstance<I, O, ErrorCode, IS_RESULT>
     where
-        Result<T, E>: scale::Decode + From<scale::Error>,
+        I: scale::Encode,
     {
         ChainExtensionMethodInstance {
             func_id: self.func_id,
             state: Default::default(),
         }
     }
+}
 
+impl<I, ErrorCode> ChainExtensionMethodInstance<I, (), ErrorCode, false> {
     /// Sets the output type of the chain extension method call to `O`.
     ///
     /// # Note
     ///
-    /// The set returned type `O` must not be of type `Result<T, E>`.
-    /// When using the `#[ink::chain_extension]` procedural macro to define
-    /// this chain extension method the above constraint is enforced at
-    /// compile time.
+    /// `IS_RESULT` must be `true` if and only if `O` is itself a
+    /// `Result<T, E>` type; the `#[ink::chain_extension]` procedural macro
+    /// derives this from the method's syntactic return type and supplies
+    /// it here, so contract authors calling this directly are responsible
+    /// for keeping the two in sync.
     #[inline(always)]
-    pub fn output<O>(
+    pub fn output<O, const IS_RESULT: bool>(
         self,
-    ) -> ChainExtensionMethodInstance<I, state::NoResult<O>, ErrorCode>
+    ) -> ChainExtensionMethodInstance<I, O, ErrorCode, IS_RESULT>
     where
         O: scale::Decode,
     {
@@ -153,7 +174,7 @@ impl<I, ErrorCode> ChainExtensionMethodInstance<I, (), ErrorCode> {
     }
 }
 
-impl<I, O> ChainExtensionMethodInstance<I, O, ()> {
+impl<I, O, const IS_RESULT: bool> ChainExtensionMethodInstance<I, O, (), IS_RESULT> {
     /// Makes the chain extension method call assume that the returned status code is always success.
     ///
     /// # Note
@@ -166,7 +187,7 @@ impl<I, O> ChainExtensionMethodInstance<I, O, ()> {
     #[inline(always)]
     pub fn ignore_error_code(
         self,
-    ) -> ChainExtensionMethodInstance<I, O, state::IgnoreErrorCode> {
+    ) -> ChainExtensionMethodInstance<I, O, state::IgnoreErrorCode, IS_RESULT> {
         ChainExtensionMethodInstance {
             func_id: self.func_id,
             state: Default::default(),
@@ -182,7 +203,7 @@ impl<I, O> ChainExtensionMethodInstance<I, O, ()> {
     #[inline(always)]
     pub fn handle_error_code<ErrorCode>(
         self,
-    ) -> ChainExtensionMethodInstance<I, O, state::HandleErrorCode<ErrorCode>>
+    ) -> ChainExtensionMethodInstance<I, O, state::HandleErrorCode<ErrorCode>, IS_RESULT>
     where
         ErrorCode: FromStatusCode,
     {
@@ -206,18 +227,10 @@ pub mod state {
     pub struct HandleErrorCode<T> {
         error_code: PhantomData<fn() -> T>,
     }
-
-    /// Type state telling that the chain extension method deliberately does not return a `Result` type.
-    ///
-    /// Additionally this is enforced by the `#[ink::chain_extension]` proc. macro when used.
-    #[derive(Debug)]
-    pub struct NoResult<T> {
-        no_result: PhantomData<fn() -> T>,
-    }
 }
 
 impl<I, T, E, ErrorCode>
-    ChainExtensionMethodInstance<I, Result<T, E>, state::HandleErrorCode<ErrorCode>>
+    ChainExtensionMethodInstance<I, Result<T, E>, state::HandleErrorCode<ErrorCode>, true>
 where
     I: scale::Encode,
     T: scale::Decode,
@@ -248,7 +261,7 @@ where
     }
 }
 
-impl<I, T, E> ChainExtensionMethodInstance<I, Result<T, E>, state::IgnoreErrorCode>
+impl<I, T, E> ChainExtensionMethodInstance<I, Result<T, E>, state::IgnoreErrorCode, true>
 where
     I: scale::Encode,
     T: scale::Decode,
@@ -278,7 +291,7 @@ where
 }
 
 impl<I, O, ErrorCode>
-    ChainExtensionMethodInstance<I, state::NoResult<O>, state::HandleErrorCode<ErrorCode>>
+    ChainExtensionMethodInstance<I, O, state::HandleErrorCode<ErrorCode>, false>
 where
     I: scale::Encode,
     O: scale::Decode,
@@ -290,13 +303,13 @@ where
     ///
     /// # Errors
     ///
-    /// If the called chain extension method returned a non-successful error code.
-    ///
-    /// # Panics
-    ///
-    /// If the returned return value of the called chain extension method cannot be decoded into `O`.
+    /// - If the called chain extension method returned a non-successful error code.
+    /// - If the returned return value of the called chain extension method cannot be decoded into `O`.
     #[inline(always)]
-    pub fn call(self, input: &I) -> Result<O, ErrorCode> {
+    pub fn call(self, input: &I) -> Result<O, ErrorCode>
+    where
+        ErrorCode: From<scale::Error>,
+    {
         <EnvInstance as OnInstance>::on_instance(|instance| {
             EnvBackend::call_chain_extension::<I, O, ErrorCode, ErrorCode, _, _>(
                 instance,
@@ -304,16 +317,58 @@ where
                 input,
                 ErrorCode::from_status_code,
                 |output| {
-                    let decoded = <O as scale::Decode>::decode(&mut &output[..])
-                        .expect("encountered error while decoding chain extension method call return value");
-                    Ok(decoded)
+                    <O as scale::Decode>::decode(&mut &output[..]).map_err(ErrorCode::from)
+                },
+            )
+        })
+    }
+
+    /// Calls the chain extension method for case 1B described [here], keeping
+    /// a failed decode of the return value distinct from the extension's own
+    /// error code instead of folding both into a single `ErrorCode`.
+    ///
+    /// [here]: [`ChainExtensionMethodInstance`]
+    ///
+    /// # Note
+    ///
+    /// Use this over [`Self::call`] when `ErrorCode` has no meaningful
+    /// `From<scale::Error>` conversion, or when the caller wants to tell a
+    /// malformed/short output buffer apart from a genuine extension error
+    /// and fall back gracefully instead of treating both the same way.
+    #[inline(always)]
+    pub fn try_call(self, input: &I) -> Result<O, TryCallError<ErrorCode>> {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            EnvBackend::call_chain_extension::<I, O, TryCallError<ErrorCode>, TryCallError<ErrorCode>, _, _>(
+                instance,
+                self.func_id,
+                input,
+                |status_code| {
+                    ErrorCode::from_status_code(status_code).map_err(TryCallError::ErrorCode)
+                },
+                |output| {
+                    <O as scale::Decode>::decode(&mut &output[..]).map_err(TryCallError::Decode)
                 },
             )
         })
     }
 }
 
-impl<I, O> ChainExtensionMethodInstance<I, state::NoResult<O>, state::IgnoreErrorCode>
+/// The error returned by [`ChainExtensionMethodInstance::try_call`].
+///
+/// Unlike calling [`ChainExtensionMethodInstance::call`] and relying on
+/// `ErrorCode: From<scale::Error>`, this keeps a failed decode of the
+/// chain extension method's return value distinct from the extension's
+/// own, successfully decoded error code.
+#[derive(Debug)]
+pub enum TryCallError<ErrorCode> {
+    /// The chain extension method returned a non-successful error code.
+    ErrorCode(ErrorCode),
+    /// The chain extension method succeeded but its return value could not
+    /// be decoded into the expected output type.
+    Decode(scale::Error),
+}
+
+impl<I, O> ChainExtensionMethodInstance<I, O, state::IgnoreErrorCode, false>
 where
     I: scale::Encode,
     O: scale::Decode,
@@ -322,23 +377,69 @@ where
     ///
     /// [here]: [`ChainExtensionMethodInstance`]
     ///
-    /// # Panics
+    /// # Errors
     ///
     /// If the returned return value of the called chain extension method cannot be decoded into `O`.
     #[inline(always)]
-    pub fn call(self, input: &I) -> O {
+    pub fn call(self, input: &I) -> Result<O, scale::Error> {
         <EnvInstance as OnInstance>::on_instance(|instance| {
-            EnvBackend::call_chain_extension::<I, O, (), (), _, _>(
+            EnvBackend::call_chain_extension::<I, O, scale::Error, (), _, _>(
                 instance,
                 self.func_id,
                 input,
                 |_status_code| Ok(()),
-                |output| {
-                    let decoded = <O as scale::Decode>::decode(&mut &output[..])
-                        .expect("encountered error while decoding chain extension method call return value");
-                    Ok(decoded)
-                },
-            ).expect("assume the chain extension method never fails")
+                |output| <O as scale::Decode>::decode(&mut &output[..]),
+            )
         })
     }
 }
+
+/// Assigns each of several independently-defined `#[ink::chain_extension]`
+/// traits a distinct 16-bit slot, so a single contract environment can bind
+/// to more than one chain extension without their method IDs colliding.
+///
+/// # Note
+///
+/// This only produces the slot-to-extension mapping; pass a variant's
+/// [`extension_id`](combine_extensions) together with a method's own
+/// `#[ink(extension = N)]` ID to
+/// [`ChainExtensionMethodInstance::build_combined`] to obtain the combined
+/// function ID. The constituent extensions' definitions are not compared,
+/// so a combined set that links two incompatible versions of one
+/// constituent extension is not detected.
+///
+/// # Example
+///
+/// ```ignore
+/// combine_extensions! {
+///     pub enum ContractExtension {
+///         Psp22 = 0,
+///         Rand = 1,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! combine_extensions {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $( $variant:ident = $slot:literal ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        $vis enum $name {
+            $( $variant ),+
+        }
+
+        impl $name {
+            /// The 16-bit slot this constituent extension occupies in the
+            /// high bits of a combined chain extension method's function ID.
+            pub const fn extension_id(self) -> u16 {
+                match self {
+                    $( Self::$variant => $slot ),+
+                }
+            }
+        }
+    };
+}
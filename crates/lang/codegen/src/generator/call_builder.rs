@@ -21,6 +21,7 @@ use quote::{
     quote,
     quote_spanned,
 };
+use std::collections::HashMap;
 use syn::spanned::Spanned as _;
 
 /// Generates code for the call builder of the ink! smart contract.
@@ -46,14 +47,18 @@ impl GenerateCode for CallBuilder<'_> {
         let auxiliary_trait_impls = self.generate_auxiliary_trait_impls();
         let call_forwarder_impls = self.generate_call_forwarder_impls();
         let contract_trait_impls = self.generate_contract_trait_impls();
+        let inherent_trait_delegates = self.generate_inherent_trait_delegates();
+        let unique_trait_id_collision_asserts = self.generate_unique_trait_id_collision_asserts();
         quote! {
             const _: () = {
                 #call_builder_struct
                 #trait_impl
                 #auxiliary_trait_impls
                 #call_forwarder_impls
+                #unique_trait_id_collision_asserts
             };
             #contract_trait_impls
+            #inherent_trait_delegates
         }
     }
 }
@@ -178,7 +183,10 @@ impl CallBuilder<'_> {
     ) -> TokenStream2 {
         let span = impl_block.span();
         let unique_trait_id = self.generate_unique_trait_id(trait_path);
+        let layout_assert = self.generate_transparent_layout_assert(trait_path);
         quote_spanned!(span=>
+            #layout_assert
+
             #[doc(hidden)]
             impl ::ink_lang::TraitCallForwarderFor<#unique_trait_id> for CallBuilder {
                 type Forwarder = <<Self as Increment>::__ink_TraitInfo as ::ink_lang::TraitCallForwarder>::Forwarder;
@@ -191,6 +199,10 @@ impl CallBuilder<'_> {
                     // only an `AccountId` to a shared reference to another type of which
                     // we know that it also thinly wraps an `AccountId`.
                     // Furthermore both types use `repr(transparent)`.
+                    //
+                    // The transparent layout assertion generated above fails to
+                    // compile should this invariant ever break, instead of
+                    // silently producing UB.
                     unsafe {
                         &*(&self.account_id as *const AccountId as *const Self::Forwarder)
                     }
@@ -204,6 +216,10 @@ impl CallBuilder<'_> {
                     // only an `AccountId` to a exclusive reference to another type of which
                     // we know that it also thinly wraps an `AccountId`.
                     // Furthermore both types use `repr(transparent)`.
+                    //
+                    // The transparent layout assertion generated above fails to
+                    // compile should this invariant ever break, instead of
+                    // silently producing UB.
                     unsafe {
                         &mut *(&mut self.account_id as *mut AccountId as *mut Self::Forwarder)
                     }
@@ -231,6 +247,37 @@ impl CallBuilder<'_> {
         )
     }
 
+    /// Generates a compile-time assertion that `AccountId` and the trait's
+    /// `Forwarder` type share the same size and alignment.
+    ///
+    /// # Note
+    ///
+    /// This guards the otherwise unchecked transparent reference casts in
+    /// [`CallBuilder::generate_call_forwarder_for_trait_impl`]: should the
+    /// layout of `AccountId` or of a generated `Forwarder` type ever drift
+    /// apart, this fails to compile instead of silently producing UB at
+    /// runtime. Uses the classic zero-variant-array idiom for a `const`
+    /// assertion instead of `assert!`/`panic!` in a `const` context, since
+    /// the latter are not available on every Rust version this crate
+    /// supports.
+    fn generate_transparent_layout_assert(&self, trait_path: &syn::Path) -> TokenStream2 {
+        let span = self.contract.module().storage().span();
+        let unique_trait_id = self.generate_unique_trait_id(trait_path);
+        quote_spanned!(span=>
+            #[doc(hidden)]
+            const _: () = {
+                type __ink_Forwarder =
+                    <CallBuilder as ::ink_lang::TraitCallForwarderFor<#unique_trait_id>>::Forwarder;
+                #[allow(clippy::no_effect)]
+                let __ink_assert_transparent_layout: [(); 0] = [(); (
+                    ::core::mem::size_of::<AccountId>() == ::core::mem::size_of::<__ink_Forwarder>()
+                    && ::core::mem::align_of::<AccountId>() == ::core::mem::align_of::<__ink_Forwarder>()
+                ) as usize - 1];
+                let _ = __ink_assert_transparent_layout;
+            };
+        )
+    }
+
     /// Unsafely implements the required trait implementation marker.
     ///
     /// This marker only states that the ink! trait definition has been properly implemented.
@@ -268,6 +315,56 @@ impl CallBuilder<'_> {
         )
     }
 
+    /// Generates compile-time assertions that every pair of distinct ink!
+    /// traits implemented by this contract resolves to a different
+    /// [`::ink_lang::TraitUniqueId::ID`].
+    ///
+    /// # Note
+    ///
+    /// `TraitUniqueId::ID` is derived from only the first four bytes of a
+    /// trait's `verify_hash()`, so two sufficiently different ink! traits
+    /// can in principle still collide on the same 32-bit id. A collision
+    /// would make the call builder's `TraitCallForwarderFor<ID>`
+    /// implementation ambiguous between the two traits and silently
+    /// dispatch to whichever one's impl happens to be selected, so this
+    /// asserts that every pair of ids actually implemented by this contract
+    /// differs.
+    ///
+    /// Uses the same zero-variant-array idiom as the layout assertions in
+    /// [`CallBuilder::generate_transparent_layout_assert`] instead of
+    /// `compile_error!`, since whether two trait paths collide can only be
+    /// decided once their `ID`s are resolved, which Rust does not surface
+    /// to a macro as a value it could branch a `compile_error!` on.
+    fn generate_unique_trait_id_collision_asserts(&self) -> TokenStream2 {
+        let trait_paths = self
+            .contract
+            .module()
+            .impls()
+            .filter_map(|impl_block| impl_block.trait_path())
+            .collect::<Vec<_>>();
+        let span = self.contract.module().storage().span();
+        let asserts = trait_paths.iter().enumerate().flat_map(|(i, lhs)| {
+            trait_paths[i + 1..].iter().map(move |rhs| {
+                let lhs_id = self.generate_unique_trait_id(lhs);
+                let rhs_id = self.generate_unique_trait_id(rhs);
+                let message = format!(
+                    "ink! trait `{}` and ink! trait `{}` hash to the same \
+                     `TraitUniqueId`: rename one of them so that their call \
+                     builder forwarders cannot collide",
+                    quote! { #lhs },
+                    quote! { #rhs },
+                );
+                quote_spanned!(span=>
+                    #[doc = #message]
+                    const _: () = {
+                        let _ = [(); (#lhs_id != #rhs_id) as usize - 1];
+                    };
+                )
+            })
+        });
+        quote! { #( #asserts )* }
+    }
+
     /// Generates the actual ink! trait implementation for the generated call builder.
     fn generate_ink_trait_impl(
         &self,
@@ -451,4 +548,93 @@ impl CallBuilder<'_> {
             }
         )
     }
+
+    /// Generates an inherent `impl #storage_ident { .. }` block containing one
+    /// thin delegating method per ink! trait message implemented by the
+    /// contract, so that callers can invoke `contract.message()` directly
+    /// without bringing the ink! trait into scope.
+    ///
+    /// # Note
+    ///
+    /// A message identifier that is implemented by more than one of the
+    /// contract's ink! traits cannot be delegated unambiguously as an
+    /// inherent method: doing so for any one of them would shadow the
+    /// others. Such colliding identifiers are reported via `compile_error!`
+    /// instead of silently delegating to an arbitrary one of them.
+    fn generate_inherent_trait_delegates(&self) -> TokenStream2 {
+        let trait_messages = self
+            .contract
+            .module()
+            .impls()
+            .filter_map(|impl_block| {
+                impl_block
+                    .trait_path()
+                    .map(|trait_path| (trait_path, impl_block))
+            })
+            .flat_map(|(trait_path, impl_block)| {
+                impl_block
+                    .iter_messages()
+                    .map(move |message| (trait_path, message))
+            })
+            .collect::<Vec<_>>();
+        let mut occurrences = HashMap::<String, u32>::new();
+        for (_, message) in &trait_messages {
+            use ir::Callable as _;
+            *occurrences
+                .entry(message.ident().to_string())
+                .or_insert(0) += 1;
+        }
+        let storage_ident = self.contract.module().storage().ident();
+        let delegates = trait_messages.into_iter().map(|(trait_path, message)| {
+            use ir::Callable as _;
+            let message_ident = message.ident();
+            if occurrences[&message_ident.to_string()] > 1 {
+                let span = message.span();
+                return quote_spanned!(span=>
+                    compile_error!(concat!(
+                        "encountered ink! trait message `",
+                        stringify!(#message_ident),
+                        "` implemented by more than one ink! trait: \
+                         cannot generate an unambiguous inherent delegate for it",
+                    ));
+                )
+            }
+            self.generate_inherent_trait_delegate_for_message(
+                storage_ident,
+                trait_path,
+                message,
+            )
+        });
+        quote! {
+            impl #storage_ident {
+                #( #delegates )*
+            }
+        }
+    }
+
+    /// Generates a single inherent delegating method for one ink! trait
+    /// message implemented by the contract.
+    fn generate_inherent_trait_delegate_for_message(
+        &self,
+        storage_ident: &syn::Ident,
+        trait_path: &syn::Path,
+        message: ir::CallableWithSelector<ir::Message>,
+    ) -> TokenStream2 {
+        use ir::Callable as _;
+        let span = message.span();
+        let message_ident = message.ident();
+        let output_ident = self.output_ident(message_ident);
+        let mut_token = message.receiver().is_ref_mut().then(|| quote! { mut });
+        let input_bindings = message.inputs().map(|input| &input.pat).collect::<Vec<_>>();
+        let input_types = message.inputs().map(|input| &input.ty).collect::<Vec<_>>();
+        quote_spanned!(span=>
+            #[inline]
+            pub fn #message_ident(
+                & #mut_token self
+                #( , #input_bindings : #input_types )*
+            ) -> <#storage_ident as #trait_path>::#output_ident {
+                <Self as #trait_path>::#message_ident(self #( , #input_bindings )*)
+            }
+        )
+    }
 }
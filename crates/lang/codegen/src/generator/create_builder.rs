@@ -0,0 +1,164 @@
+// Copyright 2018-2021 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::GenerateCode;
+use derive_more::From;
+use heck::CamelCase;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{
+    format_ident,
+    quote,
+    quote_spanned,
+};
+use syn::spanned::Spanned as _;
+
+/// Generates code for the create builders of the ink! smart contract.
+///
+/// Mirrors [`super::CallBuilder`], except that it builds up an
+/// `instantiate_contract` call for each of the contract's
+/// `#[ink(constructor)]`s instead of an `invoke_contract`/`eval_contract`
+/// call for each of its messages.
+///
+/// For every constructor this emits a dedicated, type-safe builder struct
+/// (since distinct constructors generally take distinct arguments and
+/// therefore cannot share a single builder type) that is started with the
+/// code hash and the constructor's arguments, exposes `gas_limit` and
+/// `endowment` setters and terminates in `instantiate`, which fires the
+/// instantiation and returns the newly created contract through its
+/// [`::ink_env::call::FromAccountId`] implementation.
+#[derive(From)]
+pub struct CreateBuilder<'a> {
+    contract: &'a ir::Contract,
+}
+
+impl GenerateCode for CreateBuilder<'_> {
+    fn generate_code(&self) -> TokenStream2 {
+        self.contract
+            .module()
+            .impls()
+            // We are only interested in the contract's own inherent
+            // constructors, not in constructors defined by an implemented
+            // ink! trait: those are instantiated through that trait's own
+            // generated create builder instead.
+            .filter(|impl_block| impl_block.trait_path().is_none())
+            .flat_map(|impl_block| impl_block.iter_constructors())
+            .map(|constructor| self.generate_code_for_constructor(constructor))
+            .collect()
+    }
+}
+
+impl CreateBuilder<'_> {
+    /// Returns the identifier of the generated create builder struct for the
+    /// constructor with the given identifier.
+    fn builder_ident(&self, constructor_ident: &syn::Ident) -> syn::Ident {
+        format_ident!(
+            "{}CreateBuilder",
+            constructor_ident.to_string().to_camel_case()
+        )
+    }
+
+    /// Generates the create builder struct, its inherent impl block, and the
+    /// associated function on the contract's storage type that starts
+    /// building it, for a single `#[ink(constructor)]`.
+    fn generate_code_for_constructor(
+        &self,
+        constructor: ir::CallableWithSelector<ir::Constructor>,
+    ) -> TokenStream2 {
+        use ir::Callable as _;
+        let span = constructor.span();
+        let storage_ident = self.contract.module().storage().ident();
+        let constructor_ident = constructor.ident();
+        let builder_ident = self.builder_ident(constructor_ident);
+        let input_bindings = constructor
+            .inputs()
+            .map(|input| &input.pat)
+            .collect::<Vec<_>>();
+        let input_types = constructor
+            .inputs()
+            .map(|input| &input.ty)
+            .collect::<Vec<_>>();
+        quote_spanned!(span=>
+            /// A type-safe builder for instantiating a new
+            #[doc = concat!(" `", stringify!(#storage_ident), "` contract via its `", stringify!(#constructor_ident), "` constructor.")]
+            ///
+            /// # Note
+            ///
+            /// This does not yet support setting a deployment `salt`: doing
+            /// so requires threading a salt parameter through
+            /// `TypedEnv::instantiate_contract`, which is left for follow-up
+            /// work.
+            #[derive(Debug)]
+            pub struct #builder_ident {
+                create_params: ::ink_env::call::CreateBuilder<Environment, #storage_ident>,
+            }
+
+            impl #builder_ident {
+                /// Sets the maximum allowed gas costs for the instantiation.
+                #[inline]
+                pub fn gas_limit(mut self, gas_limit: u64) -> Self {
+                    self.create_params = self.create_params.gas_limit(gas_limit);
+                    self
+                }
+
+                /// Sets the endowment for the instantiated contract.
+                #[inline]
+                pub fn endowment(mut self, endowment: Balance) -> Self {
+                    self.create_params = self.create_params.endowment(endowment);
+                    self
+                }
+
+                /// Instantiates the contract and returns a reference to it.
+                ///
+                /// # Panics
+                ///
+                /// If the instantiation failed, e.g. because the code hash is
+                /// invalid, the constructor arguments were rejected, or the
+                /// instantiation trapped or ran out of gas.
+                #[inline]
+                pub fn instantiate(self) -> #storage_ident {
+                    self.create_params
+                        .fire()
+                        .map(|account_id| {
+                            <#storage_ident as ::ink_env::call::FromAccountId<Environment>>::from_account_id(account_id)
+                        })
+                        .expect("instantiation of the contract failed")
+                }
+            }
+
+            impl #storage_ident {
+                /// Returns a create builder for instantiating this contract
+                /// via its
+                #[doc = concat!(" `", stringify!(#constructor_ident), "`")]
+                /// constructor, so that the `code_hash` and `endowment` for
+                /// the to-be-instantiated contract can be set before firing
+                /// the instantiation.
+                #[allow(clippy::new_ret_no_self)]
+                pub fn #constructor_ident(
+                    code_hash: Hash
+                    #( , #input_bindings: #input_types )*
+                ) -> #builder_ident {
+                    let mut create_params = <::ink_env::call::CreateBuilder<
+                        Environment,
+                        #storage_ident,
+                    >>::instantiate(
+                        code_hash,
+                        ::ink_lang::selector_bytes!(stringify!(#constructor_ident)).into(),
+                    );
+                    #( create_params = create_params.push_arg(&#input_bindings); )*
+                    #builder_ident { create_params }
+                }
+            }
+        )
+    }
+}
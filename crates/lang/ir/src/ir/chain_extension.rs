@@ -15,7 +15,7 @@
 use crate::{error::ExtError, ir, ir::idents_lint};
 use core::convert::TryFrom;
 use proc_macro2::TokenStream as TokenStream2;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use syn::{spanned::Spanned as _, Result};
 use core::slice::Iter as SliceIter;
 
@@ -24,8 +24,27 @@ use core::slice::Iter as SliceIter;
 pub struct ChainExtension {
     item: syn::ItemTrait,
     methods: Vec<ChainExtensionMethod>,
+    /// The combined [`ExtensionId::into_u32`] dispatch value of every method
+    /// in [`Self::methods`], cached from the uniqueness check that parsing
+    /// already performs so that [`Self::contains_extension_id`] doesn't need
+    /// to re-scan [`Self::methods`] on every lookup.
+    method_ids: HashSet<u32>,
+    error_code: Option<syn::TraitItemType>,
+    /// The trait-level namespace from `#[ink::chain_extension(extension = N)]`,
+    /// `0` if the attribute was not given.
+    extension_id: u16,
 }
 
+/// The default error-code handling policy for the methods of an ink! chain
+/// extension, in the absence of a per-method override.
+///
+/// # Note
+///
+/// Matches the existing `handle_error_code`/`ignore_error_code` choice on
+/// `ChainExtensionMethodInstance`: `true` routes a method's call through
+/// `handle_error_code`, `false` through `ignore_error_code`.
+const DEFAULT_HANDLE_STATUS: bool = true;
+
 impl ChainExtension {
     /// Returns the Rust attributes of the ink! chain extension.
     pub fn attrs(&self) -> Vec<syn::Attribute> {
@@ -48,6 +67,53 @@ impl ChainExtension {
     pub fn iter_methods(&self) -> SliceIter<ChainExtensionMethod> {
         self.methods.iter()
     }
+
+    /// Returns an iterator over this chain extension's methods in ascending
+    /// order of their combined [`ExtensionId::into_u32`] dispatch value,
+    /// rather than the declaration order [`Self::iter_methods`] preserves.
+    ///
+    /// # Note
+    ///
+    /// Lets codegen and tooling query the method set without re-deriving
+    /// the ordering guarantees parsing already established: uniqueness of
+    /// every id is already enforced by [`ChainExtension::analyse_items`].
+    pub fn methods_sorted_by_id(&self) -> impl Iterator<Item = &ChainExtensionMethod> {
+        let mut sorted: Vec<&ChainExtensionMethod> = self.methods.iter().collect();
+        sorted.sort_by_key(|method| method.id().into_u32());
+        sorted.into_iter()
+    }
+
+    /// Returns `true` if this chain extension declares a method whose
+    /// combined [`ExtensionId::into_u32`] dispatch value equals `id`.
+    pub fn contains_extension_id(&self, id: u32) -> bool {
+        self.method_ids.contains(&id)
+    }
+
+    /// Returns the `ErrorCode` associated type of the ink! chain extension,
+    /// if one was declared.
+    ///
+    /// # Note
+    ///
+    /// This is the reserved `type ErrorCode;` associated type: the error into
+    /// which a method's returned `u32` status code is decoded whenever that
+    /// method has not opted out of status handling via
+    /// `#[ink(handle_status = false)]`.
+    pub fn error_code(&self) -> Option<&syn::TraitItemType> {
+        self.error_code.as_ref()
+    }
+
+    /// Returns the trait-level namespace this chain extension's methods are
+    /// folded into, as declared via `#[ink::chain_extension(extension = N)]`.
+    ///
+    /// # Note
+    ///
+    /// This is `0` if the attribute was not given. It becomes the upper 16
+    /// bits of every one of this chain extension's [`ExtensionId`]s, so that
+    /// several independently authored chain extensions can be combined into
+    /// the same runtime without their methods' function ids colliding.
+    pub fn extension_id(&self) -> u16 {
+        self.extension_id
+    }
 }
 
 /// An ink! chain extension method.
@@ -55,6 +121,7 @@ impl ChainExtension {
 pub struct ChainExtensionMethod {
     item: syn::TraitItemMethod,
     id: ExtensionId,
+    handle_status: bool,
 }
 
 impl ChainExtensionMethod {
@@ -80,63 +147,378 @@ impl ChainExtensionMethod {
         &self.item.sig
     }
 
+    /// Returns an iterator over the method's input arguments, for codegen
+    /// and tooling that needs to reflect over the chain extension method's
+    /// wire ABI.
+    pub fn inputs(&self) -> impl Iterator<Item = &syn::PatType> {
+        self.item.sig.inputs.iter().filter_map(|arg| match arg {
+            syn::FnArg::Typed(pat_type) => Some(pat_type),
+            syn::FnArg::Receiver(_) => None,
+        })
+    }
+
+    /// Returns the method's return type.
+    pub fn output(&self) -> &syn::ReturnType {
+        &self.item.sig.output
+    }
+
     /// Returns the unique ID of the chain extension method.
     pub fn id(&self) -> ExtensionId {
         self.id
     }
+
+    /// Returns `true` if this chain extension method should handle the chain
+    /// extension's error code, or `false` if it should be ignored.
+    ///
+    /// # Note
+    ///
+    /// This is `#[ink(handle_status = flag: bool)]` on the method itself if
+    /// present, falling back to the chain extension trait's own
+    /// `#[ink(handle_status = flag: bool)]` default, falling back in turn to
+    /// `true` if neither specifies it.
+    ///
+    /// Whenever this is `true`, [`ChainExtension::try_from`] already requires
+    /// the chain extension to declare an `ErrorCode` associated type,
+    /// independently of whatever this method's declared return type looks
+    /// like: a `false` method never decodes the status code in the first
+    /// place, so it never needs `ErrorCode` no matter its return type.
+    pub fn handle_status(&self) -> bool {
+        self.handle_status
+    }
 }
 
 /// The unique ID of an ink! chain extension method.
 ///
 /// # Note
 ///
-/// The ink! attribute `#[ink(extension = N: u32)]` for chain extension methods.
-///
-/// Has a `func_id` extension ID to identify the associated chain extension method.
+/// Namespaces the per-method `#[ink(extension = M: u32)]` function id under
+/// the chain extension trait's own `#[ink::chain_extension(extension = N)]`
+/// id, so that the runtime can dispatch a single flat `u32` across several
+/// independently authored chain extensions without their methods' function
+/// ids colliding. The dispatched value folds both halves together as
+/// `(extension_id << 16) | function_id`, matching how production runtimes
+/// partition the chain extension id space per pallet.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ExtensionId {
-    index: u32,
+    extension_id: u16,
+    function_id: u16,
 }
 
 impl ExtensionId {
-    /// Creates a new chain extension method ID from the given `u32`.
+    /// Creates a new extension id from its trait-level namespace and
+    /// per-method function id.
+    pub fn new(extension_id: u16, function_id: u16) -> Self {
+        Self {
+            extension_id,
+            function_id,
+        }
+    }
+
+    /// Creates a new extension id from a raw per-method function id alone,
+    /// truncated to 16 bits, leaving the trait-level namespace at `0`.
     pub fn from_u32(index: u32) -> Self {
-        Self { index }
+        Self {
+            extension_id: 0,
+            function_id: index as u16,
+        }
+    }
+
+    /// Returns the trait-level namespace component of the extension id.
+    pub fn extension_id(self) -> u16 {
+        self.extension_id
     }
 
-    /// Returns the underlying raw `u32` index.
+    /// Returns the per-method function id component of the extension id.
+    pub fn function_id(self) -> u16 {
+        self.function_id
+    }
+
+    /// Returns the combined `u32` that the runtime actually dispatches on,
+    /// as `(extension_id << 16) | function_id`.
     pub fn into_u32(self) -> u32 {
-        self.index
+        ((self.extension_id as u32) << 16) | (self.function_id as u32)
+    }
+}
+
+/// A stable, machine-greppable diagnostic code for an error raised while
+/// analysing an ink! chain extension, paired with its message template.
+///
+/// # Note
+///
+/// Mirrors `rustc`'s own `EXXXX` codes: once published a code is never
+/// reassigned to a different diagnostic, so it stays meaningful in bug
+/// reports, editor integrations, and `assert_ink_chain_extension_eq_err!`
+/// tests even as the prose around it gets reworded.
+/// [`ChainExtensionErrorCode::render`] is what every error raised by
+/// [`ChainExtension::analyse_properties`], [`ChainExtension::analyse_items`],
+/// [`ChainExtension::analyse_methods`], and
+/// [`ChainExtension::analyse_chain_extension_method`] is actually returned
+/// as.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ChainExtensionErrorCode {
+    Unsafe,
+    Auto,
+    Generic,
+    NotPublic,
+    Supertraits,
+    AssocConst,
+    Macro,
+    Verbatim,
+    UnknownItem,
+    DuplicateExtensionId,
+    DefaultImpl,
+    ConstMethod,
+    AsyncMethod,
+    UnsafeMethod,
+    ExplicitAbi,
+    Variadic,
+    GenericMethod,
+    UnsupportedMethodAttribute,
+    MissingExtensionAttribute,
+    SelfReceiver,
+    HandleStatusMissingReturnType,
+    FunctionIdTooLarge,
+    ReferenceType,
+    PointerType,
+    ImplTraitType,
+    LifetimeType,
+    DuplicateMethodName,
+}
+
+impl ChainExtensionErrorCode {
+    /// All variants, for the code-uniqueness check in
+    /// `chain_extension_error_codes_are_unique`.
+    #[cfg(test)]
+    const ALL: &'static [Self] = &[
+        Self::Unsafe,
+        Self::Auto,
+        Self::Generic,
+        Self::NotPublic,
+        Self::Supertraits,
+        Self::AssocConst,
+        Self::Macro,
+        Self::Verbatim,
+        Self::UnknownItem,
+        Self::DuplicateExtensionId,
+        Self::DefaultImpl,
+        Self::ConstMethod,
+        Self::AsyncMethod,
+        Self::UnsafeMethod,
+        Self::ExplicitAbi,
+        Self::Variadic,
+        Self::GenericMethod,
+        Self::UnsupportedMethodAttribute,
+        Self::MissingExtensionAttribute,
+        Self::SelfReceiver,
+        Self::HandleStatusMissingReturnType,
+        Self::FunctionIdTooLarge,
+        Self::ReferenceType,
+        Self::PointerType,
+        Self::ImplTraitType,
+        Self::LifetimeType,
+        Self::DuplicateMethodName,
+    ];
+
+    /// Returns the diagnostic's stable `ink-CE-NNN` code.
+    fn code(self) -> &'static str {
+        match self {
+            Self::Unsafe => "ink-CE-001",
+            Self::Auto => "ink-CE-002",
+            Self::Generic => "ink-CE-003",
+            Self::NotPublic => "ink-CE-004",
+            Self::Supertraits => "ink-CE-005",
+            Self::AssocConst => "ink-CE-006",
+            Self::Macro => "ink-CE-007",
+            Self::Verbatim => "ink-CE-008",
+            Self::UnknownItem => "ink-CE-009",
+            Self::DuplicateExtensionId => "ink-CE-010",
+            Self::DefaultImpl => "ink-CE-011",
+            Self::ConstMethod => "ink-CE-012",
+            Self::AsyncMethod => "ink-CE-013",
+            Self::UnsafeMethod => "ink-CE-014",
+            Self::ExplicitAbi => "ink-CE-015",
+            Self::Variadic => "ink-CE-016",
+            Self::GenericMethod => "ink-CE-017",
+            Self::UnsupportedMethodAttribute => "ink-CE-018",
+            Self::MissingExtensionAttribute => "ink-CE-019",
+            Self::SelfReceiver => "ink-CE-020",
+            Self::HandleStatusMissingReturnType => "ink-CE-021",
+            Self::FunctionIdTooLarge => "ink-CE-022",
+            Self::ReferenceType => "ink-CE-023",
+            Self::PointerType => "ink-CE-024",
+            Self::ImplTraitType => "ink-CE-025",
+            Self::LifetimeType => "ink-CE-026",
+            Self::DuplicateMethodName => "ink-CE-027",
+        }
+    }
+
+    /// Returns the diagnostic's message template.
+    fn message(self) -> &'static str {
+        match self {
+            Self::Unsafe => "ink! chain extensions cannot be unsafe",
+            Self::Auto => {
+                "ink! chain extensions cannot be automatically implemented traits"
+            }
+            Self::Generic => "ink! chain extensions must not be generic",
+            Self::NotPublic => "ink! chain extensions must have public visibility",
+            Self::Supertraits => {
+                "ink! chain extensions with supertraits are not supported, yet"
+            }
+            Self::AssocConst => {
+                "associated constants in ink! chain extensions are not supported, yet"
+            }
+            Self::Macro => "macros in ink! chain extensions are not supported",
+            Self::Verbatim => "encountered unsupported item in ink! chain extensions",
+            Self::UnknownItem => {
+                "encountered unknown or unsupported item in ink! chain extensions"
+            }
+            Self::DuplicateExtensionId => "duplicate extension id `N` here",
+            Self::DefaultImpl => {
+                "ink! chain extension methods with default implementations are not supported"
+            }
+            Self::ConstMethod => "const ink! chain extension methods are not supported",
+            Self::AsyncMethod => "async ink! chain extension methods are not supported",
+            Self::UnsafeMethod => "unsafe ink! chain extension methods are not supported",
+            Self::ExplicitAbi => {
+                "ink! chain extension methods with non default ABI are not supported"
+            }
+            Self::Variadic => "variadic ink! chain extension methods are not supported",
+            Self::GenericMethod => {
+                "generic ink! chain extension methods are not supported"
+            }
+            Self::UnsupportedMethodAttribute => {
+                "encountered unsupported ink! attribute for ink! chain extension method. \
+                 expected #[ink(extension = N: usize)] attribute"
+            }
+            Self::MissingExtensionAttribute => {
+                "missing #[ink(extension = N: usize)] flag on ink! chain extension method"
+            }
+            Self::SelfReceiver => {
+                "ink! chain extension method must not have a `self` receiver"
+            }
+            Self::HandleStatusMissingReturnType => {
+                "ink! chain extension method declares #[ink(handle_status = ...)] but \
+                 has no return type for a status code to be decoded into"
+            }
+            Self::FunctionIdTooLarge => {
+                "the `M` in #[ink(extension = M)] must fit into a 16-bit function id, \
+                 since it is namespaced under the chain extension's own `extension_id` \
+                 as `(extension_id << 16) | function_id`"
+            }
+            Self::ReferenceType => {
+                "ink! chain extension method signatures must not use reference types: \
+                 every argument and the return type must be an owned, SCALE-encodable type"
+            }
+            Self::PointerType => {
+                "ink! chain extension method signatures must not use raw pointer types: \
+                 every argument and the return type must be an owned, SCALE-encodable type"
+            }
+            Self::ImplTraitType => {
+                "ink! chain extension method signatures must not use `impl Trait`: \
+                 every argument and the return type must be a concrete, SCALE-encodable type"
+            }
+            Self::LifetimeType => {
+                "ink! chain extension method signatures must not carry explicit lifetimes: \
+                 every argument and the return type must be an owned, SCALE-encodable type"
+            }
+            Self::DuplicateMethodName => "duplicate definitions with name `N`",
+        }
+    }
+
+    /// Renders this diagnostic as `"[code] message"`.
+    fn render(self) -> String {
+        format!("[{}] {}", self.code(), self.message())
+    }
+}
+
+/// The parsed `#[ink::chain_extension(extension = N)]` trait-level
+/// configuration.
+struct ExtensionIdConfig {
+    extension_id: u16,
+}
+
+impl syn::parse::Parse for ExtensionIdConfig {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+        if ident != "extension" {
+            return Err(format_err_spanned!(
+                ident,
+                "unknown configuration argument for #[ink::chain_extension]; \
+                 expected `extension = N: u16`"
+            ))
+        }
+        input.parse::<syn::Token![=]>()?;
+        let value = input.parse::<syn::LitInt>()?;
+        let extension_id = value.base10_parse::<u16>().map_err(|_| {
+            format_err_spanned!(
+                value,
+                "the `N` in `extension = N` for #[ink::chain_extension] must fit into a `u16`"
+            )
+        })?;
+        Ok(Self { extension_id })
     }
 }
 
 impl TryFrom<syn::ItemTrait> for ChainExtension {
     type Error = syn::Error;
 
+    /// Analyses a bare trait with no trait-level `extension` namespace, i.e.
+    /// as if written `#[ink::chain_extension]` with no arguments.
     fn try_from(
         item_trait: syn::ItemTrait,
     ) -> core::result::Result<Self, Self::Error> {
-        idents_lint::ensure_no_ink_identifiers(&item_trait)?;
-        Self::analyse_properties(&item_trait)?;
-        let methods = Self::analyse_items(&item_trait)?;
-        Ok(Self {
-            item: item_trait,
-            methods,
-        })
+        Self::from_item_trait(item_trait, 0)
     }
 }
 
 impl ChainExtension {
     /// Returns `Ok` if the trait matches all requirements for an ink! chain extension.
     pub fn new(attr: TokenStream2, input: TokenStream2) -> Result<Self> {
-        if !attr.is_empty() {
-            return Err(format_err_spanned!(
-                attr,
-                "unexpected attribute input for ink! chain extension"
-            ))
-        }
+        let extension_id = Self::analyse_extension_id(attr)?;
         let item_trait = syn::parse2::<syn::ItemTrait>(input)?;
-        ChainExtension::try_from(item_trait)
+        Self::from_item_trait(item_trait, extension_id)
+    }
+
+    /// Parses the trait-level `#[ink::chain_extension(extension = N)]`
+    /// configuration, returning `0` if no attribute input was given.
+    ///
+    /// # Errors
+    ///
+    /// - If the attribute input is not of the shape `extension = N`.
+    /// - If `N` does not fit into a `u16`.
+    fn analyse_extension_id(attr: TokenStream2) -> Result<u16> {
+        if attr.is_empty() {
+            return Ok(0)
+        }
+        syn::parse2::<ExtensionIdConfig>(attr).map(|config| config.extension_id)
+    }
+
+    /// Analyses the trait and its items, folding the given trait-level
+    /// `extension_id` namespace into every one of its methods' ids.
+    fn from_item_trait(item_trait: syn::ItemTrait, extension_id: u16) -> Result<Self> {
+        idents_lint::ensure_no_ink_identifiers(&item_trait)?;
+        Self::analyse_properties(&item_trait)?;
+        let handle_status_default = Self::analyse_handle_status_default(&item_trait)?;
+        let error_code = Self::analyse_error_code(&item_trait)?;
+        let methods = Self::analyse_items(&item_trait, handle_status_default, extension_id)?;
+        if error_code.is_none() {
+            if let Some(method) = methods.iter().find(|method| method.handle_status()) {
+                return Err(format_err_spanned!(
+                    method.item,
+                    "ink! chain extension method relies on status code handling \
+                     but the chain extension does not declare a `type ErrorCode;` \
+                     associated type to decode it into"
+                ))
+            }
+        }
+        let method_ids = methods.iter().map(|method| method.id().into_u32()).collect();
+        Ok(Self {
+            item: item_trait,
+            methods,
+            method_ids,
+            error_code,
+            extension_id,
+        })
     }
 
     /// Analyses the properties of the ink! chain extension.
@@ -151,31 +533,31 @@ impl ChainExtension {
         if let Some(unsafety) = &item_trait.unsafety {
             return Err(format_err_spanned!(
                 unsafety,
-                "ink! chain extensions cannot be unsafe"
+                ChainExtensionErrorCode::Unsafe.render()
             ))
         }
         if let Some(auto) = &item_trait.auto_token {
             return Err(format_err_spanned!(
                 auto,
-                "ink! chain extensions cannot be automatically implemented traits"
+                ChainExtensionErrorCode::Auto.render()
             ))
         }
         if !item_trait.generics.params.is_empty() {
             return Err(format_err_spanned!(
                 item_trait.generics.params,
-                "ink! chain extensions must not be generic"
+                ChainExtensionErrorCode::Generic.render()
             ))
         }
         if !matches!(item_trait.vis, syn::Visibility::Public(_)) {
             return Err(format_err_spanned!(
                 item_trait.vis,
-                "ink! chain extensions must have public visibility"
+                ChainExtensionErrorCode::NotPublic.render()
             ))
         }
         if !item_trait.supertraits.is_empty() {
             return Err(format_err_spanned!(
                 item_trait.supertraits,
-                "ink! chain extensions with supertraits are not supported, yet"
+                ChainExtensionErrorCode::Supertraits.render()
             ))
         }
         Ok(())
@@ -187,7 +569,9 @@ impl ChainExtension {
     ///
     /// - If the trait contains an unsupported trait item such as
     ///     - associated constants (`const`)
-    ///     - associated types (`type`)
+    ///     - associated types (`type`) other than the single reserved
+    ///       `type ErrorCode;`, which is validated separately by
+    ///       [`ChainExtension::analyse_error_code`]
     ///     - macros definitions or usages
     ///     - unknown token sequences (verbatims)
     ///     - methods with default implementations
@@ -200,56 +584,96 @@ impl ChainExtension {
     ///
     /// The input Rust trait item is going to be replaced with a concrete chain extension type definition
     /// as a result of this proc. macro invocation.
+    ///
+    /// Both the id-overlap and the name-overlap checks already detect
+    /// collisions on insertion into a `HashMap`, so this single pass stays
+    /// linear in the number of methods even for large, macro-generated
+    /// chain extension traits: there is no quadratic pairwise comparison
+    /// here to fall back from above some threshold.
     fn analyse_items(
         item_trait: &syn::ItemTrait,
+        handle_status_default: bool,
+        extension_id: u16,
     ) -> Result<Vec<ChainExtensionMethod>> {
         let mut methods = Vec::new();
         let mut seen_ids = HashMap::new();
+        let mut seen_names: HashMap<String, proc_macro2::Span> = HashMap::new();
         for trait_item in &item_trait.items {
             match trait_item {
                 syn::TraitItem::Const(const_trait_item) => {
                     return Err(format_err_spanned!(
                         const_trait_item,
-                        "associated constants in ink! chain extensions are not supported, yet"
+                        ChainExtensionErrorCode::AssocConst.render()
                     ))
                 }
                 syn::TraitItem::Macro(macro_trait_item) => {
                     return Err(format_err_spanned!(
                         macro_trait_item,
-                        "macros in ink! chain extensions are not supported"
+                        ChainExtensionErrorCode::Macro.render()
                     ))
                 }
-                syn::TraitItem::Type(type_trait_item) => {
-                    return Err(format_err_spanned!(
-                    type_trait_item,
-                    "associated types in ink! chain extensions are not supported, yet"
-                ))
+                syn::TraitItem::Type(_) => {
+                    // The reserved `type ErrorCode;` associated type (the
+                    // only associated type ink! chain extensions support) has
+                    // already been validated and extracted by
+                    // `Self::analyse_error_code` before this method runs.
+                    continue
                 }
                 syn::TraitItem::Verbatim(verbatim) => {
                     return Err(format_err_spanned!(
                         verbatim,
-                        "encountered unsupported item in ink! chain extensions"
+                        ChainExtensionErrorCode::Verbatim.render()
                     ))
                 }
                 syn::TraitItem::Method(method_trait_item) => {
-                    let method = Self::analyse_methods(method_trait_item)?;
+                    let method = Self::analyse_methods(
+                        method_trait_item,
+                        handle_status_default,
+                        extension_id,
+                    )?;
                     let method_id = method.id();
-                    if let Some(previous) = seen_ids.get(&method_id) {
-                        return Err(format_err!(
-                            method.span(),
-                            "encountered duplicate extension identifiers for the same chain extension",
-                        ).into_combine(format_err!(
-                            *previous,
-                            "previous duplicate extension identifier here",
-                        )))
+                    // Anchored at the `#[ink(extension = N)]` attribute
+                    // itself rather than the whole method, so that editors
+                    // underline just the offending id, not the entire
+                    // method body.
+                    let id_span = ir::first_ink_attribute(&method_trait_item.attrs)?
+                        .map(|attr| attr.first().span())
+                        .unwrap_or_else(|| method.span());
+                    if let Some((_first_ident, first_span)) = seen_ids.get(&method_id) {
+                        let message = format!(
+                            "[{}] duplicate extension id `{}` here",
+                            ChainExtensionErrorCode::DuplicateExtensionId.code(),
+                            method_id.function_id(),
+                        );
+                        return Err(format_err!(id_span, message).into_combine(
+                            format_err!(*first_span, "first defined here"),
+                        ))
                     }
-                    seen_ids.insert(method_id, method.span());
+                    // Independent of the id-overlap check above: two methods
+                    // with distinct `extension = N` ids can still share the
+                    // same Rust identifier, which would otherwise only
+                    // surface as a confusing "duplicate definition" error
+                    // deep in the generated trait impl.
+                    let name_span = method.ident().span();
+                    let name = method.ident().to_string();
+                    if let Some(first_span) = seen_names.get(&name) {
+                        let message = format!(
+                            "[{}] duplicate definitions with name `{}`",
+                            ChainExtensionErrorCode::DuplicateMethodName.code(),
+                            name,
+                        );
+                        return Err(format_err!(name_span, message).into_combine(
+                            format_err!(*first_span, "previous definition here"),
+                        ))
+                    }
+                    seen_names.insert(name, name_span);
+                    seen_ids.insert(method_id, (method.ident().clone(), id_span));
                     methods.push(method);
                 }
                 unknown => {
                     return Err(format_err_spanned!(
                         unknown,
-                        "encountered unknown or unsupported item in ink! chain extensions"
+                        ChainExtensionErrorCode::UnknownItem.render()
                     ))
                 }
             }
@@ -268,64 +692,71 @@ impl ChainExtension {
     /// - If the method is variadic or has generic parameters.
     fn analyse_methods(
         method: &syn::TraitItemMethod,
+        handle_status_default: bool,
+        extension_id: u16,
     ) -> Result<ChainExtensionMethod> {
         if let Some(default_impl) = &method.default {
             return Err(format_err_spanned!(
                 default_impl,
-                "ink! chain extension methods with default implementations are not supported"
+                ChainExtensionErrorCode::DefaultImpl.render()
             ))
         }
         if let Some(constness) = &method.sig.constness {
             return Err(format_err_spanned!(
                 constness,
-                "const ink! chain extension methods are not supported"
+                ChainExtensionErrorCode::ConstMethod.render()
             ))
         }
         if let Some(asyncness) = &method.sig.asyncness {
             return Err(format_err_spanned!(
                 asyncness,
-                "async ink! chain extension methods are not supported"
+                ChainExtensionErrorCode::AsyncMethod.render()
             ))
         }
         if let Some(unsafety) = &method.sig.unsafety {
             return Err(format_err_spanned!(
                 unsafety,
-                "unsafe ink! chain extension methods are not supported"
+                ChainExtensionErrorCode::UnsafeMethod.render()
             ))
         }
         if let Some(abi) = &method.sig.abi {
             return Err(format_err_spanned!(
                 abi,
-                "ink! chain extension methods with non default ABI are not supported"
+                ChainExtensionErrorCode::ExplicitAbi.render()
             ))
         }
         if let Some(variadic) = &method.sig.variadic {
             return Err(format_err_spanned!(
                 variadic,
-                "variadic ink! chain extension methods are not supported"
+                ChainExtensionErrorCode::Variadic.render()
             ))
         }
         if !method.sig.generics.params.is_empty() {
             return Err(format_err_spanned!(
                 method.sig.generics.params,
-                "generic ink! chain extension methods are not supported"
+                ChainExtensionErrorCode::GenericMethod.render()
             ))
         }
         match ir::first_ink_attribute(&method.attrs)?
                 .map(|attr| attr.first().kind().clone()) {
             Some(ir::AttributeArg::Extension(extension)) => {
-                return Self::analyse_chain_extension_method(method, extension)
+                return Self::analyse_chain_extension_method(
+                    method,
+                    extension,
+                    handle_status_default,
+                    extension_id,
+                )
             }
             Some(_unsupported) => {
                 return Err(format_err_spanned!(
                     method,
-                    "encountered unsupported ink! attribute for ink! chain extension method. expected #[ink(function = N: usize)] attribute"
+                    ChainExtensionErrorCode::UnsupportedMethodAttribute.render()
                 ))
             }
             None => {
                 return Err(format_err_spanned!(
                     method,
-                    "missing #[ink(function = N: usize)] flag on ink! chain extension method"
+                    ChainExtensionErrorCode::MissingExtensionAttribute.render()
                 ))
             }
         }
@@ -336,28 +767,196 @@ impl ChainExtension {
     /// # Errors
     ///
     /// - If the chain extension method has a `self` receiver as first argument.
+    /// - If the method declares `#[ink(handle_status = ...)]` but has no
+    ///   return type to decode a status code into.
+    /// - If an argument is a reference, raw pointer, `impl Trait`, or
+    ///   carries an explicit lifetime.
+    /// - If the method handles its status code and its return type is a
+    ///   reference, raw pointer, `impl Trait`, or carries an explicit
+    ///   lifetime.
     fn analyse_chain_extension_method(
         item_method: &syn::TraitItemMethod,
-        extension: ExtensionId,
+        function_id: u32,
+        handle_status_default: bool,
+        extension_id: u16,
     ) -> Result<ChainExtensionMethod> {
         ir::sanitize_attributes(
             item_method.span(),
             item_method.attrs.clone(),
             &ir::AttributeArgKind::Extension,
-            |c| !matches!(c, ir::AttributeArg::Extension(_)),
+            |c| {
+                !matches!(
+                    c,
+                    ir::AttributeArg::Extension(_) | ir::AttributeArg::HandleStatus(_)
+                )
+            },
         )?;
         if let Some(receiver) = item_method.sig.receiver() {
             return Err(format_err_spanned!(
                 receiver,
-                "ink! chain extension method must not have a `self` receiver",
+                ChainExtensionErrorCode::SelfReceiver.render(),
             ))
         }
+        for arg in item_method.sig.inputs.iter() {
+            if let syn::FnArg::Typed(pat_type) = arg {
+                Self::ensure_wire_compatible_type(&pat_type.ty)?;
+            }
+        }
+        let handle_status_attr = Self::extract_handle_status(&item_method.attrs)?;
+        if handle_status_attr.is_some()
+            && matches!(item_method.sig.output, syn::ReturnType::Default)
+        {
+            return Err(format_err_spanned!(
+                item_method.sig,
+                ChainExtensionErrorCode::HandleStatusMissingReturnType.render(),
+            ))
+        }
+        let handle_status = handle_status_attr.unwrap_or(handle_status_default);
+        if handle_status {
+            if let syn::ReturnType::Type(_, output_ty) = &item_method.sig.output {
+                Self::ensure_wire_compatible_type(output_ty)?;
+            }
+        }
+        let function_id = u16::try_from(function_id).map_err(|_| {
+            format_err_spanned!(
+                item_method.sig,
+                ChainExtensionErrorCode::FunctionIdTooLarge.render()
+            )
+        })?;
+        let id = ExtensionId::new(extension_id, function_id);
         let result = ChainExtensionMethod {
-            id: extension,
+            id,
             item: item_method.clone(),
+            handle_status,
         };
         Ok(result)
     }
+
+    /// Returns the chain extension's `ErrorCode` associated type, if one was
+    /// declared.
+    ///
+    /// # Errors
+    ///
+    /// - If more than one associated type is declared.
+    /// - If the declared associated type is not named `ErrorCode`.
+    /// - If the declared associated type is generic, has bounds, or a
+    ///   default.
+    fn analyse_error_code(item_trait: &syn::ItemTrait) -> Result<Option<syn::TraitItemType>> {
+        let mut error_code: Option<syn::TraitItemType> = None;
+        for trait_item in &item_trait.items {
+            let type_trait_item = match trait_item {
+                syn::TraitItem::Type(type_trait_item) => type_trait_item,
+                _ => continue,
+            };
+            if error_code.is_some() {
+                return Err(format_err_spanned!(
+                    type_trait_item,
+                    "encountered a second associated type in an ink! chain extension: \
+                     only a single `type ErrorCode;` is supported",
+                ))
+            }
+            if type_trait_item.ident != "ErrorCode" {
+                return Err(format_err_spanned!(
+                    type_trait_item,
+                    "encountered an unsupported associated type in ink! chain extension: \
+                     only `type ErrorCode;` is supported",
+                ))
+            }
+            if !type_trait_item.generics.params.is_empty() {
+                return Err(format_err_spanned!(
+                    type_trait_item.generics.params,
+                    "the `ErrorCode` associated type of an ink! chain extension must not be generic",
+                ))
+            }
+            if !type_trait_item.bounds.is_empty() {
+                return Err(format_err_spanned!(
+                    type_trait_item.bounds,
+                    "the `ErrorCode` associated type of an ink! chain extension must not have bounds",
+                ))
+            }
+            if type_trait_item.default.is_some() {
+                return Err(format_err_spanned!(
+                    type_trait_item,
+                    "the `ErrorCode` associated type of an ink! chain extension must not have a default",
+                ))
+            }
+            error_code = Some(type_trait_item.clone());
+        }
+        Ok(error_code)
+    }
+
+    /// Returns the chain extension's default error-code handling policy, as
+    /// declared via `#[ink(handle_status = flag: bool)]` on the trait itself,
+    /// falling back to [`DEFAULT_HANDLE_STATUS`] if it is absent.
+    fn analyse_handle_status_default(item_trait: &syn::ItemTrait) -> Result<bool> {
+        Ok(Self::extract_handle_status(&item_trait.attrs)?
+            .unwrap_or(DEFAULT_HANDLE_STATUS))
+    }
+
+    /// Returns `Ok` if `ty` is a shape the chain extension generator can
+    /// encode onto, and decode off of, the wire: an owned, concrete type
+    /// with no borrows, raw pointers, or explicit lifetimes.
+    ///
+    /// # Errors
+    ///
+    /// - If `ty` is a reference (`&T`, `&mut T`).
+    /// - If `ty` is a raw pointer (`*const T`, `*mut T`).
+    /// - If `ty` is an `impl Trait`.
+    /// - If `ty` carries an explicit lifetime argument, e.g. `Foo<'a>`.
+    fn ensure_wire_compatible_type(ty: &syn::Type) -> Result<()> {
+        match ty {
+            syn::Type::Reference(type_reference) => {
+                Err(format_err_spanned!(
+                    type_reference,
+                    ChainExtensionErrorCode::ReferenceType.render()
+                ))
+            }
+            syn::Type::Ptr(type_ptr) => {
+                Err(format_err_spanned!(
+                    type_ptr,
+                    ChainExtensionErrorCode::PointerType.render()
+                ))
+            }
+            syn::Type::ImplTrait(type_impl_trait) => {
+                Err(format_err_spanned!(
+                    type_impl_trait,
+                    ChainExtensionErrorCode::ImplTraitType.render()
+                ))
+            }
+            syn::Type::Path(type_path) => {
+                let carries_lifetime_arg =
+                    type_path.path.segments.iter().any(|segment| {
+                        match &segment.arguments {
+                            syn::PathArguments::AngleBracketed(args) => {
+                                args.args.iter().any(|arg| {
+                                    matches!(arg, syn::GenericArgument::Lifetime(_))
+                                })
+                            }
+                            _ => false,
+                        }
+                    });
+                if carries_lifetime_arg {
+                    return Err(format_err_spanned!(
+                        type_path,
+                        ChainExtensionErrorCode::LifetimeType.render()
+                    ))
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Looks for a `#[ink(handle_status = flag: bool)]` argument among `attrs`.
+    fn extract_handle_status(attrs: &[syn::Attribute]) -> Result<Option<bool>> {
+        let handle_status = ir::first_ink_attribute(attrs)?.and_then(|attr| {
+            attr.args().find_map(|arg| match arg.kind() {
+                ir::AttributeArg::HandleStatus(flag) => Some(*flag),
+                _ => None,
+            })
+        });
+        Ok(handle_status)
+    }
 }
 
 #[cfg(test)]
@@ -382,7 +981,7 @@ mod tests {
     #[test]
     fn unsafe_chain_extension_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "ink! chain extensions cannot be unsafe",
+            error: "[ink-CE-001] ink! chain extensions cannot be unsafe",
             pub unsafe trait MyChainExtension {}
         );
     }
@@ -390,7 +989,7 @@ mod tests {
     #[test]
     fn auto_chain_extension_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "ink! chain extensions cannot be automatically implemented traits",
+            error: "[ink-CE-002] ink! chain extensions cannot be automatically implemented traits",
             pub auto trait MyChainExtension {}
         );
     }
@@ -398,11 +997,11 @@ mod tests {
     #[test]
     fn non_pub_chain_extension_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "ink! chain extensions must have public visibility",
+            error: "[ink-CE-004] ink! chain extensions must have public visibility",
             trait MyChainExtension {}
         );
         assert_ink_chain_extension_eq_err!(
-            error: "ink! chain extensions must have public visibility",
+            error: "[ink-CE-004] ink! chain extensions must have public visibility",
             pub(crate) trait MyChainExtension {}
         );
     }
@@ -410,7 +1009,7 @@ mod tests {
     #[test]
     fn generic_chain_extension_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "ink! chain extensions must not be generic",
+            error: "[ink-CE-003] ink! chain extensions must not be generic",
             pub trait MyChainExtension<T> {}
         );
     }
@@ -418,7 +1017,7 @@ mod tests {
     #[test]
     fn chain_extension_with_supertraits_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "ink! chain extensions with supertraits are not supported, yet",
+            error: "[ink-CE-005] ink! chain extensions with supertraits are not supported, yet",
             pub trait MyChainExtension: SuperChainExtension {}
         );
     }
@@ -426,7 +1025,7 @@ mod tests {
     #[test]
     fn chain_extension_containing_const_item_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "associated constants in ink! chain extensions are not supported, yet",
+            error: "[ink-CE-006] associated constants in ink! chain extensions are not supported, yet",
             pub trait MyChainExtension {
                 const T: i32;
             }
@@ -434,19 +1033,109 @@ mod tests {
     }
 
     #[test]
-    fn chain_extension_containing_associated_type_is_denied() {
+    fn chain_extension_containing_unsupported_associated_type_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "associated types in ink! chain extensions are not supported, yet",
+            error: "encountered an unsupported associated type in ink! chain extension: \
+                only `type ErrorCode;` is supported",
             pub trait MyChainExtension {
                 type Type;
             }
         );
     }
 
+    #[test]
+    fn chain_extension_containing_multiple_associated_types_is_denied() {
+        assert_ink_chain_extension_eq_err!(
+            error: "encountered a second associated type in an ink! chain extension: \
+                only a single `type ErrorCode;` is supported",
+            pub trait MyChainExtension {
+                type ErrorCode;
+                type ErrorCode2;
+            }
+        );
+    }
+
+    #[test]
+    fn chain_extension_error_code_must_not_be_generic() {
+        assert_ink_chain_extension_eq_err!(
+            error: "the `ErrorCode` associated type of an ink! chain extension must not be generic",
+            pub trait MyChainExtension {
+                type ErrorCode<T>;
+            }
+        );
+    }
+
+    #[test]
+    fn chain_extension_error_code_must_not_have_bounds() {
+        assert_ink_chain_extension_eq_err!(
+            error: "the `ErrorCode` associated type of an ink! chain extension must not have bounds",
+            pub trait MyChainExtension {
+                type ErrorCode: Clone;
+            }
+        );
+    }
+
+    #[test]
+    fn chain_extension_error_code_must_not_have_default() {
+        assert_ink_chain_extension_eq_err!(
+            error: "the `ErrorCode` associated type of an ink! chain extension must not have a default",
+            pub trait MyChainExtension {
+                type ErrorCode = ();
+            }
+        );
+    }
+
+    #[test]
+    fn chain_extension_error_code_is_ok() {
+        let chain_extension =
+            <ChainExtension as TryFrom<syn::ItemTrait>>::try_from(syn::parse_quote! {
+                pub trait MyChainExtension {
+                    type ErrorCode;
+
+                    #[ink(extension = 1)]
+                    fn extension_1();
+                }
+            })
+            .unwrap();
+        assert_eq!(
+            chain_extension
+                .error_code()
+                .map(|error_code| error_code.ident.to_string()),
+            Some("ErrorCode".to_string()),
+        );
+    }
+
+    #[test]
+    fn chain_extension_method_relying_on_status_handling_requires_error_code() {
+        assert_ink_chain_extension_eq_err!(
+            error: "ink! chain extension method relies on status code handling \
+                but the chain extension does not declare a `type ErrorCode;` \
+                associated type to decode it into",
+            pub trait MyChainExtension {
+                #[ink(extension = 1)]
+                fn extension_1();
+            }
+        );
+    }
+
+    #[test]
+    fn chain_extension_without_error_code_is_ok_if_status_handling_is_disabled() {
+        let chain_extension =
+            <ChainExtension as TryFrom<syn::ItemTrait>>::try_from(syn::parse_quote! {
+                #[ink(handle_status = false)]
+                pub trait MyChainExtension {
+                    #[ink(extension = 1)]
+                    fn extension_1();
+                }
+            })
+            .unwrap();
+        assert!(chain_extension.error_code().is_none());
+    }
+
     #[test]
     fn chain_extension_containing_macro_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "macros in ink! chain extensions are not supported",
+            error: "[ink-CE-007] macros in ink! chain extensions are not supported",
             pub trait MyChainExtension {
                 my_macro_call!();
             }
@@ -456,19 +1145,19 @@ mod tests {
     #[test]
     fn chain_extension_containing_non_flagged_method_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "missing #[ink(function = N: usize)] flag on ink! chain extension method",
+            error: "[ink-CE-019] missing #[ink(extension = N: usize)] flag on ink! chain extension method",
             pub trait MyChainExtension {
                 fn non_flagged_1(&self);
             }
         );
         assert_ink_chain_extension_eq_err!(
-            error: "missing #[ink(function = N: usize)] flag on ink! chain extension method",
+            error: "[ink-CE-019] missing #[ink(extension = N: usize)] flag on ink! chain extension method",
             pub trait MyChainExtension {
                 fn non_flagged_2(&mut self);
             }
         );
         assert_ink_chain_extension_eq_err!(
-            error: "missing #[ink(function = N: usize)] flag on ink! chain extension method",
+            error: "[ink-CE-019] missing #[ink(extension = N: usize)] flag on ink! chain extension method",
             pub trait MyChainExtension {
                 fn non_flagged_3() -> Self;
             }
@@ -478,7 +1167,7 @@ mod tests {
     #[test]
     fn chain_extension_containing_default_implemented_methods_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "ink! chain extension methods with default implementations are not supported",
+            error: "[ink-CE-011] ink! chain extension methods with default implementations are not supported",
             pub trait MyChainExtension {
                 #[ink(constructor)]
                 fn default_implemented() -> Self {}
@@ -489,7 +1178,7 @@ mod tests {
     #[test]
     fn chain_extension_containing_const_methods_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "const ink! chain extension methods are not supported",
+            error: "[ink-CE-012] const ink! chain extension methods are not supported",
             pub trait MyChainExtension {
                 #[ink(extension = 1)]
                 const fn const_constructor() -> Self;
@@ -500,7 +1189,7 @@ mod tests {
     #[test]
     fn chain_extension_containing_async_methods_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "async ink! chain extension methods are not supported",
+            error: "[ink-CE-013] async ink! chain extension methods are not supported",
             pub trait MyChainExtension {
                 #[ink(extension = 1)]
                 async fn const_constructor() -> Self;
@@ -511,7 +1200,7 @@ mod tests {
     #[test]
     fn chain_extension_containing_unsafe_methods_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "unsafe ink! chain extension methods are not supported",
+            error: "[ink-CE-014] unsafe ink! chain extension methods are not supported",
             pub trait MyChainExtension {
                 #[ink(extension = 1)]
                 unsafe fn const_constructor() -> Self;
@@ -522,7 +1211,7 @@ mod tests {
     #[test]
     fn chain_extension_containing_methods_using_explicit_abi_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "ink! chain extension methods with non default ABI are not supported",
+            error: "[ink-CE-015] ink! chain extension methods with non default ABI are not supported",
             pub trait MyChainExtension {
                 #[ink(extension = 1)]
                 extern fn const_constructor() -> Self;
@@ -533,7 +1222,7 @@ mod tests {
     #[test]
     fn chain_extension_containing_variadic_methods_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "variadic ink! chain extension methods are not supported",
+            error: "[ink-CE-016] variadic ink! chain extension methods are not supported",
             pub trait MyChainExtension {
                 #[ink(extension = 1)]
                 fn const_constructor(...) -> Self;
@@ -544,7 +1233,7 @@ mod tests {
     #[test]
     fn chain_extension_containing_generic_methods_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "generic ink! chain extension methods are not supported",
+            error: "[ink-CE-017] generic ink! chain extension methods are not supported",
             pub trait MyChainExtension {
                 #[ink(extension = 1)]
                 fn const_constructor<T>() -> Self;
@@ -557,8 +1246,8 @@ mod tests {
     ) {
         assert_ink_chain_extension_eq_err!(
             error: "\
-                encountered unsupported ink! attribute for ink! chain extension method. \
-                expected #[ink(function = N: usize)] attribute",
+                [ink-CE-018] encountered unsupported ink! attribute for ink! chain extension method. \
+                expected #[ink(extension = N: usize)] attribute",
             pub trait MyChainExtension {
                 #[ink(message)]
                 fn unsupported_ink_attribute(&self);
@@ -634,35 +1323,35 @@ mod tests {
     #[test]
     fn chain_extension_containing_method_with_self_receiver_is_denied() {
         assert_ink_chain_extension_eq_err!(
-            error: "ink! chain extension method must not have a `self` receiver",
+            error: "[ink-CE-020] ink! chain extension method must not have a `self` receiver",
             pub trait MyChainExtension {
                 #[ink(extension = 1)]
                 fn has_self_receiver(&self) -> Self;
             }
         );
         assert_ink_chain_extension_eq_err!(
-            error: "ink! chain extension method must not have a `self` receiver",
+            error: "[ink-CE-020] ink! chain extension method must not have a `self` receiver",
             pub trait MyChainExtension {
                 #[ink(extension = 1)]
                 fn has_self_receiver(&mut self) -> Self;
             }
         );
         assert_ink_chain_extension_eq_err!(
-            error: "ink! chain extension method must not have a `self` receiver",
+            error: "[ink-CE-020] ink! chain extension method must not have a `self` receiver",
             pub trait MyChainExtension {
                 #[ink(extension = 1)]
                 fn has_self_receiver(self) -> Self;
             }
         );
         assert_ink_chain_extension_eq_err!(
-            error: "ink! chain extension method must not have a `self` receiver",
+            error: "[ink-CE-020] ink! chain extension method must not have a `self` receiver",
             pub trait MyChainExtension {
                 #[ink(extension = 1)]
                 fn has_self_receiver(self: &Self) -> Self;
             }
         );
         assert_ink_chain_extension_eq_err!(
-            error: "ink! chain extension method must not have a `self` receiver",
+            error: "[ink-CE-020] ink! chain extension method must not have a `self` receiver",
             pub trait MyChainExtension {
                 #[ink(extension = 1)]
                 fn has_self_receiver(self: Self) -> Self;
@@ -673,7 +1362,7 @@ mod tests {
     #[test]
     fn chain_extension_with_overlapping_extension_ids() {
         assert_ink_chain_extension_eq_err!(
-            error: "encountered duplicate extension identifiers for the same chain extension",
+            error: "[ink-CE-010] duplicate extension id `1` here",
             pub trait MyChainExtension {
                 #[ink(extension = 1)]
                 fn same_id_1();
@@ -683,10 +1372,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn chain_extension_with_overlapping_method_names_is_denied() {
+        assert_ink_chain_extension_eq_err!(
+            error: "[ink-CE-027] duplicate definitions with name `fetch_random`",
+            pub trait MyChainExtension {
+                #[ink(extension = 1)]
+                fn fetch_random();
+                #[ink(extension = 2)]
+                fn fetch_random();
+            }
+        );
+    }
+
+    #[test]
+    fn chain_extension_method_handle_status_requires_return_type() {
+        assert_ink_chain_extension_eq_err!(
+            error: "[ink-CE-021] ink! chain extension method declares #[ink(handle_status = ...)] \
+                but has no return type for a status code to be decoded into",
+            pub trait MyChainExtension {
+                type ErrorCode;
+
+                #[ink(extension = 1)]
+                #[ink(handle_status = false)]
+                fn extension_1();
+            }
+        );
+    }
+
     #[test]
     fn chain_extension_is_ok() {
         let chain_extension = <ChainExtension as TryFrom<syn::ItemTrait>>::try_from(syn::parse_quote! {
                 pub trait MyChainExtension {
+                    type ErrorCode;
+
                     #[ink(extension = 1)]
                     fn extension_1();
                     #[ink(extension = 2)]
@@ -727,4 +1446,252 @@ mod tests {
             assert_eq!(actual, expected);
         }
     }
+
+    #[test]
+    fn handle_status_defaults_to_true() {
+        let chain_extension =
+            <ChainExtension as TryFrom<syn::ItemTrait>>::try_from(syn::parse_quote! {
+                pub trait MyChainExtension {
+                    type ErrorCode;
+
+                    #[ink(extension = 1)]
+                    fn extension_1();
+                }
+            })
+            .unwrap();
+        assert!(chain_extension.methods[0].handle_status());
+    }
+
+    #[test]
+    fn handle_status_default_is_overridable_on_the_trait() {
+        let chain_extension =
+            <ChainExtension as TryFrom<syn::ItemTrait>>::try_from(syn::parse_quote! {
+                #[ink(handle_status = false)]
+                pub trait MyChainExtension {
+                    type ErrorCode;
+
+                    #[ink(extension = 1)]
+                    fn extension_1();
+                    #[ink(extension = 2)]
+                    #[ink(handle_status = true)]
+                    fn extension_2();
+                }
+            })
+            .unwrap();
+        assert!(!chain_extension.methods[0].handle_status());
+        assert!(chain_extension.methods[1].handle_status());
+    }
+
+    #[test]
+    fn chain_extension_method_function_id_must_fit_u16() {
+        assert_ink_chain_extension_eq_err!(
+            error: "[ink-CE-022] the `M` in #[ink(extension = M)] must fit into a 16-bit function id, \
+                since it is namespaced under the chain extension's own \
+                `extension_id` as `(extension_id << 16) | function_id`",
+            pub trait MyChainExtension {
+                type ErrorCode;
+
+                #[ink(extension = 70000)]
+                fn extension_1();
+            }
+        );
+    }
+
+    #[test]
+    fn chain_extension_method_with_reference_argument_is_denied() {
+        assert_ink_chain_extension_eq_err!(
+            error: "[ink-CE-023] ink! chain extension method signatures must not use reference types: \
+                every argument and the return type must be an owned, SCALE-encodable type",
+            pub trait MyChainExtension {
+                #[ink(extension = 1)]
+                fn extension_1(input: &i32);
+            }
+        );
+    }
+
+    #[test]
+    fn chain_extension_method_with_pointer_argument_is_denied() {
+        assert_ink_chain_extension_eq_err!(
+            error: "[ink-CE-024] ink! chain extension method signatures must not use raw pointer types: \
+                every argument and the return type must be an owned, SCALE-encodable type",
+            pub trait MyChainExtension {
+                #[ink(extension = 1)]
+                fn extension_1(input: *const i32);
+            }
+        );
+    }
+
+    #[test]
+    fn chain_extension_method_with_impl_trait_argument_is_denied() {
+        assert_ink_chain_extension_eq_err!(
+            error: "[ink-CE-025] ink! chain extension method signatures must not use `impl Trait`: \
+                every argument and the return type must be a concrete, SCALE-encodable type",
+            pub trait MyChainExtension {
+                #[ink(extension = 1)]
+                fn extension_1(input: impl Clone);
+            }
+        );
+    }
+
+    #[test]
+    fn chain_extension_method_with_lifetime_argument_is_denied() {
+        assert_ink_chain_extension_eq_err!(
+            error: "[ink-CE-026] ink! chain extension method signatures must not carry explicit lifetimes: \
+                every argument and the return type must be an owned, SCALE-encodable type",
+            pub trait MyChainExtension {
+                #[ink(extension = 1)]
+                fn extension_1(input: Foo<'static>);
+            }
+        );
+    }
+
+    #[test]
+    fn chain_extension_method_handling_status_with_reference_return_type_is_denied() {
+        assert_ink_chain_extension_eq_err!(
+            error: "[ink-CE-023] ink! chain extension method signatures must not use reference types: \
+                every argument and the return type must be an owned, SCALE-encodable type",
+            pub trait MyChainExtension {
+                type ErrorCode;
+
+                #[ink(extension = 1)]
+                fn extension_1() -> &'static i32;
+            }
+        );
+    }
+
+    #[test]
+    fn chain_extension_method_ignoring_status_with_reference_return_type_is_ok() {
+        let chain_extension =
+            <ChainExtension as TryFrom<syn::ItemTrait>>::try_from(syn::parse_quote! {
+                pub trait MyChainExtension {
+                    #[ink(extension = 1)]
+                    #[ink(handle_status = false)]
+                    fn extension_1() -> &'static i32;
+                }
+            })
+            .unwrap();
+        assert!(!chain_extension.methods[0].handle_status());
+    }
+
+    #[test]
+    fn chain_extension_method_inputs_and_output_are_reflectable() {
+        let chain_extension =
+            <ChainExtension as TryFrom<syn::ItemTrait>>::try_from(syn::parse_quote! {
+                pub trait MyChainExtension {
+                    type ErrorCode;
+
+                    #[ink(extension = 1)]
+                    fn extension_1(a: i32, b: bool) -> i32;
+                }
+            })
+            .unwrap();
+        let method = &chain_extension.methods[0];
+        assert_eq!(method.inputs().count(), 2);
+        assert!(matches!(method.output(), syn::ReturnType::Type(_, _)));
+    }
+
+    #[test]
+    fn chain_extension_methods_sorted_by_id_ignores_declaration_order() {
+        let chain_extension =
+            <ChainExtension as TryFrom<syn::ItemTrait>>::try_from(syn::parse_quote! {
+                pub trait MyChainExtension {
+                    type ErrorCode;
+
+                    #[ink(extension = 3)]
+                    fn extension_3();
+                    #[ink(extension = 1)]
+                    fn extension_1();
+                    #[ink(extension = 2)]
+                    fn extension_2();
+                }
+            })
+            .unwrap();
+        let idents = chain_extension
+            .methods_sorted_by_id()
+            .map(|method| method.ident().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(idents, vec!["extension_1", "extension_2", "extension_3"]);
+    }
+
+    #[test]
+    fn chain_extension_contains_extension_id_reflects_declared_methods() {
+        let chain_extension =
+            <ChainExtension as TryFrom<syn::ItemTrait>>::try_from(syn::parse_quote! {
+                pub trait MyChainExtension {
+                    type ErrorCode;
+
+                    #[ink(extension = 1)]
+                    fn extension_1();
+                }
+            })
+            .unwrap();
+        assert!(chain_extension.contains_extension_id(1));
+        assert!(!chain_extension.contains_extension_id(2));
+    }
+
+    #[test]
+    fn chain_extension_with_namespace_is_ok() {
+        let attr: TokenStream2 = "extension = 1".parse().unwrap();
+        let input: TokenStream2 = "
+            pub trait MyChainExtension {
+                type ErrorCode;
+
+                #[ink(extension = 2)]
+                fn extension_1();
+            }
+        "
+        .parse()
+        .unwrap();
+        let chain_extension = ChainExtension::new(attr, input).unwrap();
+        assert_eq!(chain_extension.extension_id(), 1);
+        assert_eq!(chain_extension.methods[0].id().extension_id(), 1);
+        assert_eq!(chain_extension.methods[0].id().function_id(), 2);
+        assert_eq!(
+            chain_extension.methods[0].id().into_u32(),
+            (1u32 << 16) | 2
+        );
+    }
+
+    #[test]
+    fn chain_extension_namespace_must_fit_u16() {
+        let attr: TokenStream2 = "extension = 70000".parse().unwrap();
+        let input: TokenStream2 = "pub trait MyChainExtension {}".parse().unwrap();
+        assert_eq!(
+            ChainExtension::new(attr, input).map_err(|err| err.to_string()),
+            Err(
+                "the `N` in `extension = N` for #[ink::chain_extension] must fit into a `u16`"
+                    .to_string()
+            ),
+        );
+    }
+
+    #[test]
+    fn chain_extension_error_codes_are_unique() {
+        let mut codes = ChainExtensionErrorCode::ALL
+            .iter()
+            .map(|error_code| error_code.code())
+            .collect::<Vec<_>>();
+        let len_with_duplicates = codes.len();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(
+            codes.len(),
+            len_with_duplicates,
+            "two or more ChainExtensionErrorCode variants share the same stable code"
+        );
+    }
+
+    #[test]
+    fn chain_extension_namespace_rejects_unknown_argument() {
+        let attr: TokenStream2 = "foo = 1".parse().unwrap();
+        let input: TokenStream2 = "pub trait MyChainExtension {}".parse().unwrap();
+        assert_eq!(
+            ChainExtension::new(attr, input).map_err(|err| err.to_string()),
+            Err(
+                "unknown configuration argument for #[ink::chain_extension]; \
+                 expected `extension = N: u16`"
+                    .to_string()
+            ),
+        );
+    }
 }
\ No newline at end of file
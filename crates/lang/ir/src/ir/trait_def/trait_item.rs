@@ -16,6 +16,7 @@ use super::super::InkAttribute;
 use crate::{
     ir,
     Receiver,
+    Selector,
 };
 use proc_macro2::Span;
 use syn::{
@@ -23,6 +24,105 @@ use syn::{
     Result,
 };
 
+/// Either a user-provided selector or one ink! composed deterministically
+/// from the owning trait's path and the item's identifier.
+///
+/// # Note
+///
+/// Every contract that implements the same ink! trait definition derives the
+/// same [`SelectorOrComposed::Composed`] selector for a message or
+/// constructor that does not carry an explicit `#[ink(selector = ..)]`, so
+/// cross-contract calls can target the trait's methods without every
+/// implementer agreeing on magic selector numbers out of band.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SelectorOrComposed {
+    /// The user supplied this selector via `#[ink(selector = ..)]`.
+    UserProvided(Selector),
+    /// No explicit selector was given; this is the composed fallback.
+    Composed(Selector),
+}
+
+impl SelectorOrComposed {
+    /// Returns the selector to dispatch on, preferring an explicit one.
+    pub fn selector(self) -> Selector {
+        match self {
+            Self::UserProvided(selector) | Self::Composed(selector) => selector,
+        }
+    }
+}
+
+/// Computes the selector composed from the trait's path and the identifier
+/// of one of its constructors or messages.
+///
+/// # Note
+///
+/// Takes the BLAKE2 256-bit hash of `"<trait_path>::<ident>"` and truncates
+/// it to its first four bytes, mirroring how undecorated inherent messages
+/// and constructors derive their selector from their own identifier alone.
+fn compose_selector(trait_path: &syn::Path, ident: &syn::Ident) -> Selector {
+    let joined_path = trait_path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::");
+    let preimage = format!("{}::{}", joined_path, ident);
+    let hash = blake2_rfc::blake2b::blake2b(32, &[], preimage.as_bytes());
+    let hash_bytes = hash.as_bytes();
+    Selector::new([hash_bytes[0], hash_bytes[1], hash_bytes[2], hash_bytes[3]])
+}
+
+/// Returns the user-provided selector among `attrs`, if any.
+fn user_provided_selector(attrs: &InkAttribute) -> Option<Selector> {
+    attrs.args().find_map(|arg| {
+        match arg.kind() {
+            ir::AttributeArg::Selector(selector) => Some(*selector),
+            _ => None,
+        }
+    })
+}
+
+/// A comparable summary of an ink! trait constructor's or message's
+/// signature.
+///
+/// # Note
+///
+/// Lets the impl-block checker compare a contract's implementation of a
+/// trait constructor or message against the trait definition's own
+/// [`InkTraitItem::signature`], so that e.g. a `&self` trait message cannot
+/// be silently implemented as `&mut self`, or with a different selector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InkTraitItemSignature {
+    receiver: Option<Receiver>,
+    output: Option<syn::Type>,
+    payable: bool,
+    selector: Selector,
+}
+
+impl InkTraitItemSignature {
+    /// Returns the canonical receiver of the trait item.
+    ///
+    /// Returns `None` for constructors, which take no `self` receiver.
+    pub fn receiver(&self) -> Option<Receiver> {
+        self.receiver
+    }
+
+    /// Returns the return type of the trait item, if any.
+    pub fn output(&self) -> Option<&syn::Type> {
+        self.output.as_ref()
+    }
+
+    /// Returns `true` if the trait item was declared payable.
+    pub fn is_payable(&self) -> bool {
+        self.payable
+    }
+
+    /// Returns the selector the trait item dispatches on.
+    pub fn selector(&self) -> Selector {
+        self.selector
+    }
+}
+
 /// An ink! item within an ink! trait definition.
 #[derive(Debug, Clone)]
 pub enum InkTraitItem<'a> {
@@ -39,6 +139,29 @@ impl<'a> InkTraitItem<'a> {
         }
     }
 
+    /// Returns the comparable signature of the ink! trait item, for
+    /// checking a contract's implementation against the trait definition.
+    pub fn signature(&self) -> InkTraitItemSignature {
+        match self {
+            Self::Constructor(constructor) => {
+                InkTraitItemSignature {
+                    receiver: None,
+                    output: constructor.output().cloned(),
+                    payable: false,
+                    selector: constructor.selector().selector(),
+                }
+            }
+            Self::Message(message) => {
+                InkTraitItemSignature {
+                    receiver: Some(message.receiver()),
+                    output: message.output().cloned(),
+                    payable: message.is_payable(),
+                    selector: message.selector().selector(),
+                }
+            }
+        }
+    }
+
     /// Returns the ink! attributes of the ink! trait item.
     pub fn ink_attrs(&self) -> InkAttribute {
         match self {
@@ -67,6 +190,7 @@ impl<'a> InkTraitItem<'a> {
 /// A checked ink! constructor of an ink! trait definition.
 #[derive(Debug, Clone)]
 pub struct InkTraitConstructor<'a> {
+    trait_path: &'a syn::Path,
     item: &'a syn::TraitItemMethod,
 }
 
@@ -76,8 +200,8 @@ impl<'a> InkTraitConstructor<'a> {
         "encountered invalid attributes for ink! trait constructor";
 
     /// Creates a new ink! trait definition constructor.
-    pub(super) fn new(item: &'a syn::TraitItemMethod) -> Self {
-        Self { item }
+    pub(super) fn new(trait_path: &'a syn::Path, item: &'a syn::TraitItemMethod) -> Self {
+        Self { trait_path, item }
     }
 
     /// Analyses and extracts the ink! and non-ink! attributes of an ink! trait constructor.
@@ -125,6 +249,14 @@ impl<'a> InkTraitConstructor<'a> {
         InputsIter::from(self)
     }
 
+    /// Returns the return type of the ink! constructor if any.
+    pub fn output(&self) -> Option<&syn::Type> {
+        match &self.item.sig.output {
+            syn::ReturnType::Default => None,
+            syn::ReturnType::Type(_, return_type) => Some(return_type),
+        }
+    }
+
     /// Returns the Rust identifier of the ink! constructor.
     pub fn ident(&self) -> &syn::Ident {
         &self.item.sig.ident
@@ -134,11 +266,28 @@ impl<'a> InkTraitConstructor<'a> {
     pub fn span(&self) -> Span {
         self.item.span()
     }
+
+    /// Returns the selector of the ink! constructor composed from the
+    /// owning trait's path and its own identifier.
+    pub fn composed_selector(&self) -> Selector {
+        compose_selector(self.trait_path, self.ident())
+    }
+
+    /// Returns the selector to dispatch on: the user-provided one if the
+    /// constructor carries an explicit `#[ink(selector = ..)]`, or the
+    /// composed one otherwise.
+    pub fn selector(&self) -> SelectorOrComposed {
+        match user_provided_selector(&self.ink_attrs()) {
+            Some(selector) => SelectorOrComposed::UserProvided(selector),
+            None => SelectorOrComposed::Composed(self.composed_selector()),
+        }
+    }
 }
 
 /// A checked ink! message of an ink! trait definition.
 #[derive(Debug, Clone)]
 pub struct InkTraitMessage<'a> {
+    trait_path: &'a syn::Path,
     item: &'a syn::TraitItemMethod,
 }
 
@@ -148,8 +297,8 @@ impl<'a> InkTraitMessage<'a> {
         "encountered invalid attributes for ink! trait message";
 
     /// Creates a new ink! trait definition message.
-    pub(super) fn new(item: &'a syn::TraitItemMethod) -> Self {
-        Self { item }
+    pub(super) fn new(trait_path: &'a syn::Path, item: &'a syn::TraitItemMethod) -> Self {
+        Self { trait_path, item }
     }
 
     /// Analyses and extracts the ink! and non-ink! attributes of an ink! trait message.
@@ -256,6 +405,36 @@ impl<'a> InkTraitMessage<'a> {
             })
             .expect("encountered missing receiver for ink! message")
     }
+
+    /// Returns `true` if the ink! message was annotated with
+    /// `#[ink(payable)]`.
+    pub fn is_payable(&self) -> bool {
+        self.ink_attrs()
+            .args()
+            .any(|arg| matches!(arg.kind(), ir::AttributeArg::Payable))
+    }
+
+    /// Returns the selector the ink! message was explicitly given via
+    /// `#[ink(selector = ..)]`, if any.
+    pub fn user_provided_selector(&self) -> Option<Selector> {
+        user_provided_selector(&self.ink_attrs())
+    }
+
+    /// Returns the selector of the ink! message composed from the owning
+    /// trait's path and its own identifier.
+    pub fn composed_selector(&self) -> Selector {
+        compose_selector(self.trait_path, self.ident())
+    }
+
+    /// Returns the selector to dispatch on: the user-provided one if the
+    /// message carries an explicit `#[ink(selector = ..)]`, or the composed
+    /// one otherwise.
+    pub fn selector(&self) -> SelectorOrComposed {
+        match self.user_provided_selector() {
+            Some(selector) => SelectorOrComposed::UserProvided(selector),
+            None => SelectorOrComposed::Composed(self.composed_selector()),
+        }
+    }
 }
 
 /// Iterator over the input parameters of an ink! message or constructor.
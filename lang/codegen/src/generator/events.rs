@@ -129,7 +129,7 @@ impl<'a> Events<'a> {
             const _: () = {
                 #no_cross_calling_cfg
                 impl ::ink_core::env::Topics<EnvTypes> for #base_event_ident {
-                    fn topics(&self) -> &'static [Hash] {
+                    fn topics(&self) -> ::ink_prelude::vec::Vec<Hash> {
                         match self {
                             #(
                                 Self::#event_idents(event) => {
@@ -195,19 +195,62 @@ impl<'a> Events<'a> {
     }
 
     /// Generates the `Topics` trait implementations for the user defined events.
+    ///
+    /// # Note
+    ///
+    /// The first topic is always the event's own, deterministic discriminant
+    /// `blake2_256(b"<ContractName>::<EventName>")`. Every `#[ink(topic)]`
+    /// field then contributes one more topic: its SCALE encoding is appended
+    /// to the field's own prefix `blake2_256(b"<ContractName>::<EventName>::<field>")`,
+    /// and the combined bytes are stored directly (zero-padded to 32 bytes)
+    /// if they fit, or hashed down to 32 bytes with `blake2_256` otherwise.
     fn generate_topics_impls(&'a self) -> impl Iterator<Item = TokenStream2> + 'a {
         let no_cross_calling_cfg =
             self.generate_code_using::<generator::CrossCallingConflictCfg>();
+        let contract_ident = self.contract.module().storage().ident();
         self.contract.module().events().map(move |event| {
             let span = event.span();
             let ident = event.ident();
+            let event_signature = format!("{}::{}", contract_ident, ident);
+            let field_topics =
+                event.fields().filter(|field| field.is_topic).map(|field| {
+                    let field_span = field.span();
+                    let field_ident = field.ident();
+                    let field_prefix =
+                        format!("{}::{}::{}", contract_ident, ident, field_ident);
+                    quote_spanned!(field_span =>
+                        {
+                            let mut prefix_hash = [0u8; 32];
+                            ::ink_core::env::hash_blake2_256(
+                                #field_prefix.as_bytes(),
+                                &mut prefix_hash,
+                            );
+                            let mut preimage = ::scale::Encode::encode(&self.#field_ident);
+                            preimage.extend_from_slice(&prefix_hash);
+                            let mut topic_bytes = [0u8; 32];
+                            if preimage.len() <= 32 {
+                                topic_bytes[..preimage.len()].copy_from_slice(&preimage);
+                            } else {
+                                ::ink_core::env::hash_blake2_256(&preimage, &mut topic_bytes);
+                            }
+                            topics.push(Hash::from(topic_bytes));
+                        }
+                    )
+                });
             quote_spanned!(span =>
                 #no_cross_calling_cfg
                 const _: () = {
                     impl ::ink_core::env::Topics<EnvTypes> for #ident {
-                        fn topics(&self) -> &'static [Hash] {
-                            // Issue: https://github.com/paritytech/ink/issues/105
-                            &[]
+                        fn topics(&self) -> ::ink_prelude::vec::Vec<Hash> {
+                            let mut topics = ::ink_prelude::vec::Vec::new();
+                            let mut event_hash = [0u8; 32];
+                            ::ink_core::env::hash_blake2_256(
+                                #event_signature.as_bytes(),
+                                &mut event_hash,
+                            );
+                            topics.push(Hash::from(event_hash));
+                            #( #field_topics )*
+                            topics
                         }
                     }
                 };
@@ -29,8 +29,10 @@ use quote::{
     quote,
     quote_spanned,
 };
+use std::collections::HashMap;
 use syn::{
     punctuated::Punctuated,
+    spanned::Spanned as _,
     Token,
 };
 
@@ -50,6 +52,15 @@ impl<'a> GenerateCodeUsing for Dispatch<'a> {
 
 impl GenerateCode for Dispatch<'_> {
     fn generate_code(&self) -> TokenStream2 {
+        if let Err(err) = self.ensure_selectors_are_unique() {
+            return err.to_compile_error()
+        }
+        if let Err(err) = self.ensure_at_most_one_fallback_message() {
+            return err.to_compile_error()
+        }
+        if let Err(err) = self.ensure_fallback_message_is_well_formed() {
+            return err.to_compile_error()
+        }
         let message_trait_impls = self.generate_message_trait_impls();
         let message_dispatch_enum = self.generate_message_dispatch_enum();
         let constructor_dispatch_enum = self.generate_constructor_dispatch_enum();
@@ -77,6 +88,61 @@ impl GenerateCode for Dispatch<'_> {
 }
 
 impl Dispatch<'_> {
+    /// Checks that no two messages, and no two constructors, share the same
+    /// selector.
+    ///
+    /// Messages and constructors are checked against separate maps since
+    /// they dispatch in different modes (`Instantiate` vs `Call`) and may
+    /// safely reuse each other's selectors.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`syn::Error`] spanned at both offending functions, naming
+    /// the hex selector and both function names, if a collision is found.
+    fn ensure_selectors_are_unique(&self) -> Result<(), syn::Error> {
+        Self::ensure_unique_selectors_among(self.contract_messages(), "message")?;
+        Self::ensure_unique_selectors_among(self.contract_constructors(), "constructor")?;
+        Ok(())
+    }
+
+    fn ensure_unique_selectors_among<'f>(
+        functions: impl Iterator<Item = &'f ir::Function>,
+        kind: &str,
+    ) -> Result<(), syn::Error> {
+        let mut seen: HashMap<[u8; 4], &ir::Function> = HashMap::new();
+        for function in functions {
+            let selector = function
+                .selector()
+                .expect("encountered a non-message, non-constructor function");
+            let selector_bytes = *selector.as_bytes();
+            if let Some(other) = seen.get(&selector_bytes) {
+                let mut err = syn::Error::new(
+                    function.span(),
+                    format!(
+                        "encountered a {} selector collision: 0x{:02X}{:02X}{:02X}{:02X} is \
+                         shared with `{}`",
+                        kind,
+                        selector_bytes[0],
+                        selector_bytes[1],
+                        selector_bytes[2],
+                        selector_bytes[3],
+                        other.sig.ident,
+                    ),
+                );
+                err.combine(syn::Error::new(
+                    other.span(),
+                    format!(
+                        "first {} with a colliding selector defined here: `{}`",
+                        kind, other.sig.ident,
+                    ),
+                ));
+                return Err(err)
+            }
+            seen.insert(selector_bytes, function);
+        }
+        Ok(())
+    }
+
     fn generate_dispatch_variant_ident(
         &self,
         message: &ir::Function,
@@ -101,6 +167,20 @@ impl Dispatch<'_> {
         message: &ir::Function,
         prefix: &str,
     ) -> TokenStream2 {
+        let variant_ident = self.generate_dispatch_variant_ident(message, prefix);
+        if message.is_fallback() {
+            // The fallback message has no fixed 4-byte selector to match on:
+            // it instead becomes the catch-all arm, capturing whatever raw
+            // bytes remain in the input after the (non-matching) selector.
+            return quote! {
+                _invalid => {
+                    let remaining_len = input.remaining_len()?.unwrap_or(0);
+                    let mut remaining = ::ink_prelude::vec![0u8; remaining_len];
+                    ::scale::Input::read(input, &mut remaining)?;
+                    Ok(Self::#variant_ident(remaining))
+                }
+            }
+        }
         let selector_bytes = *message
             .selector()
             .expect("encountered a non-message function")
@@ -109,14 +189,19 @@ impl Dispatch<'_> {
         let s1 = selector_bytes[1];
         let s2 = selector_bytes[2];
         let s3 = selector_bytes[3];
-        let variant_ident = self.generate_dispatch_variant_ident(message, prefix);
-        let variant_types = message.sig.inputs().map(|arg| &arg.ty);
+        let decode_args = message.sig.inputs().enumerate().map(|(arg_index, arg)| {
+            let ty = &arg.ty;
+            let arg_index = arg_index as u32;
+            quote! {
+                <#ty as ::scale::Decode>::decode(input).map_err(|_| {
+                    Self::decode_error([#s0, #s1, #s2, #s3], #arg_index)
+                })?
+            }
+        });
         quote! {
             [#s0, #s1, #s2, #s3] => {
                 Ok(Self::#variant_ident(
-                    #(
-                        <#variant_types as ::scale::Decode>::decode(input)?
-                    ),*
+                    #( #decode_args ),*
                 ))
             }
         }
@@ -134,7 +219,16 @@ impl Dispatch<'_> {
         }
     }
 
-    /// Returns an iterator yielding the functions of a contract that are messages.
+    /// Returns an iterator yielding the functions of a contract that are
+    /// messages, including the fallback message, if any.
+    ///
+    /// # Note
+    ///
+    /// The fallback message, if present, is treated like any other message
+    /// for the purposes of variant and execute-arm generation: it is only
+    /// its decode arm, generated by [`Dispatch::generate_dispatch_variant_decode`],
+    /// that is special-cased to become the catch-all instead of matching on
+    /// a fixed 4-byte selector.
     fn contract_messages<'a>(&'a self) -> impl Iterator<Item = &'a ir::Function> + 'a {
         self.contract
             .functions
@@ -142,6 +236,75 @@ impl Dispatch<'_> {
             .filter(|function| function.is_message())
     }
 
+    /// Returns the contract's fallback message, if it declared one.
+    ///
+    /// A fallback message is a single `#[ink(message, selector = "_")]`
+    /// function that receives the raw, undecoded call data for any selector
+    /// that does not match a regular message - the building block for
+    /// upgradeable-proxy and router contracts.
+    fn fallback_message(&self) -> Option<&ir::Function> {
+        self.contract
+            .functions
+            .iter()
+            .find(|function| function.is_message() && function.is_fallback())
+    }
+
+    /// Ensures at most one message is declared as the contract's fallback.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`syn::Error`] spanned at both offending functions if more
+    /// than one fallback message is found.
+    fn ensure_at_most_one_fallback_message(&self) -> Result<(), syn::Error> {
+        let mut fallbacks = self
+            .contract
+            .functions
+            .iter()
+            .filter(|function| function.is_message() && function.is_fallback());
+        let first = match fallbacks.next() {
+            Some(first) => first,
+            None => return Ok(()),
+        };
+        if let Some(second) = fallbacks.next() {
+            let mut err = syn::Error::new(
+                second.span(),
+                format!(
+                    "encountered a second fallback message `{}`; a contract may only declare one",
+                    second.sig.ident,
+                ),
+            );
+            err.combine(syn::Error::new(
+                first.span(),
+                format!("first fallback message defined here: `{}`", first.sig.ident),
+            ));
+            return Err(err)
+        }
+        Ok(())
+    }
+
+    /// Ensures the fallback message, if any, has a valid signature: `&mut
+    /// self` and no SCALE-typed arguments beyond the raw input buffer.
+    fn ensure_fallback_message_is_well_formed(&self) -> Result<(), syn::Error> {
+        let fallback = match self.fallback_message() {
+            Some(fallback) => fallback,
+            None => return Ok(()),
+        };
+        if !fallback.sig.is_mut().unwrap_or(false) {
+            return Err(syn::Error::new(
+                fallback.span(),
+                "the fallback message must take `&mut self`",
+            ))
+        }
+        if fallback.sig.inputs().next().is_some() {
+            return Err(syn::Error::new(
+                fallback.span(),
+                "the fallback message must not take any SCALE-typed arguments; \
+                 it already receives the raw, undecoded call data",
+            ))
+        }
+        Ok(())
+    }
+
     fn generate_message_dispatch_enum(&self) -> TokenStream2 {
         let storage_ident = &self.contract.storage.ident;
         let message_variants = self
@@ -150,6 +313,14 @@ impl Dispatch<'_> {
         let decode_message = self
             .contract_messages()
             .map(|message| self.generate_dispatch_variant_decode(message, "Message"));
+        // The fallback's decode arm, generated above via `generate_dispatch_variant_decode`,
+        // already covers the `_invalid` case, so the catch-all error arm below must be
+        // omitted whenever a fallback message is present to avoid a duplicate match arm.
+        let invalid_selector_arm = if self.fallback_message().is_none() {
+            Some(quote! { _invalid => Err(::scale::Error::from("invalid message selector")) })
+        } else {
+            None
+        };
         let execute_variants = self.contract_messages()
             .map(|function| {
                 let ident = self.generate_dispatch_variant_ident(function, "Message");
@@ -204,13 +375,27 @@ impl Dispatch<'_> {
                     type Type = MessageDispatchEnum;
                 }
 
+                impl MessageDispatchEnum {
+                    /// Packs the selector and index of an argument that failed
+                    /// to decode into a [`scale::Error`] so that
+                    /// [`ink_lang::DispatchUsingMode::dispatch_using_mode`]'s
+                    /// call site can recover them and report a structured
+                    /// [`ink_lang::DispatchError::Decode`].
+                    fn decode_error(selector: [u8; 4], arg_index: u32) -> ::scale::Error {
+                        ::scale::Error::from(::ink_prelude::format!(
+                            "ink-dispatch-decode:{:02x}{:02x}{:02x}{:02x}:{}",
+                            selector[0], selector[1], selector[2], selector[3], arg_index
+                        ))
+                    }
+                }
+
                 impl ::scale::Decode for MessageDispatchEnum {
                     fn decode<I: ::scale::Input>(input: &mut I) -> ::core::result::Result<Self, ::scale::Error> {
                         match <[u8; 4] as ::scale::Decode>::decode(input)? {
                             #(
                                 #decode_message
                             )*
-                            _invalid => Err(::scale::Error::from("invalid message selector"))
+                            #invalid_selector_arm
                         }
                     }
                 }
@@ -283,6 +468,16 @@ impl Dispatch<'_> {
                     type Type = ConstructorDispatchEnum;
                 }
 
+                impl ConstructorDispatchEnum {
+                    /// See [`MessageDispatchEnum::decode_error`].
+                    fn decode_error(selector: [u8; 4], arg_index: u32) -> ::scale::Error {
+                        ::scale::Error::from(::ink_prelude::format!(
+                            "ink-dispatch-decode:{:02x}{:02x}{:02x}{:02x}:{}",
+                            selector[0], selector[1], selector[2], selector[3], arg_index
+                        ))
+                    }
+                }
+
                 impl ::scale::Decode for ConstructorDispatchEnum {
                     fn decode<I: ::scale::Input>(input: &mut I) -> ::core::result::Result<Self, ::scale::Error> {
                         match <[u8; 4] as ::scale::Decode>::decode(input)? {
@@ -329,8 +524,6 @@ impl Dispatch<'_> {
         let state_ident = &self.contract.storage.ident;
         let fn_ident = &function.sig.ident;
 
-        use syn::spanned::Spanned as _;
-
         let namespace = match function.kind() {
             ir::FunctionKind::Constructor(_) => quote! { Constr },
             ir::FunctionKind::Message(_) => quote! { Msg },
@@ -459,6 +652,37 @@ impl Dispatch<'_> {
     fn generate_dispatch_using_mode(&self) -> TokenStream2 {
         let storage_ident = &self.contract.storage.ident;
         quote! {
+            /// Recovers the selector and argument index packed into a decode
+            /// error by [`MessageDispatchEnum::decode_error`] or
+            /// [`ConstructorDispatchEnum::decode_error`], if present, and
+            /// reports a structured [`ink_lang::DispatchError::Decode`] so
+            /// that off-chain tooling can surface which argument of which
+            /// message or constructor failed to decode. Falls back to the
+            /// generic [`ink_lang::DispatchError::CouldNotReadInput`] for
+            /// errors that were not raised via one of those two helpers, e.g.
+            /// a selector that itself could not be read.
+            fn dispatch_decode_error(err: ::scale::Error) -> ::ink_lang::DispatchError {
+                let message = ::ink_prelude::format!("{}", err);
+                let parsed = message
+                    .strip_prefix("ink-dispatch-decode:")
+                    .and_then(|rest| {
+                        let mut parts = rest.splitn(2, ':');
+                        let selector_hex = parts.next()?;
+                        let arg_index = parts.next()?.parse::<u32>().ok()?;
+                        if selector_hex.len() != 8 {
+                            return None
+                        }
+                        let selector_num = u32::from_str_radix(selector_hex, 16).ok()?;
+                        Some((selector_num.to_be_bytes(), arg_index))
+                    });
+                match parsed {
+                    Some((selector, arg_index)) => {
+                        ::ink_lang::DispatchError::Decode { selector, arg_index }
+                    }
+                    None => ::ink_lang::DispatchError::CouldNotReadInput,
+                }
+            }
+
             impl ::ink_lang::DispatchUsingMode for #storage_ident {
                 #[allow(unused_parens)]
                 fn dispatch_using_mode(
@@ -468,13 +692,13 @@ impl Dispatch<'_> {
                         ::ink_lang::DispatchMode::Instantiate => {
                             <<#storage_ident as ::ink_lang::ConstructorDispatcher>::Type as ::ink_lang::Execute>::execute(
                                 ::ink_core::env::decode_input::<<#storage_ident as ::ink_lang::ConstructorDispatcher>::Type>()
-                                    .map_err(|_| ::ink_lang::DispatchError::CouldNotReadInput)?
+                                    .map_err(dispatch_decode_error)?
                             )
                         }
                         ::ink_lang::DispatchMode::Call => {
                             <<#storage_ident as ::ink_lang::MessageDispatcher>::Type as ::ink_lang::Execute>::execute(
                                 ::ink_core::env::decode_input::<<#storage_ident as ::ink_lang::MessageDispatcher>::Type>()
-                                    .map_err(|_| ::ink_lang::DispatchError::CouldNotReadInput)?
+                                    .map_err(dispatch_decode_error)?
                             )
                         }
                     }
@@ -23,15 +23,27 @@ use crate::{
     hir,
     ident_ext::IdentExt,
 };
+use semver::Version;
 use serde::{
     Deserialize,
     Serialize,
 };
+use std::collections::HashMap;
 use std::convert::TryFrom;
+use url::Url;
 
-/// Describes a message parameter or return type.
+/// A numeric identifier referring to a type interned in a `TypeRegistry`.
+///
+/// `TypeId`s are only meaningful relative to the `TypeRegistry` that handed
+/// them out; the same numeric value in two different registries may refer
+/// to unrelated types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct TypeId(u32);
+
+/// The definition of a single type interned in a `TypeRegistry`.
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
-pub enum TypeDescription {
+#[serde(tag = "kind")]
+pub enum TypeDef {
     /// The `bool` primitive type.
     #[serde(rename = "bool")]
     Bool,
@@ -69,61 +81,158 @@ pub enum TypeDescription {
     Address,
     /// The SRML balance type.
     Balance,
-    /// The tuple type
+    /// The tuple type.
     Tuple {
-        elems: Vec<TypeDescription>,
+        elems: Vec<TypeId>,
     },
-    /// The fixed size array type
+    /// The fixed size array type.
     Array {
-        inner: Box<TypeDescription>,
+        inner: TypeId,
         arity: u32,
-    }
+    },
+    /// The `Vec<T>` growable array type.
+    Vec {
+        inner: TypeId,
+    },
+    /// The `Option<T>` type.
+    Option {
+        inner: TypeId,
+    },
+    /// A named struct or enum referenced by a message/return type.
+    ///
+    /// Resolving the actual fields or variants of an arbitrary named type
+    /// would require either walking that type's own item definition or, as
+    /// later ink! versions do, dispatching through the `Metadata` impl it
+    /// derives (see e.g. `storage::BTreeMap`'s
+    /// `#[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]`).
+    /// This generator only ever sees the `syn::Type` mentioned in a message
+    /// signature, never the type's own definition, so it cannot look past
+    /// the name. It still interns the type so that every message or return
+    /// type that mentions e.g. `Transfer` collapses to the same `TypeId`
+    /// instead of repeating the name inline.
+    Named {
+        name: String,
+    },
 }
 
-impl TryFrom<&syn::Type> for TypeDescription {
-    type Error = Errors;
+/// Interns every distinct type referenced by a contract's messages and
+/// deploy handler, assigning each a numeric `TypeId`.
+///
+/// `ContractDescription` serializes this as a flat `"types"` table and has
+/// every param/return type refer back into it by `TypeId`, rather than
+/// repeating an inline type tree at every occurrence the way the old
+/// `TypeDescription` enum did.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TypeRegistry {
+    /// Maps a type's stringified syntax tree to the `TypeId` it was already
+    /// registered under, for deduplication.
+    #[serde(skip)]
+    by_repr: HashMap<String, u32>,
+    /// The definitions, indexed by `TypeId`.
+    types: Vec<TypeDef>,
+}
 
-    fn try_from(ty: &syn::Type) -> Result<Self> {
-        use quote::ToTokens;
-        let primitive = |ty: &syn::Type| {
-            match ty.into_token_stream().to_string().as_str() {
-                "bool" => Ok(TypeDescription::Bool),
-                "u8" => Ok(TypeDescription::U8),
-                "u16" => Ok(TypeDescription::U16),
-                "u32" => Ok(TypeDescription::U32),
-                "u64" => Ok(TypeDescription::U64),
-                "u128" => Ok(TypeDescription::U128),
-                "i8" => Ok(TypeDescription::I8),
-                "i16" => Ok(TypeDescription::I16),
-                "i32" => Ok(TypeDescription::I32),
-                "i64" => Ok(TypeDescription::I64),
-                "i128" => Ok(TypeDescription::I128),
-                "Address" => Ok(TypeDescription::Address),
-                "Balance" => Ok(TypeDescription::Balance),
-                unsupported => {
-                    bail!(
-                        ty,
-                        "{} is unsupported as message interface type",
-                        unsupported
-                    )
-                }
+impl TypeRegistry {
+    /// Registers `ty`, interning its definition and recursively registering
+    /// any types it is parametric over (tuple elements, array/`Vec`/`Option`
+    /// element types). Returns the `TypeId` it was assigned, reusing an
+    /// existing one if an identical type was already registered.
+    ///
+    /// The slot for `ty` is reserved before its nested types are resolved,
+    /// so a cyclic or self-referential type (e.g. a linked-list-shaped
+    /// struct) resolves to its own already-reserved `TypeId` instead of
+    /// recursing forever.
+    pub(crate) fn register(&mut self, ty: &syn::Type) -> Result<TypeId> {
+        let repr = Self::ty_repr(ty);
+        if let Some(&id) = self.by_repr.get(&repr) {
+            return Ok(TypeId(id))
+        }
+        let id = self.types.len() as u32;
+        self.by_repr.insert(repr.clone(), id);
+        self.types.push(TypeDef::Named { name: repr });
+        let def = self.resolve(ty)?;
+        self.types[id as usize] = def;
+        Ok(TypeId(id))
+    }
+
+    /// Returns the definition that `id` refers to.
+    pub(crate) fn resolve_id(&self, id: TypeId) -> &TypeDef {
+        &self.types[id.0 as usize]
+    }
+
+    /// Returns the Rust type tokens that `id` was derived from.
+    ///
+    /// # Note
+    ///
+    /// This is the inverse of `register` and is what makes it possible to
+    /// regenerate Rust bindings purely from a serialized
+    /// `ContractDescription`, without ever having had access to the
+    /// contract's original HIR.
+    pub(crate) fn as_rust_type(&self, id: TypeId) -> proc_macro2::TokenStream {
+        use quote::quote;
+        match self.resolve_id(id) {
+            TypeDef::Bool => quote! { bool },
+            TypeDef::U8 => quote! { u8 },
+            TypeDef::U16 => quote! { u16 },
+            TypeDef::U32 => quote! { u32 },
+            TypeDef::U64 => quote! { u64 },
+            TypeDef::U128 => quote! { u128 },
+            TypeDef::I8 => quote! { i8 },
+            TypeDef::I16 => quote! { i16 },
+            TypeDef::I32 => quote! { i32 },
+            TypeDef::I64 => quote! { i64 },
+            TypeDef::I128 => quote! { i128 },
+            TypeDef::Address => quote! { Address },
+            TypeDef::Balance => quote! { Balance },
+            TypeDef::Tuple { elems } => {
+                let elems = elems.iter().map(|id| self.as_rust_type(*id));
+                quote! { ( #(#elems ,)* ) }
             }
-        };
+            TypeDef::Array { inner, arity } => {
+                let inner = self.as_rust_type(*inner);
+                quote! { [ #inner ; #arity ] }
+            }
+            TypeDef::Vec { inner } => {
+                let inner = self.as_rust_type(*inner);
+                quote! { Vec<#inner> }
+            }
+            TypeDef::Option { inner } => {
+                let inner = self.as_rust_type(*inner);
+                quote! { Option<#inner> }
+            }
+            TypeDef::Named { name } => {
+                let ident: proc_macro2::TokenStream =
+                    name.parse().expect("a registered type name is always valid Rust syntax");
+                quote! { #ident }
+            }
+        }
+    }
+
+    /// Returns the stringified syntax tree of `ty`, used as the
+    /// deduplication key in `by_repr`.
+    fn ty_repr(ty: &syn::Type) -> String {
+        use quote::ToTokens;
+        ty.into_token_stream().to_string()
+    }
+
+    /// Resolves `ty`'s own definition, recursively registering any types it
+    /// is parametric over.
+    fn resolve(&mut self, ty: &syn::Type) -> Result<TypeDef> {
         match ty {
             syn::Type::Tuple(tuple) => {
                 let elems = tuple
                     .elems
                     .iter()
-                    .map(primitive)
+                    .map(|elem| self.register(elem))
                     .collect::<Result<_>>()?;
-                Ok(TypeDescription::Tuple { elems })
-            },
+                Ok(TypeDef::Tuple { elems })
+            }
             syn::Type::Array(array) => {
-                let inner = Box::new(primitive(&array.elem)?);
+                let inner = self.register(&array.elem)?;
                 if let syn::Expr::Lit(syn::ExprLit {
                     lit: syn::Lit::Int(ref int_lit), ..
                 }) = array.len {
-                    Ok(TypeDescription::Array {
+                    Ok(TypeDef::Array {
                         inner,
                         arity: int_lit.value() as u32,
                     })
@@ -134,7 +243,69 @@ impl TryFrom<&syn::Type> for TypeDescription {
                     )
                 }
             }
-            ty => primitive(ty),
+            syn::Type::Path(type_path) if type_path.qself.is_none() => {
+                let segment = type_path
+                    .path
+                    .segments
+                    .last()
+                    .expect("a path always has at least one segment")
+                    .value()
+                    .clone();
+                match segment.ident.to_owned_string().as_str() {
+                    "bool" => Ok(TypeDef::Bool),
+                    "u8" => Ok(TypeDef::U8),
+                    "u16" => Ok(TypeDef::U16),
+                    "u32" => Ok(TypeDef::U32),
+                    "u64" => Ok(TypeDef::U64),
+                    "u128" => Ok(TypeDef::U128),
+                    "i8" => Ok(TypeDef::I8),
+                    "i16" => Ok(TypeDef::I16),
+                    "i32" => Ok(TypeDef::I32),
+                    "i64" => Ok(TypeDef::I64),
+                    "i128" => Ok(TypeDef::I128),
+                    "Address" => Ok(TypeDef::Address),
+                    "Balance" => Ok(TypeDef::Balance),
+                    "Vec" => {
+                        let inner = self.register(Self::single_type_arg(&segment)?)?;
+                        Ok(TypeDef::Vec { inner })
+                    }
+                    "Option" => {
+                        let inner = self.register(Self::single_type_arg(&segment)?)?;
+                        Ok(TypeDef::Option { inner })
+                    }
+                    name => Ok(TypeDef::Named { name: name.to_string() }),
+                }
+            }
+            ty => {
+                bail!(
+                    ty,
+                    "unsupported as message interface type"
+                )
+            }
+        }
+    }
+
+    /// Returns the single type argument of a `Vec<T>`/`Option<T>`-shaped
+    /// path segment.
+    fn single_type_arg(segment: &syn::PathSegment) -> Result<&syn::Type> {
+        match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+                match args.args.first().expect("checked len == 1 above").value() {
+                    syn::GenericArgument::Type(ty) => Ok(ty),
+                    arg => {
+                        bail!(
+                            arg,
+                            "expected a single type argument"
+                        )
+                    }
+                }
+            }
+            _ => {
+                bail!(
+                    segment,
+                    "expected exactly one type argument"
+                )
+            }
         }
     }
 }
@@ -145,13 +316,23 @@ pub struct ParamDescription {
     /// The name of the parameter.
     name: String,
     /// The type of the parameter.
-    ty: TypeDescription,
+    ty: TypeId,
 }
 
-impl TryFrom<&syn::ArgCaptured> for ParamDescription {
-    type Error = Errors;
+impl ParamDescription {
+    /// Returns the name of the parameter.
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
 
-    fn try_from(arg: &syn::ArgCaptured) -> Result<Self> {
+    /// Returns the type of the parameter.
+    pub(crate) fn ty(&self) -> TypeId {
+        self.ty
+    }
+}
+
+impl ParamDescription {
+    fn try_from_arg(arg: &syn::ArgCaptured, registry: &mut TypeRegistry) -> Result<Self> {
         let name = match &arg.pat {
             syn::Pat::Ident(ident) => ident.ident.to_owned_string(),
             _ => {
@@ -160,22 +341,50 @@ impl TryFrom<&syn::ArgCaptured> for ParamDescription {
         };
         Ok(Self {
             name,
-            ty: TypeDescription::try_from(&arg.ty)?,
+            ty: registry.register(&arg.ty)?,
         })
     }
 }
 
-/// Describes the deploy handler of a contract.
+/// Describes a single named constructor of a contract.
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
-pub struct DeployDescription {
-    /// The parameters of the deploy handler.
+pub struct ConstructorDescription {
+    /// The name of the constructor.
+    name: String,
+    /// The selector hash of the constructor.
+    selector: u64,
+    /// If the constructor accepts value transferred along with the call.
+    payable: bool,
+    /// The parameters of the constructor.
     params: Vec<ParamDescription>,
 }
 
-impl TryFrom<&hir::DeployHandler> for DeployDescription {
-    type Error = Errors;
+impl ConstructorDescription {
+    /// Returns the name of the constructor.
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
 
-    fn try_from(deploy_handler: &hir::DeployHandler) -> Result<Self> {
+    /// Returns the selector hash of the constructor.
+    pub(crate) fn selector(&self) -> u64 {
+        self.selector
+    }
+
+    /// Returns `true` if the constructor accepts value transferred along
+    /// with the call.
+    pub(crate) fn payable(&self) -> bool {
+        self.payable
+    }
+
+    /// Returns the parameters of the constructor.
+    pub(crate) fn params(&self) -> &[ParamDescription] {
+        &self.params
+    }
+
+    fn try_from_handler(
+        deploy_handler: &hir::DeployHandler,
+        registry: &mut TypeRegistry,
+    ) -> Result<Self> {
         let params = deploy_handler
             .decl
             .inputs
@@ -183,41 +392,67 @@ impl TryFrom<&hir::DeployHandler> for DeployDescription {
             .filter_map(|arg| {
                 match arg {
                     ast::FnArg::Captured(captured) => {
-                        let description = ParamDescription::try_from(captured);
+                        let description = ParamDescription::try_from_arg(captured, registry);
                         Some(description)
                     }
                     _ => None,
                 }
             })
             .collect::<Result<Vec<_>>>()?;
-        Ok(Self { params })
+        Ok(Self {
+            // This ink! revision's HIR models a single implicit deploy
+            // handler with no identifier of its own; `generate_create`
+            // (the HIR-driven as-dependency generator) has always exposed
+            // it under the fixed name `new`, so this keeps that name
+            // instead of inventing one that would rename the public API.
+            name: "new".to_string(),
+            selector: selector_of("new"),
+            // This ink! revision has no `#[ink(payable)]` attribute, so the
+            // HIR carries no way to mark a constructor payable; default to
+            // `false` until that attribute exists.
+            payable: false,
+            params,
+        })
     }
 }
 
+/// Computes the selector of an identifier, taking the first four bytes of
+/// its BLAKE2-256 hash.
+///
+/// This is the same derivation `hir::Message::selector` uses for messages,
+/// applied here to constructors too so both share one selector scheme.
+fn selector_of(name: &str) -> u64 {
+    let mut output = [0u8; 32];
+    ink_core::env::hash_blake2_256(name.as_bytes(), &mut output);
+    u32::from_le_bytes([output[0], output[1], output[2], output[3]]) as u64
+}
+
 /// Describes the return type of a contract message.
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
-pub struct ReturnTypeDescription(Option<TypeDescription>);
+pub struct ReturnTypeDescription(Option<TypeId>);
 
 impl ReturnTypeDescription {
     /// Creates a new return type description from the given optional type.
     pub fn new<T>(opt_type: T) -> Self
     where
-        T: Into<Option<TypeDescription>>,
+        T: Into<Option<TypeId>>,
     {
         Self(opt_type.into())
     }
-}
 
-impl TryFrom<&syn::ReturnType> for ReturnTypeDescription {
-    type Error = Errors;
+    /// Returns the described return type, if any.
+    pub(crate) fn ty(&self) -> Option<TypeId> {
+        self.0
+    }
 
-    fn try_from(ret_ty: &syn::ReturnType) -> Result<Self> {
+    fn try_from_ret_ty(
+        ret_ty: &syn::ReturnType,
+        registry: &mut TypeRegistry,
+    ) -> Result<Self> {
         match ret_ty {
             syn::ReturnType::Default => Ok(ReturnTypeDescription::new(None)),
             syn::ReturnType::Type(_, ty) => {
-                Ok(ReturnTypeDescription::new(Some(TypeDescription::try_from(
-                    &**ty,
-                )?)))
+                Ok(ReturnTypeDescription::new(Some(registry.register(&**ty)?)))
             }
         }
     }
@@ -232,20 +467,55 @@ pub struct MessageDescription {
     selector: u64,
     /// If the message is allowed to mutate the contract state.
     mutates: bool,
+    /// If the message accepts value transferred along with the call.
+    payable: bool,
     /// The parameters of the message.
     params: Vec<ParamDescription>,
     /// The return type of the message.
     ret_ty: ReturnTypeDescription,
 }
 
-impl TryFrom<&hir::Message> for MessageDescription {
-    type Error = Errors;
+impl MessageDescription {
+    /// Returns the name of the message.
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the selector hash of the message.
+    pub(crate) fn selector(&self) -> u64 {
+        self.selector
+    }
 
-    fn try_from(message: &hir::Message) -> Result<Self> {
+    /// Returns `true` if the message is allowed to mutate the contract state.
+    pub(crate) fn mutates(&self) -> bool {
+        self.mutates
+    }
+
+    /// Returns `true` if the message accepts value transferred along with
+    /// the call.
+    pub(crate) fn payable(&self) -> bool {
+        self.payable
+    }
+
+    /// Returns the parameters of the message.
+    pub(crate) fn params(&self) -> &[ParamDescription] {
+        &self.params
+    }
+
+    /// Returns the return type of the message.
+    pub(crate) fn ret_ty(&self) -> &ReturnTypeDescription {
+        &self.ret_ty
+    }
+
+    fn try_from_message(message: &hir::Message, registry: &mut TypeRegistry) -> Result<Self> {
         Ok(Self {
             name: message.sig.ident.to_owned_string(),
             selector: message.selector().into(),
             mutates: message.is_mut(),
+            // This ink! revision has no `#[ink(payable)]` attribute, so the
+            // HIR carries no way to mark a message payable; default to
+            // `false` until that attribute exists.
+            payable: false,
             params: {
                 message
                     .sig
@@ -255,14 +525,89 @@ impl TryFrom<&hir::Message> for MessageDescription {
                     .filter_map(|arg| {
                         match arg {
                             ast::FnArg::Captured(captured) => {
-                                Some(ParamDescription::try_from(captured))
+                                Some(ParamDescription::try_from_arg(captured, registry))
                             }
                             _ => None,
                         }
                     })
                     .collect::<Result<Vec<_>>>()?
             },
-            ret_ty: ReturnTypeDescription::try_from(&message.sig.decl.output)?,
+            ret_ty: ReturnTypeDescription::try_from_ret_ty(&message.sig.decl.output, registry)?,
+        })
+    }
+}
+
+/// Describes a single field of an event.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EventArgDescription {
+    /// The name and type of the field.
+    param: ParamDescription,
+    /// If this field is emitted as an indexed topic rather than as part of
+    /// the event's data payload.
+    indexed: bool,
+}
+
+impl EventArgDescription {
+    /// Returns the name and type of the field.
+    pub(crate) fn param(&self) -> &ParamDescription {
+        &self.param
+    }
+
+    /// Returns `true` if this field is emitted as an indexed topic.
+    pub(crate) fn indexed(&self) -> bool {
+        self.indexed
+    }
+
+    fn try_from_field(field: &hir::EventField, registry: &mut TypeRegistry) -> Result<Self> {
+        Ok(Self {
+            param: ParamDescription {
+                name: field.ident.to_owned_string(),
+                ty: registry.register(&field.ty)?,
+            },
+            indexed: field.is_topic,
+        })
+    }
+}
+
+/// Describes a contract event.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EventDescription {
+    /// The name of the event.
+    name: String,
+    /// The event's fields, in declaration order.
+    args: Vec<EventArgDescription>,
+}
+
+impl EventDescription {
+    /// Returns the name of the event.
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the event's fields, in declaration order.
+    pub(crate) fn args(&self) -> &[EventArgDescription] {
+        &self.args
+    }
+
+    /// Builds an `EventDescription` from `event`, registering its fields'
+    /// types in `registry`.
+    ///
+    /// # Note
+    ///
+    /// This is an inherent method rather than a `TryFrom<&hir::Event>` impl
+    /// because it needs to thread a `&mut TypeRegistry` through to register
+    /// each field's type, the same reason `MessageDescription` and
+    /// `ConstructorDescription` use `try_from_message`/`try_from_handler`
+    /// instead of `TryFrom`.
+    fn try_from_event(event: &hir::Event, registry: &mut TypeRegistry) -> Result<Self> {
+        let args = event
+            .fields
+            .iter()
+            .map(|field| EventArgDescription::try_from_field(field, registry))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            name: event.ident.to_owned_string(),
+            args,
         })
     }
 }
@@ -272,10 +617,21 @@ impl TryFrom<&hir::Message> for MessageDescription {
 pub struct ContractDescription {
     /// The name of the contract.
     name: String,
-    /// The deploy handler of the contract.
-    deploy: DeployDescription,
+    /// The named constructors of the contract.
+    ///
+    /// # Note
+    ///
+    /// This ink! revision's HIR still models only a single implicit deploy
+    /// handler, always named `new`, so this always holds exactly one entry
+    /// until the HIR itself grows support for multiple named constructors.
+    constructors: Vec<ConstructorDescription>,
     /// The external messages of the contract.
     messages: Vec<MessageDescription>,
+    /// The events the contract may emit.
+    events: Vec<EventDescription>,
+    /// Every type referenced by a constructor's, message's, or event's
+    /// params/return/field types, interned and deduplicated by `TypeId`.
+    types: TypeRegistry,
 }
 
 impl ContractDescription {
@@ -283,35 +639,176 @@ impl ContractDescription {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// Returns the named constructors of the contract.
+    pub(crate) fn constructors(&self) -> &[ConstructorDescription] {
+        &self.constructors
+    }
+
+    /// Returns the external messages of the contract.
+    pub(crate) fn messages(&self) -> &[MessageDescription] {
+        &self.messages
+    }
+
+    /// Returns the events the contract may emit.
+    pub(crate) fn events(&self) -> &[EventDescription] {
+        &self.events
+    }
+
+    /// Returns the type registry backing every param/return `TypeId` in this
+    /// description.
+    pub(crate) fn types(&self) -> &TypeRegistry {
+        &self.types
+    }
 }
 
 impl TryFrom<&hir::Contract> for ContractDescription {
     type Error = Errors;
 
     fn try_from(contract: &hir::Contract) -> Result<Self> {
+        let mut types = TypeRegistry::default();
+        let constructors =
+            vec![ConstructorDescription::try_from_handler(&contract.on_deploy, &mut types)?];
+        let messages = contract
+            .messages
+            .iter()
+            .map(|message| MessageDescription::try_from_message(message, &mut types))
+            .collect::<Result<Vec<_>>>()?;
+        let events = contract
+            .events
+            .iter()
+            .map(|event| EventDescription::try_from_event(event, &mut types))
+            .collect::<Result<Vec<_>>>()?;
         Ok(ContractDescription {
             name: contract.name.to_owned_string(),
-            deploy: DeployDescription::try_from(&contract.on_deploy)?,
-            messages: {
-                contract
-                    .messages
-                    .iter()
-                    .map(MessageDescription::try_from)
-                    .collect::<Result<Vec<_>>>()?
-            },
+            constructors,
+            messages,
+            events,
+            types,
+        })
+    }
+}
+
+/// Describes where a contract's compiled Wasm blob came from.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SourceMetadata {
+    /// The BLAKE2-256 hash of the compiled Wasm blob.
+    hash: [u8; 32],
+    /// The ink! language version the contract was compiled against.
+    language: String,
+    /// The `rustc` compiler version used to compile the contract.
+    compiler: String,
+}
+
+/// Describes the crate a contract was compiled from.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ContractMetadata {
+    /// The name of the contract crate.
+    name: String,
+    /// The semantic version of the contract crate.
+    version: Version,
+    /// The authors of the contract crate.
+    authors: Vec<String>,
+    /// The description of the contract crate, if any.
+    description: Option<String>,
+    /// The license of the contract crate, if any.
+    license: Option<String>,
+    /// The repository URL of the contract crate, if any.
+    repository: Option<Url>,
+}
+
+impl ContractMetadata {
+    /// Reads the `[package]` table of the contract crate's own `Cargo.toml`,
+    /// located via the `CARGO_MANIFEST_DIR` environment variable that cargo
+    /// sets for every crate it builds.
+    fn from_cargo_manifest() -> Result<Self> {
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+            .expect("CARGO_MANIFEST_DIR is always set by cargo at build time");
+        let manifest_path = std::path::Path::new(&manifest_dir).join("Cargo.toml");
+        let manifest_contents = std::fs::read_to_string(&manifest_path)
+            .expect("Failed at reading the contract crate's Cargo.toml");
+        let manifest: CargoManifest = toml::from_str(&manifest_contents)
+            .expect("Failed at parsing the contract crate's Cargo.toml");
+        let version = Version::parse(&manifest.package.version)
+            .expect("cargo enforces that `version` is valid semver");
+        let repository = manifest
+            .package
+            .repository
+            .map(|repository| {
+                Url::parse(&repository).expect("`repository` must be a valid URL")
+            });
+        Ok(Self {
+            name: manifest.package.name,
+            version,
+            authors: manifest.package.authors,
+            description: manifest.package.description,
+            license: manifest.package.license,
+            repository,
         })
     }
 }
 
-/// Writes a JSON API description into the `target/` folder.
-pub fn generate_api_description(contract: &hir::Contract) -> Result<()> {
-    let description = ContractDescription::try_from(contract)?;
-    let contents = serde_json::to_string(&description)
-        .expect("Failed at generating JSON API description as JSON");
+/// The `[package]` table of a `Cargo.toml`, as read by `ContractMetadata`.
+#[derive(Deserialize)]
+struct CargoManifest {
+    package: CargoPackage,
+}
+
+/// The subset of `Cargo.toml`'s `[package]` fields `ContractMetadata` cares
+/// about.
+#[derive(Deserialize)]
+struct CargoPackage {
+    name: String,
+    version: String,
+    #[serde(default)]
+    authors: Vec<String>,
+    description: Option<String>,
+    license: Option<String>,
+    repository: Option<String>,
+}
+
+/// The full metadata bundle written to `target/<name>.json`.
+///
+/// Wraps the raw ABI under `spec` together with `source` and `contract`
+/// provenance, matching the shape `cargo-contract`'s metadata crate
+/// produces, so downstream tooling can consume both provenance and
+/// interface from one file.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MetadataBundle {
+    /// Where the compiled Wasm blob came from.
+    source: SourceMetadata,
+    /// The crate the contract was compiled from.
+    contract: ContractMetadata,
+    /// The contract's ABI.
+    spec: ContractDescription,
+}
+
+/// Writes a JSON metadata bundle into the `target/` folder.
+///
+/// # Note
+///
+/// `wasm_hash` must be supplied by the caller: this function runs as part
+/// of generating the contract's interface description, before its Wasm
+/// blob has been compiled, so it has no blob of its own to hash.
+pub fn generate_api_description(contract: &hir::Contract, wasm_hash: [u8; 32]) -> Result<()> {
+    let spec = ContractDescription::try_from(contract)?;
+    let bundle = MetadataBundle {
+        source: SourceMetadata {
+            hash: wasm_hash,
+            language: format!("ink! {}", env!("CARGO_PKG_VERSION")),
+            compiler: rustc_version::version()
+                .expect("Failed at determining the rustc compiler version")
+                .to_string(),
+        },
+        contract: ContractMetadata::from_cargo_manifest()?,
+        spec,
+    };
+    let contents = serde_json::to_string(&bundle)
+        .expect("Failed at generating JSON metadata bundle");
     let mut path_buf = String::from("target/");
-    path_buf.push_str(description.name());
+    path_buf.push_str(bundle.contract.name.as_str());
     path_buf.push_str(".json");
     std::fs::write(path_buf, contents)
-        .expect("Failed at writing JSON API descrition to file");
+        .expect("Failed at writing JSON metadata bundle to file");
     Ok(())
 }
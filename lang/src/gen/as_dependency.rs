@@ -23,11 +23,13 @@
 //! replaced by direct forwards to the remote call infrastructure going through SRML contracts.
 
 use crate::{
+    api::ContractDescription,
     ast,
     hir,
 };
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{
+    format_ident,
     quote,
     quote_spanned,
 };
@@ -89,11 +91,43 @@ fn generate_state_as_dependency(contract: &hir::Contract) -> TokenStream2 {
         /// Allows to enhance calls to `&self` contract messages.
         pub struct CallEnhancer<'a> {
             contract: &'a #name,
+            gas_limit: Option<u64>,
+            transferred_value: Option<Balance>,
         }
 
         /// Allows to enhance calls to `&mut self` contract messages.
         pub struct CallEnhancerMut<'a> {
             contract: &'a mut #name,
+            gas_limit: Option<u64>,
+            transferred_value: Option<Balance>,
+        }
+
+        impl<'a> CallEnhancer<'a> {
+            /// Sets the gas limit applied to every subsequent call.
+            pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+                self.gas_limit = Some(gas_limit);
+                self
+            }
+
+            /// Sets the balance transferred along with every subsequent call.
+            pub fn with_value(mut self, value: Balance) -> Self {
+                self.transferred_value = Some(value);
+                self
+            }
+        }
+
+        impl<'a> CallEnhancerMut<'a> {
+            /// Sets the gas limit applied to every subsequent call.
+            pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+                self.gas_limit = Some(gas_limit);
+                self
+            }
+
+            /// Sets the balance transferred along with every subsequent call.
+            pub fn with_value(mut self, value: Balance) -> Self {
+                self.transferred_value = Some(value);
+                self
+            }
         }
 
         impl ink_core::env::FromAccountId<Env> for #name {
@@ -107,12 +141,20 @@ fn generate_state_as_dependency(contract: &hir::Contract) -> TokenStream2 {
 
             /// Allows to enhance calls to `&self` contract messages.
             pub fn call(&self) -> CallEnhancer {
-                CallEnhancer { contract: self }
+                CallEnhancer {
+                    contract: self,
+                    gas_limit: None,
+                    transferred_value: None,
+                }
             }
 
             /// Allows to enhance calls to `&mut self` contract messages.
             pub fn call_mut(&mut self) -> CallEnhancerMut {
-                CallEnhancerMut { contract: self }
+                CallEnhancerMut {
+                    contract: self,
+                    gas_limit: None,
+                    transferred_value: None,
+                }
             }
         }
     }
@@ -132,9 +174,16 @@ fn generate_create(contract: &hir::Contract) -> TokenStream2 {
         #(#attrs)*
         pub fn new(
             code_hash: Hash,
+            endowment: Balance,
+            salt: Option<[u8; 32]>,
             #(#args ,)*
         ) -> ink_core::env::CreateBuilder<Env, Self> {
-            ink_core::env::CreateBuilder::<Env, Self>::new(code_hash)
+            let mut builder = ink_core::env::CreateBuilder::<Env, Self>::new(code_hash)
+                .endowment(endowment);
+            if let Some(salt) = salt {
+                builder = builder.salt(salt);
+            }
+            builder
             #(
                 .push_arg(&#inputs)
             )*
@@ -165,6 +214,7 @@ fn generate_messages_as_dependency<'a>(
         let output = &message.sig.decl.output;
         let (_impl_generics, type_generics, where_clause) =
             message.sig.decl.generics.split_for_impl();
+        let try_ident = format_ident!("try_{}", ident);
         match output {
             syn::ReturnType::Default => {
                 quote_spanned! { ident.span() =>
@@ -180,6 +230,17 @@ fn generate_messages_as_dependency<'a>(
                                 stringify!(#contract_ident), "::", stringify!(#ident),
                                 " message was invalid"))
                     }
+
+                    /// Same as the non-`try_` variant of this message but does not panic
+                    /// on a reverted or trapped callee, instead propagating the
+                    /// error to the caller.
+                    #(#attrs)*
+                    pub fn #try_ident #type_generics (
+                        #self_arg ,
+                        #(#args ,)*
+                    ) -> core::result::Result<(), ink_core::env::CallError> #where_clause {
+                        self.#call_fn.#ident( #(#inputs ,)* ).fire()
+                    }
                 }
             }
             syn::ReturnType::Type(_, ty) => {
@@ -196,12 +257,438 @@ fn generate_messages_as_dependency<'a>(
                                 stringify!(#contract_ident), "::", stringify!(#ident),
                                 " message was invalid"))
                     }
+
+                    /// Same as the non-`try_` variant of this message but does not panic
+                    /// on a reverted or trapped callee, instead propagating the
+                    /// error to the caller.
+                    #(#attrs)*
+                    pub fn #try_ident #type_generics (
+                        #self_arg ,
+                        #(#args ,)*
+                    ) -> core::result::Result<#ty, ink_core::env::CallError> #where_clause {
+                        self.#call_fn.#ident( #(#inputs ,)* ).fire()
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Generates the as-dependency bindings directly from a contract's ABI/metadata.
+///
+/// # Note
+///
+/// This mirrors [`generate_code`] but does not require access to the
+/// contract's Rust HIR: it is driven entirely by a [`ContractDescription`],
+/// so it also works for contracts that are only available as a serialized
+/// metadata bundle, e.g. one fetched from a chain explorer or shipped
+/// alongside a compiled `.wasm` blob without its source.
+pub fn generate_code_from_description(
+    tokens: &mut TokenStream2,
+    description: &ContractDescription,
+) {
+    let messages = generate_messages_from_description(description);
+    let call_enhancer_messages =
+        generate_call_enhancer_messages_from_description(description, Mutability::Immutable);
+    let call_enhancer_mut_messages =
+        generate_call_enhancer_messages_from_description(description, Mutability::Mutable);
+    let state = generate_state_from_description(description);
+    let contract_ident = format_ident!("{}", description.name());
+
+    tokens.extend(quote! {
+        #[cfg(feature = "ink-as-dependency")]
+        mod as_dependency {
+            use super::*;
+
+            #state
+
+            impl #contract_ident {
+                #(#messages)*
+            }
+
+            impl<'a> CallEnhancer<'a> {
+                #(#call_enhancer_messages)*
+            }
+
+            impl<'a> CallEnhancerMut<'a> {
+                #(#call_enhancer_mut_messages)*
+            }
+        }
+
+        #[cfg(feature = "ink-as-dependency")]
+        pub use as_dependency::{
+            #contract_ident,
+            CallEnhancer,
+            CallEnhancerMut,
+        };
+    });
+}
+
+/// Generates as-dependency bindings for a whole cluster of interacting
+/// ink! contracts in one cohesive module.
+///
+/// # Note
+///
+/// Calling [`generate_code_from_description`] once per contract and
+/// concatenating the results does not work: every contract's generator
+/// unconditionally defines `mod as_dependency { .. CallEnhancer .. }`, so a
+/// second contract's output collides with the first. This function instead
+/// namespaces each contract's generated module under its own identifier
+/// (e.g. a contract named `Erc20` gets a private `erc20_dependency` module)
+/// and only re-exports the contract's own state type at the top level,
+/// leaving `CallEnhancer`/`CallEnhancerMut` reachable as
+/// `<module>::CallEnhancer` without clashing between contracts.
+///
+/// Each contract's [`TypeRegistry`](crate::api::TypeRegistry) only interns
+/// types referenced by that one contract, so even two contracts sharing a
+/// named struct/enum end up with it registered twice, once per registry;
+/// namespacing the generated modules is what prevents the collisions this
+/// batch generator exists to avoid.
+pub fn generate_code_for_contracts(
+    tokens: &mut TokenStream2,
+    descriptions: &[ContractDescription],
+) {
+    for description in descriptions {
+        let mod_ident = format_ident!("{}_dependency", to_snake_case(description.name()));
+        let contract_ident = format_ident!("{}", description.name());
+
+        let messages = generate_messages_from_description(description);
+        let call_enhancer_messages = generate_call_enhancer_messages_from_description(
+            description,
+            Mutability::Immutable,
+        );
+        let call_enhancer_mut_messages =
+            generate_call_enhancer_messages_from_description(description, Mutability::Mutable);
+        let state = generate_state_from_description(description);
+
+        tokens.extend(quote! {
+            #[cfg(feature = "ink-as-dependency")]
+            mod #mod_ident {
+                use super::*;
+
+                #state
+
+                impl #contract_ident {
+                    #(#messages)*
+                }
+
+                impl<'a> CallEnhancer<'a> {
+                    #(#call_enhancer_messages)*
+                }
+
+                impl<'a> CallEnhancerMut<'a> {
+                    #(#call_enhancer_mut_messages)*
+                }
+            }
+
+            #[cfg(feature = "ink-as-dependency")]
+            pub use #mod_ident::#contract_ident;
+        });
+    }
+}
+
+/// Converts a `PascalCase` contract name into a `snake_case` identifier
+/// fragment suitable for use as a module name.
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len());
+    for (i, ch) in name.char_indices() {
+        if ch.is_uppercase() {
+            if i != 0 {
+                snake.push('_');
+            }
+            snake.extend(ch.to_lowercase());
+        } else {
+            snake.push(ch);
+        }
+    }
+    snake
+}
+
+fn generate_state_from_description(description: &ContractDescription) -> TokenStream2 {
+    let name = format_ident!("{}", description.name());
+    let create = generate_create_from_description(description);
+    quote! {
+        pub struct #name {
+            account_id: AccountId,
+        }
+
+        /// Allows to enhance calls to `&self` contract messages.
+        pub struct CallEnhancer<'a> {
+            contract: &'a #name,
+            gas_limit: Option<u64>,
+            transferred_value: Option<Balance>,
+        }
+
+        /// Allows to enhance calls to `&mut self` contract messages.
+        pub struct CallEnhancerMut<'a> {
+            contract: &'a mut #name,
+            gas_limit: Option<u64>,
+            transferred_value: Option<Balance>,
+        }
+
+        impl<'a> CallEnhancer<'a> {
+            /// Sets the gas limit applied to every subsequent call.
+            pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+                self.gas_limit = Some(gas_limit);
+                self
+            }
+
+            /// Sets the balance transferred along with every subsequent call.
+            pub fn with_value(mut self, value: Balance) -> Self {
+                self.transferred_value = Some(value);
+                self
+            }
+        }
+
+        impl<'a> CallEnhancerMut<'a> {
+            /// Sets the gas limit applied to every subsequent call.
+            pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+                self.gas_limit = Some(gas_limit);
+                self
+            }
+
+            /// Sets the balance transferred along with every subsequent call.
+            pub fn with_value(mut self, value: Balance) -> Self {
+                self.transferred_value = Some(value);
+                self
+            }
+        }
+
+        impl ink_core::env::FromAccountId<Env> for #name {
+            fn from_account_id(account_id: AccountId) -> Self {
+                Self { account_id }
+            }
+        }
+
+        impl #name {
+            #create
+
+            /// Allows to enhance calls to `&self` contract messages.
+            pub fn call(&self) -> CallEnhancer {
+                CallEnhancer {
+                    contract: self,
+                    gas_limit: None,
+                    transferred_value: None,
+                }
+            }
+
+            /// Allows to enhance calls to `&mut self` contract messages.
+            pub fn call_mut(&mut self) -> CallEnhancerMut {
+                CallEnhancerMut {
+                    contract: self,
+                    gas_limit: None,
+                    transferred_value: None,
+                }
+            }
+        }
+    }
+}
+
+fn generate_create_from_description(description: &ContractDescription) -> TokenStream2 {
+    let constructors = description.constructors().iter().map(|constructor| {
+        let ident = format_ident!("{}", constructor.name());
+        let params = constructor.params();
+        let args = params.iter().map(|param| {
+            let ident = format_ident!("{}", param.name());
+            let ty = description.types().as_rust_type(param.ty());
+            quote! { #ident: #ty }
+        });
+        let inputs = params.iter().map(|param| {
+            let ident = format_ident!("{}", param.name());
+            quote! { #ident }
+        });
+        quote! {
+            pub fn #ident(
+                code_hash: Hash,
+                endowment: Balance,
+                salt: Option<[u8; 32]>,
+                #(#args ,)*
+            ) -> ink_core::env::CreateBuilder<Env, Self> {
+                let mut builder = ink_core::env::CreateBuilder::<Env, Self>::new(code_hash)
+                    .endowment(endowment);
+                if let Some(salt) = salt {
+                    builder = builder.salt(salt);
+                }
+                builder
+                #(
+                    .push_arg(&#inputs)
+                )*
+            }
+        }
+    });
+    quote! { #(#constructors)* }
+}
+
+fn generate_messages_from_description<'a>(
+    description: &'a ContractDescription,
+) -> impl Iterator<Item = TokenStream2> + 'a {
+    description.messages().iter().map(move |message| {
+        let ident = format_ident!("{}", message.name());
+        let self_arg = if message.mutates() {
+            quote! { &mut self }
+        } else {
+            quote! { &self }
+        };
+        let call_fn = if message.mutates() {
+            quote! { call_mut() }
+        } else {
+            quote! { call() }
+        };
+        let args = message.params().iter().map(|param| {
+            let ident = format_ident!("{}", param.name());
+            let ty = description.types().as_rust_type(param.ty());
+            quote! { #ident: #ty }
+        });
+        let inputs = message.params().iter().map(|param| {
+            let ident = format_ident!("{}", param.name());
+            quote! { #ident }
+        });
+        let message_name = message.name();
+        let try_ident = format_ident!("try_{}", message.name());
+        match message.ret_ty().ty() {
+            None => {
+                let args2 = message.params().iter().map(|param| {
+                    let ident = format_ident!("{}", param.name());
+                    let ty = description.types().as_rust_type(param.ty());
+                    quote! { #ident: #ty }
+                });
+                let inputs2 = message.params().iter().map(|param| {
+                    let ident = format_ident!("{}", param.name());
+                    quote! { #ident }
+                });
+                quote! {
+                    pub fn #ident(
+                        #self_arg ,
+                        #(#args ,)*
+                    ) {
+                        self.#call_fn.#ident( #(#inputs ,)* )
+                            .fire()
+                            .expect(concat!(
+                                "invocation of as-dependency message ",
+                                #message_name,
+                                " was invalid"))
+                    }
+
+                    /// Same as the non-`try_` variant of this message but does not panic
+                    /// on a reverted or trapped callee, instead propagating the
+                    /// error to the caller.
+                    pub fn #try_ident(
+                        #self_arg ,
+                        #(#args2 ,)*
+                    ) -> core::result::Result<(), ink_core::env::CallError> {
+                        self.#call_fn.#ident( #(#inputs2 ,)* ).fire()
+                    }
+                }
+            }
+            Some(ty) => {
+                let ty = description.types().as_rust_type(ty);
+                let args2 = message.params().iter().map(|param| {
+                    let ident = format_ident!("{}", param.name());
+                    let ty = description.types().as_rust_type(param.ty());
+                    quote! { #ident: #ty }
+                });
+                let inputs2 = message.params().iter().map(|param| {
+                    let ident = format_ident!("{}", param.name());
+                    quote! { #ident }
+                });
+                quote! {
+                    pub fn #ident(
+                        #self_arg ,
+                        #(#args ,)*
+                    ) -> #ty {
+                        self.#call_fn.#ident( #(#inputs ,)* )
+                            .fire()
+                            .expect(concat!(
+                                "evaluation of as-dependency message ",
+                                #message_name,
+                                " was invalid"))
+                    }
+
+                    /// Same as the non-`try_` variant of this message but does not panic
+                    /// on a reverted or trapped callee, instead propagating the
+                    /// error to the caller.
+                    pub fn #try_ident(
+                        #self_arg ,
+                        #(#args2 ,)*
+                    ) -> core::result::Result<#ty, ink_core::env::CallError> {
+                        self.#call_fn.#ident( #(#inputs2 ,)* ).fire()
+                    }
                 }
             }
         }
     })
 }
 
+fn generate_call_enhancer_messages_from_description<'a>(
+    description: &'a ContractDescription,
+    mutability: Mutability,
+) -> impl Iterator<Item = TokenStream2> + 'a {
+    description
+        .messages()
+        .iter()
+        .filter(move |message| {
+            if mutability == Mutability::Mutable {
+                message.mutates()
+            } else {
+                !message.mutates()
+            }
+        })
+        .map(|message| {
+            let ident = format_ident!("{}", message.name());
+            let args = message.params().iter().map(|param| {
+                let ident = format_ident!("{}", param.name());
+                let ty = description.types().as_rust_type(param.ty());
+                quote! { #ident: #ty }
+            });
+            let inputs = message.params().iter().map(|param| {
+                let ident = format_ident!("{}", param.name());
+                quote! { #ident }
+            });
+            let selector = message.selector();
+            match message.ret_ty().ty() {
+                None => quote! {
+                    pub fn #ident(
+                        self,
+                        #(#args ,)*
+                    ) -> ink_core::env::CallBuilder<Env, ()> {
+                        let mut builder = ink_core::env::CallBuilder::<Env, ()>::invoke(self.contract.account_id.clone(), #selector);
+                        if let Some(gas_limit) = self.gas_limit {
+                            builder = builder.gas_limit(gas_limit);
+                        }
+                        if let Some(transferred_value) = self.transferred_value {
+                            builder = builder.transferred_value(transferred_value);
+                        }
+                        builder
+                            #(
+                                .push_arg(&#inputs)
+                            )*
+                    }
+                },
+                Some(ty) => {
+                    let ty = description.types().as_rust_type(ty);
+                    quote! {
+                        pub fn #ident(
+                            self,
+                            #(#args ,)*
+                        ) -> ink_core::env::CallBuilder<Env, ink_core::env::ReturnType<#ty>> {
+                            let mut builder = ink_core::env::CallBuilder::eval(self.contract.account_id.clone(), #selector);
+                            if let Some(gas_limit) = self.gas_limit {
+                                builder = builder.gas_limit(gas_limit);
+                            }
+                            if let Some(transferred_value) = self.transferred_value {
+                                builder = builder.transferred_value(transferred_value);
+                            }
+                            builder
+                                #(
+                                    .push_arg(&#inputs)
+                                )*
+                        }
+                    }
+                }
+            }
+        })
+}
+
 fn generate_call_enhancer_messages<'a>(
     contract: &'a hir::Contract,
     mutability: Mutability,
@@ -233,7 +720,14 @@ fn generate_call_enhancer_messages<'a>(
                         self,
                         #(#args ,)*
                     ) -> ink_core::env::CallBuilder<Env, ()> #where_clause {
-                        ink_core::env::CallBuilder::<Env, ()>::invoke(self.contract.account_id.clone(), #selector)
+                        let mut builder = ink_core::env::CallBuilder::<Env, ()>::invoke(self.contract.account_id.clone(), #selector);
+                        if let Some(gas_limit) = self.gas_limit {
+                            builder = builder.gas_limit(gas_limit);
+                        }
+                        if let Some(transferred_value) = self.transferred_value {
+                            builder = builder.transferred_value(transferred_value);
+                        }
+                        builder
                             #(
                                 .push_arg(&#inputs)
                             )*
@@ -245,7 +739,14 @@ fn generate_call_enhancer_messages<'a>(
                         self,
                         #(#args ,)*
                     ) -> ink_core::env::CallBuilder<Env, ink_core::env::ReturnType<#ty>> #where_clause {
-                        ink_core::env::CallBuilder::eval(self.contract.account_id.clone(), #selector)
+                        let mut builder = ink_core::env::CallBuilder::eval(self.contract.account_id.clone(), #selector);
+                        if let Some(gas_limit) = self.gas_limit {
+                            builder = builder.gas_limit(gas_limit);
+                        }
+                        if let Some(transferred_value) = self.transferred_value {
+                            builder = builder.transferred_value(transferred_value);
+                        }
+                        builder
                             #(
                                 .push_arg(&#inputs)
                             )*